@@ -1,35 +1,284 @@
 """Type stubs for difflib_rs - Rust implementation of Python's difflib.unified_diff"""
 
-from typing import List, Optional
+from typing import Any, Callable, Dict, Iterable, List, Optional, TextIO, Tuple, TypedDict, Union
+
+# (tag, i1, i2, j1, j2): tag turns a[i1:i2] into b[j1:j2]. Indices are
+# 0-based and half-open like Python slices unless a function is called with
+# one_based=True, which reports 1-based inclusive line ranges as patch
+# formats use: (i1 + 1, i2, j1 + 1, j2), so an empty side (i1 == i2) becomes
+# (i1 + 1, i1), an empty range after line i1.
+OpCode = Tuple[str, int, int, int, int]
+
+__version__: str
 
 def unified_diff(
-    a: List[str],
-    b: List[str],
+    a: Iterable[str],
+    b: Iterable[str],
     fromfile: str = "",
     tofile: str = "",
     fromfiledate: str = "",
     tofiledate: str = "",
     n: int = 3,
-    lineterm: str = "\n"
+    lineterm: str = "\n",
+    track_final_newline: bool = False,
+    *,
+    brace_scope_context: Optional[int] = None,
+    window_size: Optional[int] = None,
+    isjunk: Optional[Callable[[str], bool]] = None,
+    symmetric_junk: bool = False,
+    compact: bool = False,
+    autojunk: bool = True,
+    validate: bool = False,
+    min_ratio: Optional[float] = None,
+    adaptive_context: Optional[Tuple[int, int]] = None,
+    max_occurrences_per_element: Optional[int] = None,
+    comment_prefixes: Optional[List[str]] = None,
+    block_comment: Optional[Tuple[str, str]] = None,
+    ignore_comment_changes: bool = False,
+    progress: Optional[Callable[[float], object]] = None,
+    max_line_width: Optional[int] = None,
+    numeric_tolerance: Optional[Tuple[float, float]] = None,
+    hunk_checksums: bool = False,
+    unique_anchors: bool = False,
+    embed_base: bool = False,
+    normalize: Optional[List[str]] = None,
+    show_line_numbers: bool = False,
+    gap_penalty: Optional[float] = None,
+    reverse_hunks: bool = False,
+    max_output_bytes: Optional[int] = None,
+    min_hunk_lines: Optional[int] = None,
+    elide_equal_over: Optional[int] = None,
+    neighbor_tiebreak: bool = False,
+    auto_context: Optional[int] = None,
+    strip_common_affixes: bool = False,
+    dedent: bool = False
 ) -> List[str]:
     """
     Compare two sequences of lines; generate the unified diff.
     
     Args:
-        a: First sequence of lines
-        b: Second sequence of lines
+        a: First sequence of lines, as a list or any iterable of str such
+            as a generator or an open file. Both a and b are read in full
+            before diffing: b to index its lines, and a because matching
+            revisits its ranges
+        b: Second sequence of lines, likewise
         fromfile: Name of the first file
         tofile: Name of the second file
         fromfiledate: Timestamp for the first file
         tofiledate: Timestamp for the second file
         n: Number of context lines
         lineterm: Line terminator string
+        brace_scope_context: If set, add up to this many extra context lines
+            so hunks don't split a brace-delimited block (lines ending in
+            `{` open a scope, lines starting with `}` close one)
+        window_size: If set, diff in overlapping windows of this many lines
+            so matcher memory is bounded by the window instead of the file.
+            Changes spanning a window boundary may be slightly less minimal
+            than a whole-file diff.
+        isjunk: Lines for which this returns true never anchor a match but
+            may extend one, as with SequenceMatcher's isjunk
+        symmetric_junk: Also treat lines popular in either sequence as junk,
+            so e.g. blank lines common to both files extend matches without
+            anchoring them
+        compact: Also merge hunks separated by up to 3*n unchanged lines
+            (instead of 2*n), trading a few context lines for fewer headers
+        autojunk: Ignore lines making up more than 1% of b (when b has 200
+            or more lines) as match anchors, as with SequenceMatcher's
+            autojunk
+        track_final_newline: If the last line of a or b doesn't end in a
+            line break, append an empty sentinel line to it, so adding or
+            removing the final newline is reported as an inserted or deleted
+            empty line. Only meaningful for lines that keep their endings,
+            e.g. from readlines()
+        validate: Check that the hunks form a gap-free, non-overlapping walk
+            of both sequences before rendering them, raising ValueError
+            instead of returning a malformed diff
+        min_ratio: If set, compute the similarity ratio of a and b first
+            and, when it is below this value, return the single line
+            "@@ files differ substantially (ratio=0.12) @@" instead of the
+            diff. Must be in [0.0, 1.0]
+        adaptive_context: A (min, max) pair that replaces n with context
+            sized per cluster of changes: changes fewer than 2 * max equal
+            lines apart form a cluster, and a cluster of k changes gets
+            min + (max - min) // k lines of context. A lone change gets max
+            lines, crowded changes approach min
+        max_occurrences_per_element: If set, scan at most this many
+            positions of each line in b (the first ones in the region being
+            matched) when looking for the longest match. A middle ground
+            between autojunk=True, which stops popular lines from anchoring
+            matches at all, and autojunk=False, which can be quadratic on
+            very repetitive input: popular lines still line up, but a match
+            starting at a later occurrence can be missed, giving a less
+            minimal diff. Must be positive
+        comment_prefixes: Prefixes of line comments, e.g. ["#", "//"].
+            Lines that are comment-only after trimming whitespace never
+            anchor a match but may extend one, as with isjunk. Prefixes
+            must not be empty
+        block_comment: Opening and closing delimiters of block comments,
+            e.g. ("/*", "*/"). Lines inside a block comment, or holding
+            only comments, are comment-only too
+        ignore_comment_changes: Also drop hunks whose changed lines are all
+            comment-only, so a change to comments alone gives an empty diff.
+            Hunks that change code still show their comment changes
+        progress: Called with the estimated fraction of the matching work
+            done, a float in [0.0, 1.0], at most every 0.1 seconds and once
+            more with 1.0 at the end, e.g. to drive a "diffing... 60%"
+            indicator. The estimate is the share of lines already resolved
+            as matched or changed, so it is approximate: the remaining
+            regions can take much more or less time than that suggests.
+            Exceptions raised by the callback are reported as unraisable
+            and don't stop the diff
+        max_line_width: Show at most this many characters of each line,
+            cutting longer ones on a character boundary and appending "…"
+            before the line ending. Matching still compares whole lines.
+            None (the default) never truncates
+        numeric_tolerance: (abs_tol, rel_tol) under which lines differing
+            only in their numbers count as equal, see get_opcodes_numeric.
+            Lines are then matched without isjunk, autojunk or comment
+            junk, and window_size can't be used
+        hunk_checksums: Append a non-standard " crc32=xxxxxxxx" comment to
+            each @@ header: the CRC-32 of the hunk's context and removed
+            lines, each followed by "\n". Standard tools ignore text after
+            the closing @@; apply_unified_diff checks it before applying
+        unique_anchors: First match the lines occurring exactly once in a
+            and once in b, keeping the longest run of them in the same
+            order on both sides (the anchors of patience diff), then diff
+            only the gaps between them. Keeps unique lines such as function
+            signatures aligned where a longer run of common lines like "}"
+            would otherwise pull the diff out of place
+        embed_base: Put the whole of a before a non-empty diff, as a
+            "base-lines: N" line followed by the N lines of a, each prefixed
+            with "=". Patch tools skip it like any text before the file
+            headers; apply_unified_diff_3way merges with it when the hunks
+            no longer apply
+        normalize: Names of transforms applied in order to each line to get
+            the key lines are matched by, e.g. ["strip_cr", "lowercase"]:
+            "lowercase", "trim" (leading and trailing whitespace),
+            "collapse_ws" (each whitespace run to one space), "strip_cr"
+            ("\r\n" line endings to "\n") and "dedent" (see dedent). Only
+            strip_cr touches line endings. The diff shows the original lines, and is empty when
+            all keys are equal. isjunk and comment junk see the keys
+        show_line_numbers: Prefix each content line with its old and new
+            line numbers, right-aligned in columns as wide as the longest
+            input's line count and each followed by a space, e.g.
+            " 9 10  context", " 9    -removed", "   10 +added". Removed lines
+            only show the old number, added ones only the new. The result
+            is for reading, not a patch
+        gap_penalty: Pick the match each region of the diff is anchored on
+            by its length minus gap_penalty per line of imbalance it forces
+            between the unmatched lines of the two sides, instead of the
+            longest match. Helps with reordered blocks, where a long match
+            far from where the region's ends line up would turn everything
+            around it into changes. Must be finite and not negative; None
+            (the default) keeps difflib's choice of the longest match
+        reverse_hunks: Emit the hunks last one first, e.g. to see the
+            changes at the bottom of a log first. Each hunk keeps its lines
+            in order and its @@ header. For display only: patch tools need
+            hunks in file order, so git apply and apply_unified_diff reject
+            the result
+        max_output_bytes: Stop the output before its total length in UTF-8
+            bytes, counting each line's prefix and line ending, would go
+            past this, and end it with a line like "@@ output truncated:
+            40 lines omitted (2 hunks) @@" instead. The notice counts the
+            hunks whose header was cut and is always kept, even when it
+            alone doesn't fit. Lines are never cut partway, but a hunk may
+            be, so a truncated diff isn't a valid patch. None (the default)
+            doesn't limit the output
+        min_hunk_lines: Merge each hunk of fewer lines than this (context,
+            removed and added lines together) into the hunk before it, or
+            the one after it for the first hunk, showing the equal lines
+            between them as context. Hunks keep merging while either side
+            is still small, so scattered one-line edits end up in one
+            readable hunk. None (the default) keeps difflib's hunks
+        elide_equal_over: Within a hunk, show a run of more than this many
+            unchanged lines between two changes as its first and last 2
+            lines around a line like "… 12 unchanged lines …", e.g. with a
+            large n or min_hunk_lines. Context before the first and after
+            the last change of a hunk is kept whole. The @@ headers still
+            count the hidden lines, so the result is for reading, not a
+            patch. None (the default) shows every line
+        neighbor_tiebreak: When several matches tie for the one a region of
+            the diff is anchored on, take the one whose neighboring lines
+            (the pair just before it and the pair just after it) are most
+            alike character by character, instead of the earliest. In
+            repetitive code this anchors lines like "}" or blank lines
+            next to the edited lines they belong with. Costs a character
+            diff of two line pairs per tied match, so it is off by default
+        auto_context: Fit the diff into about this many lines by picking
+            the context size instead of using n: the largest n whose
+            output, file headers included, has at most this many lines,
+            or n=0 if even that is longer, for CLI output that shows more
+            context around small diffs and less around huge ones. The
+            lines are matched once and only regrouped for each n tried,
+            so the search is cheap. Can't be combined with
+            adaptive_context
+        strip_common_affixes: Match the lines a and b start and end with
+            directly and run the matcher only on the lines between, a large
+            speedup for a small edit to a large file. Junk and autojunk
+            are still decided over all of b. Off by default because the
+            result can differ from difflib's, which may prefer a longer
+            match elsewhere over the common prefix or suffix
+        dedent: Match lines as if each input went through textwrap.dedent
+            first, e.g. to check a snippet copied into indented docs
+            against its source: the spaces and tabs leading every line of
+            a that isn't blank are removed from a's keys, those leading
+            every line of b from b's, and blank lines match whatever
+            whitespace they hold. Indentation beyond the common margin
+            still counts, unlike with normalize=["trim"]. The diff shows
+            the original lines; dedent them beforehand to show them
+            dedented. Runs before the transforms of normalize
     
     Returns:
         Generator-like list of diff lines
     """
     ...
 
+def split_lines(text: str, keepends: bool = True) -> List[str]:
+    """
+    Split text into lines exactly like str.splitlines(keepends).
+    
+    Lines end at "\n", "\r\n" and "\r" as well as the other boundaries
+    Python recognizes: "\v", "\f", "\x1c", "\x1d", "\x1e", "\x85",
+    "\u2028" and "\u2029". A final line break doesn't start an empty line.
+    
+    Args:
+        text: Text to split
+        keepends: Whether to keep each line's terminator (default: True,
+            unlike str.splitlines, since diffing lines with their endings
+            is what preserves final-newline changes)
+    
+    Returns:
+        The lines of text
+    """
+    ...
+
+def diff_str(
+    a: str,
+    b: str,
+    fromfile: str = "",
+    tofile: str = "",
+    n: int = 3
+) -> str:
+    """
+    Unified diff of two texts as one printable string.
+    
+    Both texts are split with `split_lines`, keeping line endings. Every
+    line of the diff ends in exactly one line break: a "\n" is added to
+    lines that don't already have one, such as headers and the last line
+    of a text without a final newline.
+    
+    Args:
+        a: Original text
+        b: Modified text
+        fromfile: Name of the first file
+        tofile: Name of the second file
+        n: Number of context lines
+    
+    Returns:
+        The diff, or "" if the texts are equal
+    """
+    ...
+
 def unified_diff_str(
     a: str,
     b: str,
@@ -39,13 +288,46 @@ def unified_diff_str(
     tofiledate: str = "",
     n: int = 3,
     lineterm: str = "\n",
-    keepends: bool = False
+    keepends: bool = False,
+    track_final_newline: bool = False,
+    *,
+    brace_scope_context: Optional[int] = None,
+    window_size: Optional[int] = None,
+    isjunk: Optional[Callable[[str], bool]] = None,
+    symmetric_junk: bool = False,
+    compact: bool = False,
+    autojunk: bool = True,
+    validate: bool = False,
+    min_ratio: Optional[float] = None,
+    adaptive_context: Optional[Tuple[int, int]] = None,
+    max_occurrences_per_element: Optional[int] = None,
+    comment_prefixes: Optional[List[str]] = None,
+    block_comment: Optional[Tuple[str, str]] = None,
+    ignore_comment_changes: bool = False,
+    progress: Optional[Callable[[float], object]] = None,
+    max_line_width: Optional[int] = None,
+    numeric_tolerance: Optional[Tuple[float, float]] = None,
+    hunk_checksums: bool = False,
+    unique_anchors: bool = False,
+    embed_base: bool = False,
+    normalize: Optional[List[str]] = None,
+    show_line_numbers: bool = False,
+    gap_penalty: Optional[float] = None,
+    reverse_hunks: bool = False,
+    max_output_bytes: Optional[int] = None,
+    min_hunk_lines: Optional[int] = None,
+    elide_equal_over: Optional[int] = None,
+    neighbor_tiebreak: bool = False,
+    auto_context: Optional[int] = None,
+    strip_common_affixes: bool = False,
+    dedent: bool = False
 ) -> List[str]:
     """
     Compare two strings; generate the unified diff.
     
     This is a convenience function that handles string splitting internally,
-    providing better performance than splitting in Python.
+    providing better performance than splitting in Python. Strings are split
+    like `split_lines`.
     
     Args:
         a: First string to compare
@@ -57,8 +339,1849 @@ def unified_diff_str(
         n: Number of context lines
         lineterm: Line terminator string
         keepends: Whether to keep line endings when splitting
+        brace_scope_context: See `unified_diff`
+        window_size: See `unified_diff`
+        isjunk: See `unified_diff`
+        symmetric_junk: See `unified_diff`
+        compact: See `unified_diff`
+        autojunk: See `unified_diff`
+        track_final_newline: Append an empty sentinel line to a string not
+            ending in a line break, so adding or removing the final newline
+            is reported as an inserted or deleted empty line
+        validate: See `unified_diff`
+        min_ratio: See `unified_diff`
+        adaptive_context: See `unified_diff`
+        max_occurrences_per_element: See `unified_diff`
+        comment_prefixes: See `unified_diff`
+        block_comment: See `unified_diff`
+        ignore_comment_changes: See `unified_diff`
+        progress: See `unified_diff`
+        max_line_width: See `unified_diff`
+        numeric_tolerance: See `unified_diff`
+        hunk_checksums: See `unified_diff`
+        unique_anchors: See `unified_diff`
+        embed_base: See `unified_diff`
+        normalize: See `unified_diff`
+        show_line_numbers: See `unified_diff`
+        gap_penalty: See `unified_diff`
+        reverse_hunks: See `unified_diff`
+        max_output_bytes: See `unified_diff`
+        min_hunk_lines: See `unified_diff`
+        elide_equal_over: See `unified_diff`
+        neighbor_tiebreak: See `unified_diff`
+        auto_context: See `unified_diff`
+        strip_common_affixes: See `unified_diff`
+        dedent: See `unified_diff`
     
     Returns:
         Generator-like list of diff lines
     """
-    ...
\ No newline at end of file
+    ...
+
+def get_opcodes(
+    a: Iterable[str],
+    b: Iterable[str],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True,
+    one_based: bool = False,
+    max_occurrences_per_element: Optional[int] = None,
+    progress: Optional[Callable[[float], object]] = None,
+    unique_anchors: bool = False,
+    gap_penalty: Optional[float] = None,
+    neighbor_tiebreak: bool = False,
+    strip_common_affixes: bool = False
+) -> List[OpCode]:
+    """
+    Opcodes turning a into b, like SequenceMatcher(isjunk, a, b).get_opcodes().
+    
+    The matching runs with the GIL released, so calling this from several
+    threads of a ThreadPoolExecutor diffs pairs in parallel; get_grouped_opcodes
+    and get_matching_blocks do the same.
+    
+    Args:
+        a: First sequence of lines, any iterable of str as for `unified_diff`
+        b: Second sequence of lines, likewise
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+        one_based: Report 1-based inclusive ranges instead of 0-based
+            half-open ones, see `OpCode`
+        max_occurrences_per_element: See `unified_diff`
+        progress: See `unified_diff`
+        unique_anchors: See `unified_diff`
+        gap_penalty: See `unified_diff`
+        neighbor_tiebreak: See `unified_diff`
+        strip_common_affixes: See `unified_diff`
+    
+    Returns:
+        The opcodes, covering both sequences in order
+    """
+    ...
+
+def get_opcodes_for_render(
+    a: List[str],
+    b: List[str],
+    max_equal_run: Optional[int] = None,
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True,
+    one_based: bool = False,
+    keys: bool = False
+) -> List[Tuple]:
+    """
+    Opcodes like `get_opcodes`, with each equal opcode longer than
+    max_equal_run lines split into consecutive equal opcodes of at most
+    that many lines, e.g. to render a long unchanged stretch in bounded
+    chunks. The pieces keep the ranges contiguous, so joining them back
+    gives `get_opcodes`.
+    
+    With keys, each tuple also carries a list of keys for the lines it
+    renders, e.g. as React keys, so a viewer re-rendering after a new diff
+    can reuse the nodes of lines that persisted. An unchanged line's key
+    comes from its content alone: "=" and the CRC-32 of the line in hex,
+    then "." and how many earlier unchanged lines had the same CRC, like
+    "=1a2b3c4d.0". A changed opcode lists the keys of its deleted lines,
+    then of its inserted lines, from position and content: "-12:1a2b3c4d"
+    for a[12] and "+7:1a2b3c4d" for b[7]. The keys of one diff are
+    distinct.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        max_equal_run: Most lines per equal opcode, or None to not split
+        isjunk: See `get_opcodes`
+        autojunk: See `get_opcodes`
+        one_based: See `get_opcodes`. Keys always use 0-based positions
+        keys: Append the list of line keys to each opcode tuple
+    
+    Returns:
+        (tag, i1, i2, j1, j2) tuples, or (tag, i1, i2, j1, j2, keys) with
+        keys
+    
+    Raises:
+        ValueError: If max_equal_run is 0
+    """
+    ...
+
+def get_opcodes_numeric(
+    a: List[str],
+    b: List[str],
+    abs_tol: float = 0.0,
+    rel_tol: float = 0.0
+) -> List[Tuple[str, int, int, int, int]]:
+    """
+    Opcodes turning a into b, counting lines that differ only in numbers
+    close to each other as equal.
+    
+    A numeric token is an optional sign, digits with an optional fraction
+    (12, 1.5, .5, 3.) and an optional exponent (1e-3). A sign only belongs
+    to the token when it doesn't follow a letter, digit, '_' or '.', so
+    "3-1" holds 3 and 1. Digits touching a letter, '_' or a second '.' are
+    part of a word, so "v2", "x_1", "5px" and "1.2.3" must match exactly.
+    
+    Lines are matched on their text with every numeric token replaced by a
+    placeholder, then each matched pair is confirmed by comparing its
+    numbers like math.isclose(x, y, rel_tol=rel_tol, abs_tol=abs_tol).
+    Pairs that aren't close become "replace" opcodes.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        abs_tol: Largest absolute difference between equal numbers
+        rel_tol: Largest difference relative to the larger magnitude
+    
+    Returns:
+        List of (tag, i1, i2, j1, j2) tuples
+    
+    Raises:
+        ValueError: If a tolerance is negative or not finite
+    """
+    ...
+
+def get_opcodes_where(
+    a: List[str],
+    b: List[str],
+    eq: Callable[[int, str, int, str], bool]
+) -> List[Tuple[str, int, int, int, int]]:
+    """
+    Opcodes turning a into b, with a[i] and b[j] counted as equal when
+    eq(i, a[i], j, b[j]) is true.
+    
+    Because eq sees the positions as well as the lines, it can force a
+    header row to match or compare some columns loosely. Matching follows
+    SequenceMatcher: the longest run of equal pairs, then the regions on
+    either side of it, with no junk heuristic.
+    
+    Performance note: eq can't be hashed like plain line equality, so the
+    b2j index is skipped and every pair of lines in a region is compared,
+    O(len(a) * len(b)) calls to eq. A RuntimeWarning is issued when that is
+    more than 10 million pairs.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        eq: Called with (i, a[i], j, b[j]); truthy when the lines are equal
+    
+    Returns:
+        List of (tag, i1, i2, j1, j2) tuples
+    
+    Raises:
+        Any exception raised by eq
+    """
+    ...
+
+def get_grouped_opcodes(
+    a: Iterable[str],
+    b: Iterable[str],
+    n: int = 3,
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True,
+    one_based: bool = False,
+    max_occurrences_per_element: Optional[int] = None,
+    progress: Optional[Callable[[float], object]] = None,
+    unique_anchors: bool = False,
+    gap_penalty: Optional[float] = None,
+    neighbor_tiebreak: bool = False,
+    strip_common_affixes: bool = False
+) -> List[List[OpCode]]:
+    """
+    Hunks of opcodes with up to n lines of context, like
+    SequenceMatcher(isjunk, a, b).get_grouped_opcodes(n).
+    
+    Takes the same arguments as `get_opcodes`, plus the context size n.
+    """
+    ...
+
+def annotated_hunks(
+    a: List[str],
+    b: List[str],
+    n: int = 3,
+    line_endings: bool = False
+) -> List[List[Tuple]]:
+    """
+    Hunks like `get_grouped_opcodes`, with each opcode tuple extended by an
+    indent_only flag, and an eol_only flag after it with line_endings.
+    
+    Replace opcodes are split into runs of lines paired one to one, and a
+    run is indent_only when each of its pairs is equal after stripping
+    leading whitespace, so re-indentation can be told apart from logic
+    changes. With line_endings, a run is eol_only when its pairs only
+    differ in their line breaks (e.g. "\n" against "\r\n"). Lines left
+    over when one side of a replace is longer are never flagged.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        n: Number of context lines
+        line_endings: Also split out and flag line-ending-only runs
+    
+    Returns:
+        Hunks of (tag, i1, i2, j1, j2, indent_only) tuples, or
+        (tag, i1, i2, j1, j2, indent_only, eol_only) with line_endings
+    """
+    ...
+
+def line_ending_report(lines: List[str]) -> List[str]:
+    """
+    The line break each line ends with, e.g. for a linter enforcing
+    consistent line endings.
+    
+    Args:
+        lines: Lines with their endings kept, e.g. from
+            split_lines(text, keepends=True)
+    
+    Returns:
+        One of "lf", "crlf", "cr", "none" (no line break, as on a last line
+        without a newline) or "other" (the rarer breaks split_lines
+        recognizes, like "\x0c" or U+2028) per line
+    """
+    ...
+
+def get_matching_blocks(
+    a: Iterable[str],
+    b: Iterable[str],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True,
+    include_sentinel: bool = True
+) -> List[Tuple[int, int, int]]:
+    """
+    Matching blocks of a and b, like
+    SequenceMatcher(isjunk, a, b).get_matching_blocks().
+    
+    Each (i, j, size) block means a[i:i+size] == b[j:j+size]. Like difflib,
+    the list ends with the zero-size sentinel (len(a), len(b), 0): it gives
+    the loop turning blocks into opcodes a last step to close the final
+    change. Consumers that only want the real blocks can pass
+    include_sentinel=False instead of slicing it off.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        isjunk: See `get_opcodes`
+        autojunk: See `get_opcodes`
+        include_sentinel: End with the (len(a), len(b), 0) sentinel
+    
+    Returns:
+        List of (i, j, size) tuples, increasing in i and j
+    """
+    ...
+
+def matching_slices(
+    a: List[str],
+    b: List[str],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True
+) -> List[List[str]]:
+    """
+    The matched lines of each block of
+    SequenceMatcher(isjunk, a, b).get_matching_blocks().
+    
+    Saves indexing back into a or b for each (i, j, size) triple. Both
+    sides of a block are equal, so one list of lines is returned per
+    block. The zero-length sentinel block is left out.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+    
+    Returns:
+        The lines of each matching block, in order
+    """
+    ...
+
+def invert_opcodes(opcodes: List[OpCode]) -> List[OpCode]:
+    """
+    Turn the opcodes of an a -> b diff into the opcodes of b -> a.
+    
+    Indices are swapped and 'insert'/'delete' trade places; 'equal' and
+    'replace' keep their tag with swapped ranges.
+    
+    Args:
+        opcodes: Opcodes as returned by SequenceMatcher.get_opcodes()
+    
+    Returns:
+        The inverted opcodes
+    
+    Raises:
+        ValueError: If an opcode has an unknown tag
+    """
+    ...
+
+def opcodes_to_matching_blocks(opcodes: List[OpCode]) -> List[Tuple[int, int, int]]:
+    """
+    Turn opcodes back into the matching blocks they came from, e.g. for a
+    renderer expecting blocks when the opcodes were deserialized.
+    
+    Each run of touching equal opcodes becomes one (i, j, size) block, and
+    the list ends with the (len(a), len(b), 0) sentinel, the lengths taken
+    from where the last opcode ends, so `get_matching_blocks(a, b)` ==
+    `opcodes_to_matching_blocks(get_opcodes(a, b))`.
+    
+    Args:
+        opcodes: Opcodes as returned by SequenceMatcher.get_opcodes()
+    
+    Returns:
+        List of (i, j, size) tuples ending with the sentinel
+    
+    Raises:
+        ValueError: If an opcode has an unknown tag
+    """
+    ...
+
+def json_patch(a: List[str], b: List[str]) -> str:
+    """
+    Diff two lists of lines as a JSON Patch (RFC 6902), for systems that
+    store a file as a JSON array of line strings.
+    
+    The patch is a compact JSON array of "add", "remove" and "replace"
+    operations with "/N" index paths. Since each operation applies to the
+    array the ones before it left, changes are emitted from the end of the
+    array backwards: removals come in descending index order and every
+    path stays an index into a as far as it has been patched.
+    
+    Args:
+        a: Old lines
+        b: New lines
+    
+    Returns:
+        The patch as a JSON string, "[]" when a and b are equal
+    """
+    ...
+
+def get_opcodes_arrays(
+    a: Iterable[str],
+    b: Iterable[str],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True
+) -> Tuple[bytes, bytes, bytes, bytes, bytes]:
+    """
+    Opcodes turning a into b as five parallel columns, for numeric
+    processing of diffs with millions of opcodes without a Python tuple
+    per opcode.
+    
+    Each column is a bytes object numpy can wrap without copying:
+    
+        tags, i1, i2, j1, j2 = get_opcodes_arrays(a, b)
+        tags = numpy.frombuffer(tags, dtype=numpy.uint8)
+        i1 = numpy.frombuffer(i1, dtype="<u8")
+    
+    The arrays are read-only, like the bytes they wrap. array.array("Q",
+    i1) also works on little-endian machines.
+    
+    Args:
+        a: First sequence of lines, any iterable of str as for `unified_diff`
+        b: Second sequence of lines, likewise
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+    
+    Returns:
+        (tags, i1, i2, j1, j2): tags has one byte per opcode, 0 for
+        "equal", 1 for "delete", 2 for "insert" and 3 for "replace" (the
+        numbering of serialize_opcodes); the index columns hold the
+        opcode's indices as 8-byte little-endian unsigned integers, in the
+        order of get_opcodes(a, b)
+    """
+    ...
+
+def serialize_opcodes(opcodes: List[OpCode]) -> bytes:
+    """
+    Encode opcodes compactly, e.g. for a diff cache.
+    
+    Each opcode takes a tag byte and four delta-encoded varints, usually
+    five bytes in all.
+    
+    Args:
+        opcodes: Opcodes as returned by SequenceMatcher.get_opcodes()
+    
+    Returns:
+        The encoded opcodes
+    
+    Raises:
+        ValueError: If an opcode has an unknown tag
+    """
+    ...
+
+def deserialize_opcodes(data: bytes) -> List[OpCode]:
+    """
+    Decode opcodes encoded by serialize_opcodes.
+    
+    Args:
+        data: Output of serialize_opcodes
+    
+    Returns:
+        The decoded opcodes
+    
+    Raises:
+        ValueError: If data is truncated or otherwise malformed
+    """
+    ...
+
+def invert_unified_diff(patch: List[str]) -> List[str]:
+    """
+    Reverse a unified diff so it turns b back into a, like `patch -R`.
+    
+    Args:
+        patch: Lines of a unified diff, e.g. as returned by unified_diff
+    
+    Returns:
+        Lines of the reversed unified diff
+    
+    Raises:
+        ValueError: If a hunk header can't be parsed
+    """
+    ...
+
+def line_edits(a: List[str], b: List[str]) -> List[Tuple[int, int, List[str]]]:
+    """
+    The fewest line edits turning a into b.
+    
+    Each edit is (start, end, lines), replacing a[start:end] by lines: an
+    insertion when start == end, a deletion when lines is empty. Edits are
+    in ascending order and never overlap, so applying them from last to
+    first, e.g. `a[start:end] = lines`, reconstructs b.
+    
+    Args:
+        a: Original lines
+        b: Lines to reconstruct
+    
+    Returns:
+        List of (start, end, lines) edits
+    """
+    ...
+
+def apply_unified_diff(
+    a: List[str],
+    patch: List[str],
+    fuzz: int = 0,
+    max_offset: Optional[int] = None
+) -> Tuple[List[str], List[Tuple[int, int]]]:
+    """
+    Apply a unified diff to a, like GNU patch.
+    
+    A hunk whose lines aren't where its header says is searched for nearby,
+    starting from where the previous hunk's offset predicts it. If it still
+    doesn't match, up to fuzz context lines are ignored at each end of the
+    hunk and the search repeats, like `patch -F`. When a hunk header
+    carries a checksum (see the hunk_checksums option of unified_diff), the
+    hunk's context and removed lines must match it before it is placed.
+    
+    Args:
+        a: Lines to patch, matching the '-' and ' ' lines of the patch
+            exactly (including any line endings)
+        patch: Lines of a unified diff, e.g. as returned by unified_diff
+        fuzz: Most context lines ignored at either end of a hunk
+        max_offset: Most lines a hunk may have moved from its header's
+            position, or None to search the whole file
+    
+    Returns:
+        The patched lines and an (offset, fuzz) tuple per hunk: how many
+        lines from its header's position it applied (negative if earlier)
+        and how much fuzz it needed
+    
+    Raises:
+        ValueError: If a hunk is malformed, fails its checksum or can't be
+            placed
+    """
+    ...
+
+def verify_diff(a: Iterable[str], b: Iterable[str], n: int = 3, **options: Any) -> bool:
+    """
+    Check that a diff round-trips: apply unified_diff(a, b, n=n, **options)
+    to a with `apply_unified_diff`, without fuzz or offsets, and compare the
+    result with b.
+    
+    Matching and grouping options only change which lines are shown as
+    changed, never the lines themselves, so with those this should always
+    be true; a false result is a bug worth reporting with its inputs.
+    Options that change what the diff shows, such as normalize, dedent,
+    numeric_tolerance, show_line_numbers or max_line_width, can make it
+    false instead. Lines are compared exactly, line endings included.
+    
+    Args:
+        a: First sequence of lines, any iterable of str as for `unified_diff`
+        b: Second sequence of lines, likewise
+        n: Number of context lines
+        **options: Any keyword option of `unified_diff` after lineterm,
+            except track_final_newline
+    
+    Returns:
+        True if the patched a equals b
+    
+    Raises:
+        ValueError: If an option is outside its valid range
+        TypeError: If an option isn't one of unified_diff's
+    """
+    ...
+
+def apply_unified_diff_3way(
+    a: List[str],
+    patch: List[str],
+    fuzz: int = 0,
+    max_offset: Optional[int] = None
+) -> Tuple[List[str], int]:
+    """
+    Apply a unified diff to a, falling back to a three-way merge like
+    `git apply --3way` when a hunk doesn't apply.
+    
+    The fallback needs the base embedded by unified_diff's embed_base: the
+    patch is applied to that base, and the changes from the base to a and
+    to the patched base are merged. A region changed on one side only takes
+    that change; one changed differently on both becomes a conflict, a's
+    lines between "<<<<<<< ours" and "=======" and the patch's up to
+    ">>>>>>> theirs". The marker lines end in "\n" unless no line of a has
+    a line ending.
+    
+    Args:
+        a: Lines to patch
+        patch: Lines of a unified diff, e.g. as returned by unified_diff
+        fuzz: See `apply_unified_diff`
+        max_offset: See `apply_unified_diff`
+    
+    Returns:
+        The patched or merged lines and the number of conflicts in them, 0
+        when the patch applied directly
+    
+    Raises:
+        ValueError: If a hunk can't be placed and the patch has no base, or
+            the patch is malformed
+    """
+    ...
+
+def merge_patches(
+    base: List[str],
+    patch1: List[str],
+    patch2: List[str]
+) -> Tuple[List[str], List[Tuple[int, int]]]:
+    """
+    Combine two unified diffs of the same base, e.g. two patches kept
+    against one upstream file.
+    
+    Each patch is applied to base like apply_unified_diff without fuzz,
+    and the two results are merged: lines changed by only one patch, or
+    the same way by both, take that change. Lines the patches change
+    differently become a conflict, patch1's lines between "<<<<<<< ours"
+    and "=======" and patch2's up to ">>>>>>> theirs". The marker lines
+    end in "\n" unless no line of patch1's result has a line ending.
+    
+    Args:
+        base: Lines both patches were made against
+        patch1: First unified diff of base
+        patch2: Second unified diff of base
+    
+    Returns:
+        The merged lines, and for each conflict the (start, end) range of
+        base lines it replaces (0-based, end exclusive; empty when both
+        patches insert different lines at the same place)
+    
+    Raises:
+        ValueError: If either patch is malformed or doesn't apply to base
+    """
+    ...
+
+def differs_by_at_most(a: List[str], b: List[str], max_diff_lines: int) -> bool:
+    """
+    Whether turning a into b changes at most max_diff_lines lines, counting
+    removed and added lines like unified_diff_with_stats, for cheap gating.
+    
+    Returns False before diffing when a lower bound is already over budget:
+    the length difference, then the lines of either side with no equal
+    line left on the other. Otherwise the opcodes are counted, stopping at
+    the first change over budget. The count follows difflib's opcodes,
+    which aren't always the smallest diff, so the answer is conservative:
+    it can be False although a smaller edit fits the budget.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        max_diff_lines: Most removed plus added lines allowed
+    
+    Returns:
+        True if the diff is within budget
+    """
+    ...
+
+def first_difference(a: Iterable[str], b: Iterable[str]) -> Optional[Tuple[int, int]]:
+    """
+    Where a and b start to differ, for a quick "are these the same, and if
+    not where do they part" check without diffing.
+    
+    Lines are taken from both iterables in step and compared as they come,
+    so the work is proportional to the common prefix: nothing after the
+    first difference is read, which also makes this cheap on generators
+    and file objects.
+    
+    Args:
+        a: First iterable of lines
+        b: Second iterable of lines
+    
+    Returns:
+        (i, j), the 0-based index of the first differing line in a and in
+        b, equal since both are the length of the common prefix; an index
+        past the end of the shorter sequence if it is a prefix of the
+        other. None if a and b are equal
+    
+    Raises:
+        TypeError: If a or b is a str, or holds something other than str
+    """
+    ...
+
+def ratio_range(
+    a: List[str],
+    b: List[str],
+    a_range: Tuple[int, int],
+    b_range: Tuple[int, int],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True
+) -> float:
+    """
+    Similarity ratio of only a[a_range[0]:a_range[1]] and
+    b[b_range[0]:b_range[1]], e.g. to score the two sides of one hunk.
+    
+    Like SequenceMatcher(isjunk, a, b).ratio() on the slices, except that
+    junk and autojunk are decided over the whole of b. 1.0 when both ranges
+    are empty.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        a_range: (start, end) of the lines of a to compare
+        b_range: (start, end) of the lines of b to compare
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+    
+    Raises:
+        ValueError: If a range ends before it starts or past its sequence
+    """
+    ...
+
+def similarity_profile(
+    a: Iterable[str],
+    b: Iterable[str],
+    window: int,
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True
+) -> List[float]:
+    """
+    How similar the files are along b, e.g. for a heatmap of where a long
+    document changed most.
+    
+    For each window of `window` consecutive lines of b, in order (b[0:w],
+    b[1:w+1], ...), the ratio of the window and the lines of a the diff
+    aligns with it. Matches are the window's lines that get_opcodes(a, b)
+    reports as equal; the aligned lines of a are found by spreading each
+    opcode's lines of a evenly over its lines of b, and lines deleted after
+    a line of b count in the windows holding that line. Nothing is matched
+    again, so the profile costs one diff plus a pass over the opcodes,
+    unlike a `ratio_range` call per window.
+    
+    Args:
+        a: First sequence of lines, any iterable of str as for `unified_diff`
+        b: Second sequence of lines, likewise
+        window: Lines of b per window
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+    
+    Returns:
+        len(b) - window + 1 ratios in [0.0, 1.0], or a single one for the
+        whole pair when b has at most window lines
+    
+    Raises:
+        ValueError: If window is 0
+    """
+    ...
+
+def ratio_streaming(
+    a: List[str],
+    b: List[str],
+    cutoff: float,
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True
+) -> Optional[float]:
+    """
+    SequenceMatcher(isjunk, a, b).ratio() if it is at least cutoff, None
+    otherwise, giving up early on inputs that can't reach it.
+    
+    While the matching blocks are searched, an upper bound on the total
+    match is kept: the lines matched so far plus the shorter side of every
+    region still to search. The search stops as soon as that bound can't
+    reach cutoff, so bulk near-duplicate filtering doesn't pay for full
+    diffs of clearly different inputs. The result is exactly
+    `ratio() if ratio() >= cutoff else None`.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        cutoff: Lowest ratio of interest, in [0.0, 1.0]
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+    
+    Raises:
+        ValueError: If cutoff is outside [0.0, 1.0]
+    """
+    ...
+
+def estimate_cost(
+    a: List[str],
+    b: List[str],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True
+) -> int:
+    """
+    Cheap estimate of how expensive diffing a against b will be, e.g. to
+    decide whether to diff inline, defer the work or turn on autojunk.
+    
+    b is indexed as for a diff, and the result is the number of positions
+    of b listed for the lines of a: how often the inner loop of the first
+    longest-match search runs, the dominant cost of matching. Only b is
+    indexed and nothing is matched.
+    
+    This is an estimate, not a guarantee: later searches only cover the
+    regions between matches, while inputs with few matches are searched
+    again region by region. Junk lines, and with autojunk the lines that
+    are popular in b, cost nothing.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+    
+    Returns:
+        The estimated number of comparisons
+    """
+    ...
+
+def get_b2j(
+    b: List[str],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True
+) -> Dict[str, List[int]]:
+    """
+    The index a diff against b searches, like CPython's
+    SequenceMatcher.b2j, for seeing why a diff aligned the way it did.
+    
+    Maps each line of b that can anchor a match to its positions in b, in
+    order. It reflects the index after pruning: junk lines and, with
+    autojunk, lines popular in b (more than 1% of a b of at least 200
+    lines) are missing, so a line you expected to anchor a match but that
+    isn't a key was pruned. The dict is a snapshot copy; changing it has no
+    effect on any diff.
+    
+    Args:
+        b: Second sequence of lines
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+    
+    Returns:
+        A dict of line to the list of its indices in b
+    """
+    ...
+
+def diff_in_background(
+    a: Iterable[str],
+    b: Iterable[str],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True,
+    unique_anchors: bool = False
+) -> "DiffHandle":
+    """
+    Start diffing a and b on a new thread, for keeping a UI or an event
+    loop responsive during a large diff.
+    
+    The matching runs without the GIL. isjunk is called for the lines of
+    b before this returns, on the calling thread. Await the result from
+    asyncio with
+    
+        handle = diff_in_background(a, b)
+        opcodes = await asyncio.to_thread(handle.result)
+    
+    Args:
+        a: First sequence of lines, any iterable of str as for `unified_diff`
+        b: Second sequence of lines, likewise
+        isjunk: See `unified_diff`
+        autojunk: See `unified_diff`
+        unique_anchors: See `unified_diff`
+    
+    Returns:
+        A `DiffHandle` whose result is get_opcodes(a, b, ...)
+    """
+    ...
+
+def hunk_headers(a: List[str], b: List[str], n: int = 3) -> List[str]:
+    """
+    Only the "@@ -l,s +l,s @@" headers of unified_diff(a, b, n=n), without
+    line terminators, as an outline of where the changes are.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        n: Number of context lines, which decides how hunks are merged
+    
+    Returns:
+        One header per hunk
+    """
+    ...
+
+def diffstat_line(
+    a: List[str],
+    b: List[str],
+    filename: str,
+    max_width: int = 50
+) -> str:
+    """
+    Summarize a diff as one git-style diffstat line, e.g. "file | 12 +++---".
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        filename: Name shown before the bar
+        max_width: Maximum number of +/- characters in the bar
+    
+    Returns:
+        The diffstat line
+    """
+    ...
+
+def unified_diff_bytes(
+    a: List[bytes],
+    b: List[bytes],
+    fromfile: bytes = b"",
+    tofile: bytes = b"",
+    fromfiledate: bytes = b"",
+    tofiledate: bytes = b"",
+    n: int = 3,
+    lineterm: bytes = b"\n",
+    text_only: bool = False,
+    binary_threshold: float = 0.3
+) -> List[bytes]:
+    """
+    Unified diff of byte lines, for content that isn't valid UTF-8 such as
+    latin-1 logs, without lossy decoding.
+    
+    Like unified_diff but with bytes in and out: headers are bytes, and
+    content lines are the input lines unchanged behind a single b" ", b"-"
+    or b"+" prefix byte. Lines are compared byte for byte.
+    
+    Like GNU diff, inputs that differ while either looks binary give the
+    single line b"Binary files <fromfile> and <tofile> differ" + lineterm.
+    An input looks binary when its first 8000 bytes contain a NUL byte or
+    more than binary_threshold of them are control characters other than
+    whitespace and backspace. Bytes from 0x80 up count as text.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        fromfile: Name of the first file
+        tofile: Name of the second file
+        fromfiledate: Timestamp for the first file
+        tofiledate: Timestamp for the second file
+        n: Number of context lines
+        lineterm: Line terminator of the header lines
+        text_only: Always diff lines, even of binary-looking inputs
+        binary_threshold: Fraction of control bytes above which an input
+            looks binary, in [0.0, 1.0]
+    
+    Returns:
+        List of diff lines
+    
+    Raises:
+        ValueError: If binary_threshold is outside [0.0, 1.0]
+    """
+    ...
+
+def context_diff(
+    a: List[str],
+    b: List[str],
+    fromfile: str = "",
+    tofile: str = "",
+    fromfiledate: str = "",
+    tofiledate: str = "",
+    n: int = 3,
+    lineterm: str = "\n",
+    change_marker: str = "!",
+    delete_marker: str = "-",
+    insert_marker: str = "+",
+    context_marker: str = " "
+) -> List[str]:
+    """
+    Compare two sequences of lines; generate the context diff, like
+    difflib.context_diff.
+    
+    The markers starting each hunk line can be replaced for consumers that
+    expect other characters. Each takes one column of the two-column line
+    prefix, so it must be a single character and not a control character.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        fromfile: Name of the first file
+        tofile: Name of the second file
+        fromfiledate: Timestamp for the first file
+        tofiledate: Timestamp for the second file
+        n: Number of context lines
+        lineterm: Line terminator of the header lines
+        change_marker: Marks lines of a replaced block
+        delete_marker: Marks lines only in a
+        insert_marker: Marks lines only in b
+        context_marker: Marks unchanged lines
+    
+    Returns:
+        The context diff lines
+    
+    Raises:
+        ValueError: If a marker isn't a single printable character
+    """
+    ...
+
+def unified_diff_range(
+    a: List[str],
+    b: List[str],
+    a_range: Tuple[int, int],
+    b_range: Tuple[int, int],
+    fromfile: str = "",
+    tofile: str = "",
+    fromfiledate: str = "",
+    tofiledate: str = "",
+    n: int = 3,
+    lineterm: str = "\n",
+    *,
+    brace_scope_context: Optional[int] = None,
+    window_size: Optional[int] = None,
+    isjunk: Optional[Callable[[str], bool]] = None,
+    symmetric_junk: bool = False,
+    compact: bool = False,
+    autojunk: bool = True,
+    validate: bool = False,
+    min_ratio: Optional[float] = None,
+    adaptive_context: Optional[Tuple[int, int]] = None,
+    max_occurrences_per_element: Optional[int] = None,
+    comment_prefixes: Optional[List[str]] = None,
+    block_comment: Optional[Tuple[str, str]] = None,
+    ignore_comment_changes: bool = False,
+    progress: Optional[Callable[[float], object]] = None,
+    max_line_width: Optional[int] = None,
+    numeric_tolerance: Optional[Tuple[float, float]] = None,
+    hunk_checksums: bool = False,
+    unique_anchors: bool = False,
+    embed_base: bool = False,
+    normalize: Optional[List[str]] = None,
+    show_line_numbers: bool = False,
+    gap_penalty: Optional[float] = None,
+    reverse_hunks: bool = False,
+    max_output_bytes: Optional[int] = None,
+    min_hunk_lines: Optional[int] = None,
+    elide_equal_over: Optional[int] = None,
+    neighbor_tiebreak: bool = False,
+    auto_context: Optional[int] = None,
+    strip_common_affixes: bool = False,
+    dedent: bool = False
+) -> List[str]:
+    """
+    Unified diff of only the lines a[a_range[0]:a_range[1]] and
+    b[b_range[0]:b_range[1]], for re-diffing a region known to contain
+    every change.
+    
+    Hunk headers use the line numbers of the whole sequences. Takes the
+    same keyword arguments as `unified_diff`.
+    
+    Raises:
+        ValueError: If a range ends before it starts or past its sequence
+    """
+    ...
+
+def unified_diff_with_stats(
+    a: Iterable[str],
+    b: Iterable[str],
+    fromfile: str = "",
+    tofile: str = "",
+    fromfiledate: str = "",
+    tofiledate: str = "",
+    n: int = 3,
+    lineterm: str = "\n",
+    track_final_newline: bool = False,
+    *,
+    brace_scope_context: Optional[int] = None,
+    window_size: Optional[int] = None,
+    isjunk: Optional[Callable[[str], bool]] = None,
+    symmetric_junk: bool = False,
+    compact: bool = False,
+    autojunk: bool = True,
+    validate: bool = False,
+    min_ratio: Optional[float] = None,
+    adaptive_context: Optional[Tuple[int, int]] = None,
+    max_occurrences_per_element: Optional[int] = None,
+    comment_prefixes: Optional[List[str]] = None,
+    block_comment: Optional[Tuple[str, str]] = None,
+    ignore_comment_changes: bool = False,
+    progress: Optional[Callable[[float], object]] = None,
+    max_line_width: Optional[int] = None,
+    numeric_tolerance: Optional[Tuple[float, float]] = None,
+    hunk_checksums: bool = False,
+    unique_anchors: bool = False,
+    embed_base: bool = False,
+    normalize: Optional[List[str]] = None,
+    show_line_numbers: bool = False,
+    gap_penalty: Optional[float] = None,
+    reverse_hunks: bool = False,
+    max_output_bytes: Optional[int] = None,
+    min_hunk_lines: Optional[int] = None,
+    elide_equal_over: Optional[int] = None,
+    neighbor_tiebreak: bool = False,
+    auto_context: Optional[int] = None,
+    strip_common_affixes: bool = False,
+    dedent: bool = False
+) -> Tuple[List[str], Tuple[int, int, int]]:
+    """
+    Compute the unified diff and its line counts in one pass.
+    
+    Takes the same arguments as `unified_diff`.
+    
+    Returns:
+        The diff lines and an (added, removed, hunks) tuple
+    """
+    ...
+
+def unified_diff_dual(
+    a: Iterable[str],
+    b: Iterable[str],
+    fromfile: str = "",
+    tofile: str = "",
+    fromfiledate: str = "",
+    tofiledate: str = "",
+    n: int = 3,
+    lineterm: str = "\n"
+) -> Tuple[List[str], List[str]]:
+    """
+    Unified diff as plain lines and as lines colored for a terminal, from
+    one computation, e.g. in CI that logs the plain diff and displays the
+    colored one.
+    
+    The colored lines are the plain ones wrapped in ANSI escapes, with the
+    colors of Python 3.14's unified_diff(..., color=True): bold file
+    headers, blue hunk headers, red removed and green added lines, each
+    line (line ending included) followed by a reset.
+    
+    Args:
+        a: First sequence of lines, any iterable of str as for `unified_diff`
+        b: Second sequence of lines, likewise
+        fromfile: See `unified_diff`
+        tofile: See `unified_diff`
+        fromfiledate: See `unified_diff`
+        tofiledate: See `unified_diff`
+        n: Number of context lines
+        lineterm: See `unified_diff`
+    
+    Returns:
+        A (plain, colored) pair of lists of the same length, plain being
+        unified_diff(a, b, ...)
+    """
+    ...
+
+def unified_diff_readers(
+    fa: Iterable[str],
+    fb: Iterable[str],
+    fromfile: str = "",
+    tofile: str = "",
+    fromfiledate: str = "",
+    tofiledate: str = "",
+    n: int = 3,
+    lineterm: str = "\n",
+    out: Optional[TextIO] = None
+) -> Optional[List[str]]:
+    """
+    Unified diff of two open text files, e.g. from scripts that already
+    hold the file handles.
+    
+    The files are read by iterating them, line by line straight into the
+    native diff, so their contents never exist as Python lists. fb is read
+    first, since the index of b is built before matching starts. fa is read
+    in full as well: matching searches the regions of a between matches
+    again and again, so it can't consume a as it goes. Lines keep their
+    line endings, as readlines() gives them.
+    
+    Args:
+        fa: Old file, opened in text mode, or any iterable of str
+        fb: New file, likewise
+        fromfile: See `unified_diff`
+        tofile: See `unified_diff`
+        fromfiledate: See `unified_diff`
+        tofiledate: See `unified_diff`
+        n: Number of context lines
+        lineterm: See `unified_diff`
+        out: Where to write the diff, one write() call per line, e.g.
+            sys.stdout or another open file
+    
+    Returns:
+        The diff lines, or None when they were written to out
+    
+    Raises:
+        TypeError: If a file yields something other than str, e.g. when
+            opened in binary mode
+    """
+    ...
+
+def walk_opcodes(
+    a: Iterable[str],
+    b: Iterable[str],
+    callback: Callable[[str, int, int, int, int], Optional[bool]],
+    isjunk: Optional[Callable[[str], bool]] = None,
+    autojunk: bool = True
+) -> bool:
+    """
+    Call `callback(tag, i1, i2, j1, j2)` for each opcode as it is produced.
+    
+    The opcode list is never materialized. Returning False (or any falsy
+    value other than None) from the callback stops the walk.
+    
+    Args:
+        a: First sequence of lines, any iterable of str as for `unified_diff`
+        b: Second sequence of lines, likewise
+        callback: Called once per opcode
+        isjunk: Lines for which this returns true never anchor a match
+        autojunk: See `unified_diff`
+    
+    Returns:
+        True if every opcode was visited, False if the callback stopped early
+    """
+    ...
+
+def inline_char_diff(
+    a: str,
+    b: str,
+    delete: Tuple[str, str] = ("[-", "-]"),
+    insert: Tuple[str, str] = ("{+", "+}"),
+    isolate_rtl: bool = False
+) -> str:
+    """
+    Character-level diff of two lines rendered inline, e.g. "foo[-bar-]{+baz+}qux".
+    
+    Equal runs are left bare. A replaced run is written as its deletion
+    followed by its insertion.
+    
+    Characters are extended grapheme clusters, what a reader sees as one
+    character: a letter with its combining marks (Hebrew points, Arabic
+    harakat, accents), an emoji ZWJ sequence or "\r\n". A mark is never
+    split from its letter by a marker.
+    
+    Args:
+        a: Original line
+        b: Modified line
+        delete: Opening and closing markers around deleted characters
+        insert: Opening and closing markers around inserted characters
+        isolate_rtl: Wrap each marked run holding right-to-left text
+            (Hebrew, Arabic and the like) in U+2068 FIRST STRONG ISOLATE and
+            U+2069 POP DIRECTIONAL ISOLATE, inside its markers, so that the
+            bidi algorithm lays the run out on its own and can't move the
+            markers away from it
+    
+    Returns:
+        The annotated line
+    """
+    ...
+
+def word_unified_diff(a: str, b: str, n: int = 3, isolate_rtl: bool = False) -> List[str]:
+    """
+    Unified-style diff of the words of two texts, for prose where line
+    hunks are too coarse.
+    
+    A word is a run of non-whitespace characters, so word k of a text is
+    text.split()[k] (for ordinary whitespace). Whitespace only separates
+    words: changing it alone doesn't change the diff. Text is scanned by
+    grapheme clusters, so a combining mark after a space stays with the
+    space.
+    
+    Each hunk is two strings: an "@@ -start,count +start,count @@" header
+    counting words, 1-based like line numbers in a unified diff, and the
+    hunk's words with up to n words of context, joined by their original
+    whitespace (which may include newlines), deleted runs in [-...-] and
+    inserted ones in {+...+}. Context and deleted words keep the whitespace
+    of a, inserted words that of b.
+    
+    Args:
+        a: Original text
+        b: Modified text
+        n: Number of context words
+        isolate_rtl: See `inline_char_diff`
+    
+    Returns:
+        Header and body strings, alternating
+    """
+    ...
+
+def char_opcodes_utf16(a: str, b: str) -> List[OpCode]:
+    """
+    Character-level opcodes turning a into b, with every index a UTF-16
+    code unit offset, as LSP positions and JavaScript strings count them.
+    
+    Characters are grapheme clusters, as in `inline_char_diff`, matched
+    like SequenceMatcher(None, a, b) would match single characters. A
+    character outside the Basic Multilingual Plane (e.g. most emoji) spans
+    two code units instead of one index, and each combining mark adds one
+    more.
+    
+    Args:
+        a: Original text
+        b: Modified text
+    
+    Returns:
+        The opcodes, with (i1, i2) offsets into a and (j1, j2) into b
+    """
+    ...
+
+def utf16_len(text: str) -> int:
+    """
+    Length of text in UTF-16 code units, e.g. 2 for "\U0001F600".
+    
+    Args:
+        text: Any string
+    
+    Returns:
+        The number of UTF-16 code units encoding text
+    """
+    ...
+
+def prefix_weighted_ratio(a: str, b: str, decay: float = 0.9) -> float:
+    """
+    Character similarity of a and b that counts matches near the start
+    more, e.g. to rank autocompletion candidates for a typed prefix, where
+    get_close_matches favors matches anywhere. Characters are grapheme
+    clusters, as in `inline_char_diff`.
+    
+    Like SequenceMatcher(None, a, b).ratio(), twice the matched characters
+    over len(a) + len(b), except that each matching block (i, j, size)
+    counts as size * decay ** max(i, j) characters: a block loses a factor
+    of decay for each character it starts away from the beginning of
+    either string.
+    
+    Args:
+        a: First string
+        b: Second string
+        decay: Weight kept per character of offset, in [0.0, 1.0]. 1.0
+            gives the plain ratio; 0.0 only counts a match at the start of
+            both strings
+    
+    Returns:
+        A score in [0.0, 1.0], 1.0 for equal strings
+    
+    Raises:
+        ValueError: If decay is outside [0.0, 1.0]
+    """
+    ...
+
+def hunk_density(a: List[str], b: List[str], n: int = 3) -> float:
+    """
+    Number of changed hunks divided by the total number of lines.
+    
+    A high value means changes are spread throughout the files and full
+    context may read better; a low value means a few tight hunks.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        n: Number of context lines used when grouping hunks
+    
+    Returns:
+        Hunks per line of a + b, 0.0 if nothing changed
+    """
+    ...
+
+def changed_bounds(
+    a: List[str],
+    b: List[str]
+) -> Optional[Tuple[Tuple[int, int], Tuple[int, int]]]:
+    """
+    The region of each sequence containing every change, e.g. for a
+    minimap of a file's changes.
+    
+    Ranges are 0-based and half-open like opcode indices, running from the
+    start of the first non-equal opcode to the end of the last. A side that
+    was only inserted into (or only deleted from) gets an empty range at
+    the insertion point.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        ((old_start, old_end), (new_start, new_end)), or None if a == b
+    """
+    ...
+
+def change_mask_old(a: List[str], b: List[str]) -> List[bool]:
+    """
+    Per-line change flags of a, e.g. for a change gutter or overview bar.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        len(a) flags, True where the line was deleted or replaced
+    """
+    ...
+
+def change_mask_new(a: List[str], b: List[str]) -> List[bool]:
+    """
+    Per-line change flags of b, e.g. for a change gutter or overview bar.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        len(b) flags, True where the line was inserted or replaced
+    """
+    ...
+
+def added_lines(a: List[str], b: List[str]) -> List[str]:
+    """
+    Lines of b that were inserted or replaced, as they are: no "+" prefix,
+    no headers, ready to pipe into grep-style tools.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        The changed lines of b in order, line endings kept as given
+    """
+    ...
+
+def removed_lines(a: List[str], b: List[str]) -> List[str]:
+    """
+    Lines of a that were deleted or replaced, as they are: no "-" prefix
+    and no headers.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        The changed lines of a in order, line endings kept as given
+    """
+    ...
+
+def diff_fingerprint(a: List[str], b: List[str], ignore_position: bool = False) -> int:
+    """
+    Stable 64-bit hash of the changes between a and b, e.g. to key a cache
+    of work that only needs redoing when the changes themselves change.
+    
+    Hashes the tag and the removed and added lines of every non-equal
+    opcode in order, plus where each one starts unless ignore_position.
+    Unchanged lines don't count, so the same edits give the same
+    fingerprint however the files around them differ in unchanged lines,
+    and every pair of equal inputs has the same fingerprint. The hash
+    (FNV-1a) is the same on every run, platform and Python version, but it
+    isn't cryptographic.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+        ignore_position: Leave line numbers out, so the same edits at other
+            places in the files fingerprint the same
+    
+    Returns:
+        The fingerprint, an unsigned 64-bit integer
+    """
+    ...
+
+def unchanged_ranges_old(a: List[str], b: List[str]) -> List[Tuple[int, int]]:
+    """
+    Line ranges of a that are unchanged in b, the complement of the lines
+    flagged by change_mask_old.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        1-based inclusive (first, last) ranges in order, runs of equal
+        lines that touch on this side merged
+    """
+    ...
+
+def unchanged_ranges_new(a: List[str], b: List[str]) -> List[Tuple[int, int]]:
+    """
+    Line ranges of b that were already in a, e.g. to skip re-linting code
+    a change didn't touch. The complement of the lines flagged by
+    change_mask_new.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        1-based inclusive (first, last) ranges in order, runs of equal
+        lines that touch on this side merged
+    """
+    ...
+
+def tsv_diff(a: List[str], b: List[str]) -> List[str]:
+    """
+    Diff as tab-separated rows for spreadsheet import.
+    
+    Each row is "op<TAB>old_lineno<TAB>new_lineno<TAB>content", with op one
+    of "equal", "delete", "insert", "replace-del" and "replace-ins". Line
+    numbers are 1-based and blank for the side the row doesn't exist on.
+    Backslashes, tabs and line breaks in content are escaped as \\, \t, \n
+    and \r.
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        One row per line, without a trailing newline
+    """
+    ...
+
+class RecordDiff(TypedDict):
+    added: List[Tuple[str, str]]
+    removed: List[Tuple[str, str]]
+    changed: List[Tuple[str, str, str]]
+
+def diff_records(
+    a: Union[Dict[str, str], List[str]],
+    b: Union[Dict[str, str], List[str]]
+) -> RecordDiff:
+    """
+    Diff two config-like sets of records by key.
+    
+    Records are sorted by key before diffing, so their order doesn't
+    matter. Lists are parsed as "key=value" lines, split at the first "="
+    with whitespace around both parts trimmed; blank lines are skipped and
+    a line without "=" is a key with an empty value. A repeated key keeps
+    its last value.
+    
+    Args:
+        a: Old records
+        b: New records
+    
+    Returns:
+        A dict with "added" and "removed" lists of (key, value) and a
+        "changed" list of (key, old_value, new_value), each sorted by key
+    """
+    ...
+
+class KeyedDiff(TypedDict):
+    added: List[Tuple[str, str]]
+    removed: List[Tuple[str, str]]
+    changed: List[Tuple[str, str, str, str]]
+
+def keyed_diff(
+    a: Union[Dict[str, str], List[Tuple[str, str]]],
+    b: Union[Dict[str, str], List[Tuple[str, str]]]
+) -> KeyedDiff:
+    """
+    Diff two keyed documents, e.g. translation files of key = "text"
+    entries: align the records by key, then diff the texts of the keys
+    both sides have character by character.
+    
+    Matching is by key like diff_records, so record order doesn't matter,
+    a repeated key keeps its last text and the results are sorted by key.
+    
+    Args:
+        a: Old records, as a dict or a list of (key, text) pairs
+        b: New records, in the same form
+    
+    Returns:
+        A dict with "added" and "removed" lists of (key, text) and a
+        "changed" list of (key, old_text, new_text, inline), inline being
+        inline_char_diff(old_text, new_text), e.g. "Save [-fi-]{+a+}l[-e-]{+l+}"
+    """
+    ...
+
+def sectioned_diff(
+    a: List[str],
+    b: List[str],
+    section_pattern: str,
+    n: int = 3,
+    lineterm: str = '\n'
+) -> Dict[Tuple[str, int], List[str]]:
+    """
+    Diff two documents section by section, e.g. a markdown changelog split
+    at its "## " headings.
+    
+    Both documents are split at the lines starting with section_pattern, a
+    literal prefix rather than a regular expression. Sections are paired by
+    title and each changed pair gets its own unified diff of just the two
+    sections, so line numbers count from the heading. A section on only one
+    side is reported wholesale, as all removed or all added lines. Lines
+    before the first heading form a section titled "".
+    
+    Args:
+        a: Old document lines
+        b: New document lines
+        section_pattern: Prefix of the heading lines, e.g. "## "
+        n: Number of context lines (default: 3)
+        lineterm: Line terminator of the header lines (default: '\n')
+    
+    Returns:
+        A dict of (title, occurrence) to the unified diff of that section,
+        for the sections that changed in the order they appear. The title
+        is the heading line without its line ending, and occurrence counts
+        the sections with that title from 0: a title repeated in a document
+        pairs its occurrences in order, each changed pair with a diff of
+        its own. Both file labels of a diff are its title.
+    
+    Raises:
+        ValueError: If section_pattern is empty
+    """
+    ...
+
+def map_diff(
+    a: Union[Dict[str, str], List[Tuple[str, str]]],
+    b: Union[Dict[str, str], List[Tuple[str, str]]],
+    value_context: Optional[int] = None
+) -> List[Tuple[str, str, Optional[str], Optional[str], Optional[str]]]:
+    """
+    Diff two ordered maps, e.g. flattened YAML or TOML, key by key.
+    
+    Unlike diff_records, order matters and every key of either map is
+    reported, in the order SequenceMatcher interleaves the two key
+    sequences. A key that moved is reported once, at whichever of its
+    positions comes first. Like a dict, a repeated key keeps its first
+    position and its last value.
+    
+    Args:
+        a: Old map, as a dict or a list of (key, value) pairs
+        b: New map, in the same form
+        value_context: If set, a changed key whose old or new value spans
+            several lines also gets a unified diff of the values, labelled
+            with the key, with this many context lines
+    
+    Returns:
+        A (tag, key, old_value, new_value, value_diff) tuple per key, with
+        tag one of "added", "removed", "changed" or "unchanged". old_value
+        is None for added keys and new_value for removed ones; value_diff
+        is a string for multiline changed values when value_context is
+        set, and None otherwise
+    """
+    ...
+
+def best_base(target: List[str], candidates: List[List[str]]) -> Tuple[int, float]:
+    """
+    Find the candidate closest to target, e.g. which stored previous
+    version a document came from.
+    
+    target is indexed once and compared with each candidate in turn.
+    Candidates whose quick upper bounds can't beat the best ratio so far
+    are skipped without a full comparison.
+    
+    Args:
+        target: Lines of the document to place
+        candidates: Possible previous versions of target
+    
+    Returns:
+        The index of the most similar candidate (the first one on ties)
+        and its SequenceMatcher(None, candidate, target).ratio()
+    
+    Raises:
+        ValueError: If candidates is empty
+    """
+    ...
+
+def get_close_matches_set(
+    query: List[str],
+    possibilities: List[List[str]],
+    n: int = 3,
+    cutoff: float = 0.6
+) -> List[List[str]]:
+    """
+    Like difflib.get_close_matches for token sets, such as tags or
+    keywords, where order doesn't matter.
+    
+    Similarity is the Jaccard index of the two multisets: the number of
+    tokens they share, counting repeats, over the size of their union. Two
+    empty lists are identical.
+    
+    Args:
+        query: Tokens to match
+        possibilities: Token lists to match query against
+        n: Maximum number of matches to return
+        cutoff: Minimum similarity in [0.0, 1.0]
+    
+    Returns:
+        Up to n possibilities scoring at least cutoff, best first, earlier
+        ones first on ties
+    
+    Raises:
+        ValueError: If n is 0 or cutoff is outside [0.0, 1.0]
+    """
+    ...
+
+def features() -> List[str]:
+    """
+    Names of the optional cargo features compiled into this build.
+    
+    Lets callers check for an optional capability and fall back gracefully
+    when it is missing.
+    
+    Returns:
+        Feature names, e.g. ["python"]
+    """
+    ...
+
+def benchmark_against_difflib(a: List[str], b: List[str]) -> Tuple[int, int, bool]:
+    """
+    Time unified_diff against Python's difflib.unified_diff on the same
+    input, e.g. to see the speedup on your own files.
+    
+    Both run with default arguments; converting the inputs is not timed.
+    Only available when built with the "bench" cargo feature, see features().
+    
+    Args:
+        a: First sequence of lines
+        b: Second sequence of lines
+    
+    Returns:
+        (rust_micros, python_micros, outputs_match): the microseconds each
+        took and whether their diffs are identical
+    """
+    ...
+
+class CorpusIndex:
+    """
+    A corpus of documents (lists of lines) indexed for repeated
+    get_close_matches-style queries.
+    
+    Documents are bucketed by length and by the lines they contain, so a
+    query only computes the full ratio against plausible candidates.
+    """
+    
+    def __init__(self, docs: List[List[str]]) -> None: ...
+    
+    def __len__(self) -> int: ...
+    
+    def query(self, query: List[str], k: int = 3, cutoff: float = 0.6) -> List[Tuple[int, float]]:
+        """
+        Find the documents most similar to query.
+        
+        Args:
+            query: Lines to look for
+            k: Maximum number of matches to return
+            cutoff: Minimum SequenceMatcher ratio in [0.0, 1.0]
+        
+        Returns:
+            Up to k (document index, ratio) pairs, best first, ties broken
+            by the lower index
+        
+        Raises:
+            ValueError: If k is not positive or cutoff is outside [0.0, 1.0]
+        """
+        ...
+
+class SequenceMatcher:
+    """
+    A matcher configuration for diffing many pairs in a row, reusing the
+    buffers of its searches between pairs.
+    
+    Each comparison needs a few scratch maps, a queue of regions and a list
+    of matches; a plain get_opcodes call allocates them for every pair,
+    while this class keeps them, with their grown capacity, until it is
+    done. In a tight loop over many pairs (millions, in a data pipeline)
+    that saves the allocator work per pair. Use it as a context manager to
+    free the buffers at the end of the batch:
+    
+        with SequenceMatcher() as m:
+            for a, b in pairs:
+                opcodes = m.compare(a, b)
+    
+    Results are the same as SequenceMatcher(isjunk, a, b) from difflib.
+    An instance isn't meant to be shared between threads.
+    """
+    
+    def __init__(
+        self,
+        isjunk: Optional[Callable[[str], bool]] = None,
+        autojunk: bool = True
+    ) -> None: ...
+    
+    def __enter__(self) -> "SequenceMatcher": ...
+    
+    def __exit__(self, exc_type: object, exc_value: object, traceback: object) -> bool:
+        """Free the pooled buffers. Exceptions aren't suppressed."""
+        ...
+    
+    def compare(self, a: List[str], b: List[str]) -> List[OpCode]:
+        """Opcodes turning a into b, like `get_opcodes`."""
+        ...
+    
+    def ratio(self, a: List[str], b: List[str]) -> float:
+        """Similarity of a and b, like difflib's SequenceMatcher.ratio()."""
+        ...
+    
+    def get_matching_blocks(
+        self,
+        a: List[str],
+        b: List[str],
+        include_sentinel: bool = True
+    ) -> List[Tuple[int, int, int]]:
+        """Matching blocks of a and b, like `get_matching_blocks`."""
+        ...
+
+class IncrementalMatcher:
+    """
+    A diff of a against b that follows edits to b, e.g. an editor buffer
+    diffed against its saved version on every keystroke.
+    
+    An edit only invalidates the matching blocks it overlaps: blocks
+    before it are kept, blocks after it are shifted by the change in
+    length, and only the lines between the two kept sets are matched
+    again, so a small edit to a large file costs about as much as diffing
+    the lines around it.
+    
+        m = IncrementalMatcher(saved, buffer)
+        m.edit(41, 42, ["the retyped line"])
+        opcodes = m.get_opcodes()
+    
+    After an edit the opcodes are always a valid diff of a and the new b,
+    but since kept blocks aren't reconsidered they can differ from a fresh
+    get_opcodes(a, b), e.g. when the edit makes a longer match across its
+    boundaries possible.
+    """
+    
+    def __init__(
+        self,
+        a: List[str],
+        b: List[str],
+        opcodes: Optional[List[OpCode]] = None
+    ) -> None:
+        """
+        Diff a against b, or pick up a diff computed earlier from its
+        opcodes.
+        
+        Raises:
+            ValueError: If opcodes don't cover a and b in order, or an equal
+                opcode spans lines that differ
+        """
+        ...
+    
+    @property
+    def a(self) -> List[str]: ...
+    
+    @property
+    def b(self) -> List[str]:
+        """b with every edit applied."""
+        ...
+    
+    def edit(self, start: int, end: int, lines: List[str]) -> None:
+        """
+        Replace b[start:end] with lines, like b[start:end] = lines, and
+        update the diff.
+        
+        Raises:
+            ValueError: If start > end or end > len(b)
+        """
+        ...
+    
+    def get_matching_blocks(self, include_sentinel: bool = True) -> List[Tuple[int, int, int]]:
+        """
+        Matching blocks of the current diff, ending with (len(a), len(b), 0)
+        unless include_sentinel is false, see `get_matching_blocks`.
+        """
+        ...
+    
+    def get_opcodes(self) -> List[OpCode]:
+        """Opcodes of the current diff."""
+        ...
+
+class DiffHandle:
+    """
+    A diff running on its own thread, returned by `diff_in_background`.
+    Its methods follow concurrent.futures.Future and are safe to call from
+    any thread.
+    """
+    
+    def cancel(self) -> bool:
+        """
+        Ask the diff to stop. The matcher checks between the regions it
+        searches, so a huge region can delay the stop.
+        
+        Returns:
+            False if the diff had already finished, True otherwise
+        """
+        ...
+    
+    def cancelled(self) -> bool:
+        """Whether the diff stopped early because of `cancel`."""
+        ...
+    
+    def done(self) -> bool:
+        """Whether the diff finished or stopped, so `result` won't block."""
+        ...
+    
+    def result(self, timeout: Optional[float] = None) -> List[OpCode]:
+        """
+        Opcodes of the diff, waiting with the GIL released.
+        
+        Args:
+            timeout: Seconds to wait at most, forever if None
+        
+        Raises:
+            TimeoutError: If the diff isn't done within timeout
+            concurrent.futures.CancelledError: If it was cancelled before
+                finishing
+            ValueError: If timeout is negative or not finite
+        """
+        ...
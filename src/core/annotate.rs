@@ -0,0 +1,123 @@
+//! Opcodes annotated with what kind of change they are, or with keys for
+//! the lines they render.
+
+use super::checksum::lines_checksum;
+use super::grouping::group_opcodes;
+use super::matcher::{OpCode, OpTag, SequenceMatcher};
+use super::text::split_line_end;
+use rustc_hash::FxHashMap;
+
+/// An opcode and whether it only re-indents lines or only changes their
+/// line endings
+#[derive(Debug, Clone)]
+pub struct AnnotatedOpCode {
+    pub op: OpCode,
+    /// A `replace` of lines by the same lines with different leading
+    /// whitespace, e.g. from a reformat
+    pub indent_only: bool,
+    /// A `replace` of lines by the same lines with different line endings,
+    /// e.g. LF turned into CRLF
+    pub eol_only: bool,
+}
+
+impl AnnotatedOpCode {
+    /// Whether the opcode is one of the cosmetic kinds of change
+    fn cosmetic(&self) -> bool {
+        self.indent_only || self.eol_only
+    }
+}
+
+/// Annotate `ops` of `a` -> `b`. A `replace` is split into runs of lines
+/// paired one to one, and the runs whose pairs are equal after
+/// `trim_start` are marked `indent_only`. With `line_endings`, the runs
+/// whose pairs only differ in their line breaks are marked `eol_only`.
+/// Lines left over when one side of the `replace` is longer stay a plain
+/// change, joined to the run before them unless that run is marked.
+pub fn annotate_opcodes(a: &[String], b: &[String], ops: &[OpCode], line_endings: bool) -> Vec<AnnotatedOpCode> {
+    let mut annotated = Vec::with_capacity(ops.len());
+    for op in ops {
+        if op.tag != OpTag::Replace {
+            annotated.push(AnnotatedOpCode { op: op.clone(), indent_only: false, eol_only: false });
+            continue;
+        }
+        let start = annotated.len();
+        let pairs = (op.i2 - op.i1).min(op.j2 - op.j1);
+        for k in 0..pairs {
+            let (i, j) = (op.i1 + k, op.j1 + k);
+            let indent_only = a[i].trim_start() == b[j].trim_start();
+            let eol_only = line_endings && !indent_only && split_line_end(&a[i]).0 == split_line_end(&b[j]).0;
+            match annotated[start..].last_mut() {
+                Some(last) if (last.indent_only, last.eol_only) == (indent_only, eol_only) => {
+                    last.op.i2 += 1;
+                    last.op.j2 += 1;
+                }
+                _ => annotated.push(AnnotatedOpCode {
+                    op: OpCode { tag: OpTag::Replace, i1: i, i2: i + 1, j1: j, j2: j + 1 },
+                    indent_only,
+                    eol_only,
+                }),
+            }
+        }
+        let (i, j) = (op.i1 + pairs, op.j1 + pairs);
+        if (i, j) == (op.i2, op.j2) {
+            continue;
+        }
+        match annotated[start..].last_mut() {
+            Some(last) if !last.cosmetic() => {
+                last.op.i2 = op.i2;
+                last.op.j2 = op.j2;
+            }
+            _ => {
+                let tag = if i < op.i2 { OpTag::Delete } else { OpTag::Insert };
+                annotated.push(AnnotatedOpCode {
+                    op: OpCode { tag, i1: i, i2: op.i2, j1: j, j2: op.j2 },
+                    indent_only: false,
+                    eol_only: false,
+                });
+            }
+        }
+    }
+    annotated
+}
+
+/// Hunks of `a` -> `b` with up to `n` lines of context, like
+/// `get_grouped_opcodes`, with each opcode annotated by `annotate_opcodes`
+pub fn annotated_hunks(a: &[String], b: &[String], n: usize, line_endings: bool) -> Vec<Vec<AnnotatedOpCode>> {
+    let matcher = SequenceMatcher::new(a, b);
+    group_opcodes(matcher.get_opcodes().to_vec(), n)
+        .iter()
+        .map(|group| annotate_opcodes(a, b, group, line_endings))
+        .collect()
+}
+
+/// Keys for rendering the lines of each of `ops` of `a` -> `b`, e.g. as
+/// React keys, so a viewer re-rendering a new diff reuses the nodes of the
+/// lines that persisted.
+///
+/// An `equal` opcode gets a key per line built from the CRC-32 of its
+/// content and the number of earlier unchanged lines with the same CRC,
+/// `=1a2b3c4d.0`, which doesn't depend on where the line is. A change gets
+/// a key per deleted line, then per inserted line, built from the position
+/// and the CRC: `-12:1a2b3c4d` for `a[12]`, `+7:1a2b3c4d` for `b[7]`. The
+/// keys of one diff are all distinct.
+pub fn render_keys(a: &[String], b: &[String], ops: &[OpCode]) -> Vec<Vec<String>> {
+    let crc = |line: &str| lines_checksum([line]);
+    let mut seen: FxHashMap<u32, usize> = FxHashMap::default();
+    let mut keys = Vec::with_capacity(ops.len());
+    for op in ops {
+        if op.tag == OpTag::Equal {
+            let equal = a[op.i1..op.i2].iter().map(|line| {
+                let hash = crc(line);
+                let occurrence = seen.entry(hash).or_default();
+                *occurrence += 1;
+                format!("={:08x}.{}", hash, *occurrence - 1)
+            });
+            keys.push(equal.collect());
+            continue;
+        }
+        let deleted = (op.i1..op.i2).map(|i| format!("-{}:{:08x}", i, crc(&a[i])));
+        let inserted = (op.j1..op.j2).map(|j| format!("+{}:{:08x}", j, crc(&b[j])));
+        keys.push(deleted.chain(inserted).collect());
+    }
+    keys
+}
@@ -0,0 +1,53 @@
+//! Terminal colors for unified diff lines, matching the default theme of
+//! Python 3.14's `difflib.unified_diff(..., color=True)`.
+
+/// What a rendered unified diff line is, which decides its color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineStyle {
+    /// `---` and `+++` file headers, and the base preamble before them
+    Header,
+    /// `@@` hunk headers, and notices rendered in their place
+    Hunk,
+    /// Unchanged lines, elision markers and `\ No newline` markers
+    Context,
+    Removed,
+    Added,
+}
+
+/// Resets every attribute, ending each colored line
+pub const ANSI_RESET: &str = "\x1b[0m";
+
+impl LineStyle {
+    /// Style of a line of `unified_diff_lines` by its prefix, header lines
+    /// having none
+    pub fn of(prefix: u8, text: &str) -> Self {
+        match prefix {
+            b'-' => LineStyle::Removed,
+            b'+' => LineStyle::Added,
+            0 if text.starts_with("@@") => LineStyle::Hunk,
+            0 => LineStyle::Header,
+            _ => LineStyle::Context,
+        }
+    }
+
+    /// ANSI escape starting a line of this style
+    pub fn ansi(self) -> &'static str {
+        match self {
+            LineStyle::Header => "\x1b[1m",
+            LineStyle::Hunk => "\x1b[34m",
+            LineStyle::Context => ANSI_RESET,
+            LineStyle::Removed => "\x1b[31m",
+            LineStyle::Added => "\x1b[32m",
+        }
+    }
+}
+
+/// `line` wrapped in the escapes of `style`, line ending included, as
+/// CPython colors it
+pub fn ansi_line(line: &str, style: LineStyle) -> String {
+    let mut colored = String::with_capacity(line.len() + 9);
+    colored.push_str(style.ansi());
+    colored.push_str(line);
+    colored.push_str(ANSI_RESET);
+    colored
+}
@@ -0,0 +1,115 @@
+//! Unified diffs of byte lines, for content that isn't valid UTF-8.
+//!
+//! Each byte is mapped to the char with the same value (Latin-1), which is
+//! a bijection, so the string pipeline compares exactly the same lines as a
+//! byte-level one would and its output maps back to the original bytes.
+//!
+//! Inputs that look binary get a single `Binary files a and b differ` line
+//! instead of a line diff, like GNU diff.
+
+use super::unified::{unified_diff, DiffLabels, UnifiedDiffOptions};
+use super::Error;
+
+/// File labels and line terminator of `unified_diff_bytes`, as bytes
+#[derive(Debug, Clone, Copy)]
+pub struct ByteDiffLabels<'a> {
+    pub fromfile: &'a [u8],
+    pub tofile: &'a [u8],
+    pub fromfiledate: &'a [u8],
+    pub tofiledate: &'a [u8],
+    pub lineterm: &'a [u8],
+}
+
+impl Default for ByteDiffLabels<'_> {
+    /// Empty labels and `b"\n"`, like `DiffLabels`
+    fn default() -> Self {
+        Self {
+            fromfile: b"",
+            tofile: b"",
+            fromfiledate: b"",
+            tofiledate: b"",
+            lineterm: b"\n",
+        }
+    }
+}
+
+/// Bytes of each input checked by `looks_binary`, like git
+const BINARY_SAMPLE: usize = 8000;
+
+/// Default `binary_threshold` of the Python `unified_diff_bytes`
+pub const DEFAULT_BINARY_THRESHOLD: f64 = 0.3;
+
+/// Whether the first 8000 bytes of `lines` look binary rather than text:
+/// they contain a NUL byte, or more than `threshold` of them are control
+/// characters other than whitespace and backspace. Bytes from 0x80 up
+/// count as text, since they make up UTF-8 and latin-1 characters.
+pub fn looks_binary(lines: &[&[u8]], threshold: f64) -> bool {
+    let sample = lines.iter().flat_map(|line| line.iter()).take(BINARY_SAMPLE);
+    let (mut total, mut control) = (0usize, 0usize);
+    for &byte in sample {
+        if byte == 0 {
+            return true;
+        }
+        total += 1;
+        if (byte < 0x20 && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x08 | 0x0b | 0x0c)) || byte == 0x7f {
+            control += 1;
+        }
+    }
+    total > 0 && control as f64 > threshold * total as f64
+}
+
+/// `bytes` with each byte as the char of the same value
+fn decode(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| char::from(byte)).collect()
+}
+
+/// Inverse of `decode`; every char of `text` must be below U+0100
+fn encode(text: &str) -> Vec<u8> {
+    text.chars().map(|c| c as u8).collect()
+}
+
+/// Unified diff of two sequences of byte lines with up to `n` lines of
+/// context, like `unified_diff` but with bytes in and out: content lines
+/// are prefixed with a single `b' '`, `b'-'` or `b'+'` byte and keep their
+/// bytes unchanged, valid UTF-8 or not.
+///
+/// With a `binary_threshold`, differing inputs either of which
+/// `looks_binary` give the single line `Binary files {fromfile} and
+/// {tofile} differ` instead. `None` always diffs lines.
+pub fn unified_diff_bytes(
+    a: &[&[u8]],
+    b: &[&[u8]],
+    labels: &ByteDiffLabels,
+    n: usize,
+    binary_threshold: Option<f64>,
+) -> Result<Vec<Vec<u8>>, Error> {
+    if let Some(threshold) = binary_threshold {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(Error::InvalidArgument("binary_threshold must be in [0.0, 1.0]"));
+        }
+        if a != b && (looks_binary(a, threshold) || looks_binary(b, threshold)) {
+            let mut line = b"Binary files ".to_vec();
+            for part in [labels.fromfile, b" and ", labels.tofile, b" differ", labels.lineterm] {
+                line.extend_from_slice(part);
+            }
+            return Ok(vec![line]);
+        }
+    }
+    let a: Vec<String> = a.iter().map(|line| decode(line)).collect();
+    let b: Vec<String> = b.iter().map(|line| decode(line)).collect();
+    let (fromfile, tofile) = (decode(labels.fromfile), decode(labels.tofile));
+    let (fromfiledate, tofiledate) = (decode(labels.fromfiledate), decode(labels.tofiledate));
+    let lineterm = decode(labels.lineterm);
+    let labels = DiffLabels {
+        fromfile: &fromfile,
+        tofile: &tofile,
+        fromfiledate: &fromfiledate,
+        tofiledate: &tofiledate,
+        lineterm: &lineterm,
+    };
+    let options = UnifiedDiffOptions {
+        n,
+        ..UnifiedDiffOptions::default()
+    };
+    Ok(unified_diff(&a, &b, &labels, &options)?.iter().map(|line| encode(line)).collect())
+}
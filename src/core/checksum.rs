@@ -0,0 +1,104 @@
+//! Hunk checksums: a non-standard `crc32=` comment after a hunk header's
+//! closing `@@`, where standard tools expect free text such as a function
+//! name, so they ignore it. Also diff fingerprints, hashes of just the
+//! changes of a diff for cache keys.
+
+use super::matcher::{OpTag, SequenceMatcher};
+
+/// Starts the checksum comment in a hunk header
+const TAG: &str = "crc32=";
+
+/// CRC-32 (IEEE) lookup table
+const TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn update(crc: u32, bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(crc, |crc, &byte| TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// CRC-32 of `lines`, each followed by `\n` so that splitting the same
+/// text differently gives a different checksum
+pub fn lines_checksum<'s>(lines: impl IntoIterator<Item = &'s str>) -> u32 {
+    !lines
+        .into_iter()
+        .fold(!0, |crc, line| update(update(crc, line.as_bytes()), b"\n"))
+}
+
+/// The comment appended to a hunk header for `checksum`
+pub fn format_checksum(checksum: u32) -> String {
+    format!("{}{:08x}", TAG, checksum)
+}
+
+/// The checksum in `rest`, the part of a hunk header from its closing
+/// ` @@`, if it has one
+pub fn parse_checksum(rest: &str) -> Option<u32> {
+    let comment = rest.strip_prefix(" @@")?;
+    let hex = comment.split_whitespace().find_map(|word| word.strip_prefix(TAG))?;
+    if hex.len() != 8 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// `rest` of a hunk header without its checksum comment
+pub fn strip_checksum(rest: &str) -> String {
+    let Some(start) = rest.find(&format!(" {}", TAG)) else {
+        return rest.to_string();
+    };
+    let end = rest[start + 1..]
+        .find(char::is_whitespace)
+        .map_or(rest.len(), |end| start + 1 + end);
+    format!("{}{}", &rest[..start], &rest[end..])
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a of `bytes` continuing from `hash`
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Hash of the changes between `a` and `b`: the tag and the removed and
+/// added lines of every non-equal opcode, in order, with the opcode's
+/// `i1` and `j1` unless `ignore_position`. Equal lines don't count, so
+/// diffs whose changes are the same hash the same however much context
+/// surrounds them, and with `ignore_position` wherever in the files they
+/// are. Equal inputs hash the same as any other pair without changes.
+///
+/// The hash is FNV-1a with every number and line length-prefixed as eight
+/// little-endian bytes, so it is the same on every run and platform, but
+/// it is not cryptographic.
+pub fn diff_fingerprint(a: &[String], b: &[String], ignore_position: bool) -> u64 {
+    let matcher = SequenceMatcher::new(a, b);
+    let mut hash = FNV_OFFSET;
+    let number = |hash: u64, value: usize| fnv1a(hash, &(value as u64).to_le_bytes());
+    for op in matcher.get_opcodes().iter().filter(|op| op.tag != OpTag::Equal) {
+        hash = fnv1a(hash, op.tag.as_str().as_bytes());
+        if !ignore_position {
+            hash = number(number(hash, op.i1), op.j1);
+        }
+        for lines in [&a[op.i1..op.i2], &b[op.j1..op.j2]] {
+            hash = number(hash, lines.len());
+            for line in lines {
+                hash = fnv1a(number(hash, line.len()), line.as_bytes());
+            }
+        }
+    }
+    hash
+}
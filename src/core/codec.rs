@@ -0,0 +1,121 @@
+//! Compact binary encoding of opcodes, for caching diffs.
+//!
+//! The encoding is a format version byte followed by one record per opcode:
+//! a tag byte and four zigzag varints. Indices are delta-encoded: `i1` and
+//! `j1` against the previous opcode's `i2` and `j2`, `i2` and `j2` against
+//! `i1` and `j1`. For the contiguous opcodes of a diff the first two deltas
+//! are zero and most records take five bytes.
+
+use super::matcher::{OpCode, OpTag};
+use super::Error;
+
+const FORMAT_VERSION: u8 = 1;
+
+pub fn serialize_opcodes(opcodes: &[OpCode]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + opcodes.len() * 5);
+    out.push(FORMAT_VERSION);
+    let (mut i, mut j) = (0, 0);
+    for op in opcodes {
+        out.push(tag_byte(op.tag));
+        for (value, base) in [(op.i1, i), (op.i2, op.i1), (op.j1, j), (op.j2, op.j1)] {
+            write_varint(&mut out, zigzag(value as i64 - base as i64));
+        }
+        (i, j) = (op.i2, op.j2);
+    }
+    out
+}
+
+pub fn deserialize_opcodes(bytes: &[u8]) -> Result<Vec<OpCode>, Error> {
+    let (&version, mut rest) = bytes.split_first().ok_or(Error::MalformedOpcodes("empty input"))?;
+    if version != FORMAT_VERSION {
+        return Err(Error::MalformedOpcodes("unknown format version"));
+    }
+    let mut opcodes = Vec::with_capacity(rest.len() / 5);
+    let (mut i, mut j) = (0, 0);
+    while let Some((&tag, tail)) = rest.split_first() {
+        rest = tail;
+        let tag = tag_from_byte(tag).ok_or(Error::MalformedOpcodes("unknown tag"))?;
+        let i1 = offset(i, read_varint(&mut rest)?)?;
+        let i2 = offset(i1, read_varint(&mut rest)?)?;
+        let j1 = offset(j, read_varint(&mut rest)?)?;
+        let j2 = offset(j1, read_varint(&mut rest)?)?;
+        opcodes.push(OpCode { tag, i1, i2, j1, j2 });
+        (i, j) = (i2, j2);
+    }
+    Ok(opcodes)
+}
+
+/// Opcodes as parallel columns, one entry per opcode: the tag numbered as
+/// in the binary encoding (0 equal, 1 delete, 2 insert, 3 replace) and the
+/// four indices
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpcodeArrays {
+    pub tags: Vec<u8>,
+    pub i1: Vec<u64>,
+    pub i2: Vec<u64>,
+    pub j1: Vec<u64>,
+    pub j2: Vec<u64>,
+}
+
+pub fn opcode_arrays(opcodes: &[OpCode]) -> OpcodeArrays {
+    let column = |index: fn(&OpCode) -> usize| opcodes.iter().map(|op| index(op) as u64).collect();
+    OpcodeArrays {
+        tags: opcodes.iter().map(|op| tag_byte(op.tag)).collect(),
+        i1: column(|op| op.i1),
+        i2: column(|op| op.i2),
+        j1: column(|op| op.j1),
+        j2: column(|op| op.j2),
+    }
+}
+
+fn tag_byte(tag: OpTag) -> u8 {
+    match tag {
+        OpTag::Equal => 0,
+        OpTag::Delete => 1,
+        OpTag::Insert => 2,
+        OpTag::Replace => 3,
+    }
+}
+
+fn tag_from_byte(byte: u8) -> Option<OpTag> {
+    match byte {
+        0 => Some(OpTag::Equal),
+        1 => Some(OpTag::Delete),
+        2 => Some(OpTag::Insert),
+        3 => Some(OpTag::Replace),
+        _ => None,
+    }
+}
+
+fn zigzag(delta: i64) -> u64 {
+    ((delta << 1) ^ (delta >> 63)) as u64
+}
+
+fn offset(base: usize, encoded: u64) -> Result<usize, Error> {
+    let delta = (encoded >> 1) as i64 ^ -((encoded & 1) as i64);
+    (base as i64)
+        .checked_add(delta)
+        .and_then(|index| usize::try_from(index).ok())
+        .ok_or(Error::MalformedOpcodes("index out of range"))
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, Error> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or(Error::MalformedOpcodes("truncated record"))?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::MalformedOpcodes("varint too long"))
+}
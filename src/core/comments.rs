@@ -0,0 +1,114 @@
+//! Recognizing comment-only lines, for diffs that ignore comment changes.
+
+use super::matcher::{OpCode, OpTag};
+use super::Error;
+use rustc_hash::FxHashSet;
+
+/// Comment syntax of a source language, e.g. `#` and `//` line prefixes and
+/// `/*`, `*/` block delimiters.
+///
+/// A line is comment-only when, after trimming whitespace, it starts with a
+/// line comment prefix, is inside a block comment, or holds nothing but
+/// block comments and line comments. Blank lines are only comment-only
+/// inside a block comment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommentSyntax {
+    /// Prefixes starting a comment that runs to the end of the line
+    pub prefixes: Vec<String>,
+    /// `(open, close)` delimiters of block comments, which may span lines
+    pub block: Option<(String, String)>,
+    /// Drop hunks whose changes only touch comment-only lines, instead of
+    /// just keeping comments from anchoring matches
+    pub ignore_changes: bool,
+}
+
+impl CommentSyntax {
+    /// Check that no prefix or delimiter is empty, which would make every
+    /// line a comment
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.prefixes.iter().any(String::is_empty) {
+            return Err(Error::InvalidArgument("comment prefixes must not be empty"));
+        }
+        if self.block.as_ref().is_some_and(|(open, close)| open.is_empty() || close.is_empty()) {
+            return Err(Error::InvalidArgument("block comment delimiters must not be empty"));
+        }
+        Ok(())
+    }
+
+    /// Whether each of `lines` is comment-only, following block comments
+    /// from one line to the next
+    pub fn comment_lines(&self, lines: &[String]) -> Vec<bool> {
+        let mut in_block = false;
+        lines.iter().map(|line| self.scan_line(line, &mut in_block)).collect()
+    }
+
+    /// Comment-only lines of `b`, to be used as matcher junk so comments
+    /// never anchor a match
+    pub fn junk<'a>(&self, b: &'a [String]) -> FxHashSet<&'a str> {
+        b.iter()
+            .zip(self.comment_lines(b))
+            .filter(|(_, comment)| *comment)
+            .map(|(line, _)| line.as_str())
+            .collect()
+    }
+
+    /// Whether `line` is comment-only, given and updating whether it starts
+    /// inside a block comment
+    fn scan_line(&self, line: &str, in_block: &mut bool) -> bool {
+        let mut rest = line.trim();
+        if rest.is_empty() {
+            return *in_block;
+        }
+        let mut code = false;
+        loop {
+            if *in_block {
+                let (_, close) = self.block.as_ref().expect("only block comments are entered");
+                match rest.find(close.as_str()) {
+                    Some(end) => {
+                        rest = rest[end + close.len()..].trim_start();
+                        *in_block = false;
+                    }
+                    None => return !code,
+                }
+            }
+            if rest.is_empty() {
+                return !code;
+            }
+            if !code && self.prefixes.iter().any(|prefix| rest.starts_with(prefix.as_str())) {
+                return true;
+            }
+            let Some((open, _)) = &self.block else {
+                return false;
+            };
+            // Code may be followed by a block comment left open for the
+            // next lines, so keep scanning for one
+            match rest.find(open.as_str()) {
+                Some(start) => {
+                    code |= start > 0;
+                    rest = &rest[start + open.len()..];
+                    *in_block = true;
+                }
+                None => return false,
+            }
+        }
+    }
+}
+
+/// Drop the groups whose changed lines, on both sides, are all comment-only
+/// per `syntax`
+pub fn drop_comment_groups(
+    a: &[String],
+    b: &[String],
+    groups: Vec<Vec<OpCode>>,
+    syntax: &CommentSyntax,
+) -> Vec<Vec<OpCode>> {
+    let (a_comments, b_comments) = (syntax.comment_lines(a), syntax.comment_lines(b));
+    let changes_code = |op: &OpCode| {
+        op.tag != OpTag::Equal
+            && (a_comments[op.i1..op.i2].contains(&false) || b_comments[op.j1..op.j2].contains(&false))
+    };
+    groups
+        .into_iter()
+        .filter(|group| group.iter().any(changes_code))
+        .collect()
+}
@@ -0,0 +1,108 @@
+//! Rendering context diffs, like Python's `difflib.context_diff`.
+
+use super::grouping::group_opcodes;
+use super::matcher::{OpTag, SequenceMatcher};
+use super::unified::DiffLabels;
+use super::Error;
+
+/// Characters written before each line of a context diff hunk, followed by
+/// a space
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextMarkers {
+    /// Lines of a `replace`, `!` by default
+    pub change: char,
+    /// Lines only in `a`, `-` by default
+    pub delete: char,
+    /// Lines only in `b`, `+` by default
+    pub insert: char,
+    /// Unchanged lines, a space by default
+    pub context: char,
+}
+
+impl Default for ContextMarkers {
+    fn default() -> Self {
+        Self {
+            change: '!',
+            delete: '-',
+            insert: '+',
+            context: ' ',
+        }
+    }
+}
+
+impl ContextMarkers {
+    fn prefix(&self, tag: OpTag) -> [char; 2] {
+        let marker = match tag {
+            OpTag::Equal => self.context,
+            OpTag::Delete => self.delete,
+            OpTag::Insert => self.insert,
+            OpTag::Replace => self.change,
+        };
+        [marker, ' ']
+    }
+}
+
+/// `start..stop` as a context diff range: `first,last` 1-based inclusive,
+/// just the line for one line, and the line before for an empty range
+pub fn format_range_context(start: usize, stop: usize) -> String {
+    let length = stop.saturating_sub(start);
+    let beginning = if length == 0 { start } else { start + 1 };
+    if length <= 1 {
+        format!("{}", beginning)
+    } else {
+        format!("{},{}", beginning, beginning + length - 1)
+    }
+}
+
+/// Compare two sequences of lines and generate the context diff, like
+/// Python's `difflib.context_diff` but with `markers` in place of `!`, `-`,
+/// `+` and the space. Each marker takes one column of the fixed-width line
+/// prefix, so control characters are rejected.
+pub fn context_diff(
+    a: &[String],
+    b: &[String],
+    labels: &DiffLabels,
+    n: usize,
+    markers: &ContextMarkers,
+) -> Result<Vec<String>, Error> {
+    if [markers.change, markers.delete, markers.insert, markers.context]
+        .iter()
+        .any(|marker| marker.is_control())
+    {
+        return Err(Error::InvalidArgument("context diff markers must not be control characters"));
+    }
+
+    let groups = group_opcodes(SequenceMatcher::new(a, b).get_opcodes().to_vec(), n);
+    let mut result = Vec::new();
+    let line = |prefix: [char; 2], text: &str| {
+        let mut line = String::with_capacity(text.len() + 2);
+        line.extend(prefix);
+        line.push_str(text);
+        line
+    };
+    for (index, group) in groups.iter().enumerate() {
+        if index == 0 {
+            let date = |date: &str| if date.is_empty() { String::new() } else { format!("\t{}", date) };
+            result.push(format!("*** {}{}{}", labels.fromfile, date(labels.fromfiledate), labels.lineterm));
+            result.push(format!("--- {}{}{}", labels.tofile, date(labels.tofiledate), labels.lineterm));
+        }
+        let first = &group[0];
+        let last = &group[group.len() - 1];
+        result.push(format!("***************{}", labels.lineterm));
+
+        result.push(format!("*** {} ****{}", format_range_context(first.i1, last.i2), labels.lineterm));
+        if group.iter().any(|op| matches!(op.tag, OpTag::Replace | OpTag::Delete)) {
+            for op in group.iter().filter(|op| op.tag != OpTag::Insert) {
+                result.extend(a[op.i1..op.i2].iter().map(|text| line(markers.prefix(op.tag), text)));
+            }
+        }
+
+        result.push(format!("--- {} ----{}", format_range_context(first.j1, last.j2), labels.lineterm));
+        if group.iter().any(|op| matches!(op.tag, OpTag::Replace | OpTag::Insert)) {
+            for op in group.iter().filter(|op| op.tag != OpTag::Delete) {
+                result.extend(b[op.j1..op.j2].iter().map(|text| line(markers.prefix(op.tag), text)));
+            }
+        }
+    }
+    Ok(result)
+}
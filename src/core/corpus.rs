@@ -0,0 +1,191 @@
+//! Fuzzy search over a corpus of documents, each a list of lines.
+
+use super::matcher::{calculate_ratio, Index, SequenceMatcher};
+use rustc_hash::{FxHashMap, FxHasher};
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+/// Documents indexed for repeated `get_close_matches`-style queries.
+///
+/// A query only runs the full `ratio` against documents that pass two cheap
+/// upper bounds: one from the document lengths, answered by the length
+/// buckets, and one from the lines the query shares with each document,
+/// answered by the line postings. Candidates are then scored best bound
+/// first, stopping once no remaining bound can beat the current top `k`.
+pub struct CorpusIndex {
+    docs: Vec<Vec<String>>,
+    /// Document ids by line count
+    by_len: BTreeMap<usize, Vec<usize>>,
+    /// `(document id, occurrences)` for each line hash
+    postings: FxHashMap<u64, Vec<(usize, usize)>>,
+}
+
+impl CorpusIndex {
+    pub fn build(docs: Vec<Vec<String>>) -> Self {
+        let mut by_len: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        let mut postings: FxHashMap<u64, Vec<(usize, usize)>> = FxHashMap::default();
+        for (id, doc) in docs.iter().enumerate() {
+            by_len.entry(doc.len()).or_default().push(id);
+            for (hash, count) in line_counts(doc) {
+                postings.entry(hash).or_default().push((id, count));
+            }
+        }
+        Self { docs, by_len, postings }
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&[String]> {
+        self.docs.get(id).map(Vec::as_slice)
+    }
+
+    /// Up to `k` `(document id, ratio)` pairs with a ratio of at least
+    /// `cutoff`, best first, ties broken by the lower id. Scores are those of
+    /// `SequenceMatcher::new(document, query).ratio()`, as in CPython's
+    /// `get_close_matches`.
+    pub fn query(&self, query: &[String], k: usize, cutoff: f64) -> Vec<(usize, f64)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let lq = query.len();
+
+        // 2 * min(lq, ld) / (lq + ld) >= cutoff bounds ld on both sides
+        let (min_len, max_len) = if cutoff <= 0.0 {
+            (0, usize::MAX)
+        } else {
+            let min_len = (cutoff * lq as f64 / (2.0 - cutoff)).ceil() as usize;
+            let max_len = (lq as f64 * (2.0 - cutoff) / cutoff).floor();
+            (min_len, if max_len >= usize::MAX as f64 { usize::MAX } else { max_len as usize })
+        };
+
+        // Lines shared with each document, an upper bound on its matches
+        let mut shared: FxHashMap<usize, usize> = FxHashMap::default();
+        for (hash, query_count) in line_counts(query) {
+            for &(id, count) in self.postings.get(&hash).into_iter().flatten() {
+                *shared.entry(id).or_default() += count.min(query_count);
+            }
+        }
+
+        let mut candidates: Vec<(f64, usize)> = self
+            .by_len
+            .range(min_len..=max_len)
+            .flat_map(|(&ld, ids)| {
+                let shared = &shared;
+                ids.iter().map(move |&id| {
+                    let bound = calculate_ratio(shared.get(&id).copied().unwrap_or(0), lq + ld);
+                    (bound, id)
+                })
+            })
+            .filter(|&(bound, _)| bound >= cutoff)
+            .collect();
+        candidates.sort_unstable_by(|x, y| y.0.total_cmp(&x.0).then(x.1.cmp(&y.1)));
+
+        let index = Index::build(query);
+        let mut best: Vec<(usize, f64)> = Vec::with_capacity(k.min(candidates.len()));
+        for (bound, id) in candidates {
+            if best.len() == k && bound < best[k - 1].1 {
+                break;
+            }
+            let score = SequenceMatcher::from_index(&self.docs[id], &index).ratio();
+            if score < cutoff {
+                continue;
+            }
+            let pos = best.partition_point(|&(other, other_score)| {
+                other_score > score || (other_score == score && other < id)
+            });
+            if pos < k {
+                best.insert(pos, (id, score));
+                best.truncate(k);
+            }
+        }
+        best
+    }
+}
+
+/// Occurrences of each distinct line of `doc`, keyed by line hash
+fn line_counts(doc: &[String]) -> FxHashMap<u64, usize> {
+    let mut counts: FxHashMap<u64, usize> = FxHashMap::default();
+    for line in doc {
+        let mut hasher = FxHasher::default();
+        line.hash(&mut hasher);
+        *counts.entry(hasher.finish()).or_default() += 1;
+    }
+    counts
+}
+
+/// `(index, ratio)` of the candidate most similar to `target`, the first
+/// one on ties, e.g. to find which stored version a document came from.
+/// `None` when there are no candidates.
+///
+/// One matcher indexes `target` once and is pointed at each candidate in
+/// turn; candidates whose `real_quick_ratio` or `quick_ratio` can't beat
+/// the best ratio so far are skipped without computing theirs.
+pub fn best_base(target: &[String], candidates: &[Vec<String>]) -> Option<(usize, f64)> {
+    let (first, rest) = candidates.split_first()?;
+    let mut matcher = SequenceMatcher::new(first, target);
+    let mut best = (0, matcher.ratio());
+    for (index, candidate) in rest.iter().enumerate() {
+        matcher.set_seq1(candidate);
+        if matcher.real_quick_ratio() <= best.1 || matcher.quick_ratio() <= best.1 {
+            continue;
+        }
+        let ratio = matcher.ratio();
+        if ratio > best.1 {
+            best = (index + 1, ratio);
+        }
+    }
+    Some(best)
+}
+
+/// Occurrences of each distinct element of `tokens`, as in `quick_ratio`
+fn token_counts(tokens: &[String]) -> FxHashMap<&str, usize> {
+    let mut counts: FxHashMap<&str, usize> = FxHashMap::default();
+    for token in tokens {
+        *counts.entry(token.as_str()).or_default() += 1;
+    }
+    counts
+}
+
+/// Jaccard similarity of `a` and `b` as multisets: the size of their
+/// intersection over that of their union, counting repeated elements, so
+/// order doesn't matter. 1.0 when both are empty.
+pub fn set_ratio(a: &[String], b: &[String]) -> f64 {
+    let (a_counts, b_counts) = (token_counts(a), token_counts(b));
+    let shared: usize = a_counts
+        .iter()
+        .filter_map(|(token, &count)| Some(count.min(*b_counts.get(token)?)))
+        .sum();
+    let union = a.len() + b.len() - shared;
+    if union == 0 {
+        1.0
+    } else {
+        shared as f64 / union as f64
+    }
+}
+
+/// Up to `n` `(index, score)` pairs of the `possibilities` whose
+/// `set_ratio` with `query` is at least `cutoff`, best first, ties broken
+/// by the lower index. Like `get_close_matches` for tag or keyword sets,
+/// where order doesn't count.
+pub fn get_close_matches_set(
+    query: &[String],
+    possibilities: &[Vec<String>],
+    n: usize,
+    cutoff: f64,
+) -> Vec<(usize, f64)> {
+    let mut scored: Vec<(usize, f64)> = possibilities
+        .iter()
+        .enumerate()
+        .map(|(index, tokens)| (index, set_ratio(query, tokens)))
+        .filter(|&(_, score)| score >= cutoff)
+        .collect();
+    scored.sort_by(|x, y| y.1.total_cmp(&x.1).then(x.0.cmp(&y.0)));
+    scored.truncate(n);
+    scored
+}
@@ -0,0 +1,468 @@
+//! Grouping opcodes into hunks.
+
+use super::matcher::{push_opcode, OpCode, OpTag};
+use super::Error;
+use std::ops::Range;
+
+/// Predicates describing where a scope opens and closes, used to keep hunks
+/// from splitting a brace-delimited block when a few extra context lines
+/// would avoid it.
+#[derive(Clone, Copy)]
+pub struct ScopeHints {
+    pub is_start: fn(&str) -> bool,
+    pub is_end: fn(&str) -> bool,
+    /// Maximum number of context lines added to either end of a hunk
+    pub max_extra: usize,
+}
+
+impl ScopeHints {
+    /// C-like scopes: a line ending in `{` opens, a line starting with `}` closes
+    pub fn braces(max_extra: usize) -> Self {
+        Self {
+            is_start: |line| line.trim_end().ends_with('{'),
+            is_end: |line| line.trim_start().starts_with('}'),
+            max_extra,
+        }
+    }
+}
+
+/// Group opcodes into hunks with up to `n` lines of context, like Python's
+/// `SequenceMatcher.get_grouped_opcodes`
+pub fn group_opcodes(codes: Vec<OpCode>, n: usize) -> Vec<Vec<OpCode>> {
+    group_opcodes_within(codes, n, 2 * n)
+}
+
+/// Like `group_opcodes`, but changes separated by at most `max_gap` equal
+/// lines share a hunk (`group_opcodes` uses `2 * n`, where the contexts of
+/// two hunks would meet)
+pub fn group_opcodes_within(mut codes: Vec<OpCode>, n: usize, max_gap: usize) -> Vec<Vec<OpCode>> {
+    if codes.is_empty() {
+        return Vec::new();
+    }
+    
+    // Special case: only equal operations (no changes)
+    if codes.len() == 1 && codes[0].tag == OpTag::Equal {
+        return Vec::new();
+    }
+    
+    // Fixup leading and trailing groups if they show no changes
+    // This matches Python's behavior to limit context lines
+    if !codes.is_empty() && codes[0].tag == OpTag::Equal {
+        let first = &mut codes[0];
+        first.i1 = first.i2.saturating_sub(n);
+        first.j1 = first.j2.saturating_sub(n);
+    }
+    
+    if !codes.is_empty() && codes[codes.len() - 1].tag == OpTag::Equal {
+        let last_idx = codes.len() - 1;
+        let last = &mut codes[last_idx];
+        last.i2 = (last.i1 + n).min(last.i2);
+        last.j2 = (last.j1 + n).min(last.j2);
+    }
+    
+    let mut groups: Vec<Vec<OpCode>> = Vec::new();
+    let mut group: Vec<OpCode> = Vec::new();
+
+    for code in codes.drain(..) {
+        // Split on large equal operations
+        if code.tag == OpTag::Equal && code.i2 - code.i1 > max_gap {
+            // End current group with trailing context
+            if !group.is_empty() {
+                group.push(OpCode {
+                    tag: OpTag::Equal,
+                    i1: code.i1,
+                    i2: (code.i1 + n).min(code.i2),
+                    j1: code.j1,
+                    j2: (code.j1 + n).min(code.j2),
+                });
+                groups.push(std::mem::take(&mut group));
+            }
+            // Start new group with leading context
+            group.push(OpCode {
+                tag: OpTag::Equal,
+                i1: code.i2.saturating_sub(n).max(code.i1),
+                i2: code.i2,
+                j1: code.j2.saturating_sub(n).max(code.j1),
+                j2: code.j2,
+            });
+        } else {
+            group.push(code);
+        }
+    }
+    
+    // Add final group if it exists and has non-equal operations or more than just context
+    if !group.is_empty() {
+        // Python's behavior: include group if it has changes or if it's not just a single equal operation
+        let has_changes = group.iter().any(|op| op.tag != OpTag::Equal);
+        let is_single_equal = group.len() == 1 && group[0].tag == OpTag::Equal;
+        
+        if has_changes || !is_single_equal {
+            groups.push(group);
+        }
+    }
+    
+    groups
+}
+
+/// Context bounds of `group_opcodes_adaptive`.
+///
+/// Changes less than `2 * max` equal lines apart form a cluster, and a
+/// cluster of `k` changes gets `min + (max - min) / k` lines of context
+/// (integer division). An isolated change shows `max` lines for orientation,
+/// and the context shrinks towards `min` as changes crowd together, keeping
+/// busy hunks short.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveContext {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl AdaptiveContext {
+    /// Context lines of a cluster of `changes` changes
+    pub fn context_for(&self, changes: usize) -> usize {
+        self.min + (self.max - self.min) / changes.max(1)
+    }
+}
+
+impl Default for AdaptiveContext {
+    /// One to five lines, so a lone change gets five and a cluster of four
+    /// or more gets two or fewer
+    fn default() -> Self {
+        Self { min: 1, max: 5 }
+    }
+}
+
+/// Context lines of every opcode's cluster (0 for `equal` opcodes), the
+/// pre-pass of `group_opcodes_adaptive`
+fn cluster_contexts(codes: &[OpCode], context: &AdaptiveContext) -> Vec<usize> {
+    let mut contexts = vec![0; codes.len()];
+    let mut cluster: Vec<usize> = Vec::new();
+    let mut finish = |cluster: &mut Vec<usize>| {
+        let n = context.context_for(cluster.len());
+        cluster.drain(..).for_each(|idx| contexts[idx] = n);
+    };
+    for (idx, code) in codes.iter().enumerate() {
+        if code.tag != OpTag::Equal {
+            cluster.push(idx);
+        } else if code.i2 - code.i1 >= 2 * context.max {
+            finish(&mut cluster);
+        }
+    }
+    finish(&mut cluster);
+    contexts
+}
+
+/// Group opcodes into hunks whose context follows the density of their
+/// changes, see `AdaptiveContext`. An equal run between two changes is
+/// hidden when it is longer than the contexts of both sides together (plus
+/// the smaller of the two in `compact` mode).
+pub fn group_opcodes_adaptive(codes: Vec<OpCode>, context: &AdaptiveContext, compact: bool) -> Vec<Vec<OpCode>> {
+    let contexts = cluster_contexts(&codes, context);
+    let change_context = |idx: Option<usize>| idx.map(|idx| contexts[idx]);
+
+    let mut groups: Vec<Vec<OpCode>> = Vec::new();
+    let mut group: Vec<OpCode> = Vec::new();
+    let push_equal = |group: &mut Vec<OpCode>, i1: usize, i2: usize, j1: usize| {
+        if i2 > i1 {
+            group.push(OpCode { tag: OpTag::Equal, i1, i2, j1, j2: j1 + (i2 - i1) });
+        }
+    };
+
+    let mut prev_change: Option<usize> = None;
+    for (idx, code) in codes.iter().enumerate() {
+        if code.tag != OpTag::Equal {
+            group.push(code.clone());
+            prev_change = Some(idx);
+            continue;
+        }
+        let next_change = (idx + 1..codes.len()).find(|&k| codes[k].tag != OpTag::Equal);
+        let before = change_context(prev_change);
+        let after = change_context(next_change);
+        let len = code.i2 - code.i1;
+        if let (Some(before), Some(after)) = (before, after) {
+            let slack = if compact { before.min(after) } else { 0 };
+            if len <= before + after + slack {
+                group.push(code.clone());
+                continue;
+            }
+        }
+        if let Some(before) = before {
+            push_equal(&mut group, code.i1, code.i1 + before.min(len), code.j1);
+            groups.push(std::mem::take(&mut group));
+        }
+        if let Some(after) = after {
+            let shown = after.min(len);
+            push_equal(&mut group, code.i2 - shown, code.i2, code.j2 - shown);
+        }
+    }
+    if prev_change.is_some() && !group.is_empty() {
+        groups.push(group);
+    }
+    groups
+}
+
+/// Number of lines a hunk of `group` shows: its context once, plus the
+/// removed and added lines
+fn hunk_lines(group: &[OpCode]) -> usize {
+    group
+        .iter()
+        .map(|op| match op.tag {
+            OpTag::Equal => op.i2 - op.i1,
+            _ => (op.i2 - op.i1) + (op.j2 - op.j1),
+        })
+        .sum()
+}
+
+/// Lines of a unified diff of `groups`: the two file headers, then each
+/// hunk's header and `hunk_lines`, or none at all without hunks. Missing
+/// final newline markers aren't counted.
+pub fn rendered_line_count(groups: &[Vec<OpCode>]) -> usize {
+    if groups.is_empty() {
+        return 0;
+    }
+    2 + groups.iter().map(|group| 1 + hunk_lines(group)).sum::<usize>()
+}
+
+/// Merge each hunk of fewer than `min_hunk_lines` lines with its neighbor,
+/// the one before it if there is one, so scattered one-line edits read as
+/// one hunk rather than many tiny ones. The equal lines between merged
+/// hunks become context, and hunks keep merging while the one being built
+/// or the next is still small.
+///
+/// `groups` must be in order without overlapping, as grouped from one
+/// opcode walk, so the lines between two of them are equal.
+pub fn merge_small_groups(groups: Vec<Vec<OpCode>>, min_hunk_lines: usize) -> Vec<Vec<OpCode>> {
+    let mut merged: Vec<Vec<OpCode>> = Vec::with_capacity(groups.len());
+    for group in groups {
+        match merged.last_mut() {
+            Some(last) if hunk_lines(last) < min_hunk_lines || hunk_lines(&group) < min_hunk_lines => {
+                let (i, j) = last.last().map_or((0, 0), |op| (op.i2, op.j2));
+                if let Some(first) = group.first().filter(|first| first.i1 > i) {
+                    push_opcode(last, OpCode { tag: OpTag::Equal, i1: i, i2: first.i1, j1: j, j2: first.j1 });
+                }
+                for op in group {
+                    push_opcode(last, op);
+                }
+            }
+            _ => merged.push(group),
+        }
+    }
+    merged
+}
+
+/// Nudge hunk boundaries of `groups` (grouped from `codes`) so brace-delimited
+/// scopes aren't cut in half, see `ScopeHints`.
+pub fn apply_scope_hints(
+    a: &[String],
+    b: &[String],
+    codes: &[OpCode],
+    groups: Vec<Vec<OpCode>>,
+    hints: &ScopeHints,
+) -> Vec<Vec<OpCode>> {
+    if groups.is_empty() {
+        return groups;
+    }
+
+    // Full equal runs, so context can only ever grow inside them
+    let equal_runs: Vec<(usize, usize, usize, usize)> = codes
+        .iter()
+        .filter(|op| op.tag == OpTag::Equal)
+        .map(|op| (op.i1, op.i2, op.j1, op.j2))
+        .collect();
+    let run_containing = |i1: usize, i2: usize| {
+        let idx = equal_runs.partition_point(|run| run.1 < i2);
+        equal_runs.get(idx).copied().filter(|run| run.0 <= i1)
+    };
+
+    let mut merged: Vec<Vec<OpCode>> = Vec::with_capacity(groups.len());
+    for mut group in groups {
+        let (open, unmatched_close) = scope_balance(a, b, &group, hints);
+
+        // Extend trailing context forward until open scopes are closed
+        if open > 0 {
+            if let Some(last) = group.last_mut().filter(|op| op.tag == OpTag::Equal) {
+                if let Some((_, run_i2, _, _)) = run_containing(last.i1, last.i2) {
+                    let limit = (last.i2 + hints.max_extra).min(run_i2);
+                    let mut depth = open;
+                    let mut i = last.i2;
+                    while i < limit && depth > 0 {
+                        let text = a[i].as_str();
+                        if (hints.is_end)(text) {
+                            depth -= 1;
+                        }
+                        if (hints.is_start)(text) {
+                            depth += 1;
+                        }
+                        i += 1;
+                    }
+                    if depth == 0 {
+                        last.j2 += i - last.i2;
+                        last.i2 = i;
+                    }
+                }
+            }
+        }
+
+        // Extend leading context backward until stray closers are opened
+        if unmatched_close > 0 {
+            if let Some(first) = group.first_mut().filter(|op| op.tag == OpTag::Equal) {
+                if let Some((run_i1, _, _, _)) = run_containing(first.i1, first.i2) {
+                    let limit = first.i1.saturating_sub(hints.max_extra).max(run_i1);
+                    let mut need = unmatched_close;
+                    let mut i = first.i1;
+                    while i > limit && need > 0 {
+                        let text = a[i - 1].as_str();
+                        if (hints.is_start)(text) {
+                            need -= 1;
+                        }
+                        if (hints.is_end)(text) {
+                            need += 1;
+                        }
+                        i -= 1;
+                    }
+                    if need == 0 {
+                        first.j1 -= first.i1 - i;
+                        first.i1 = i;
+                    }
+                }
+            }
+        }
+
+        // Groups whose context now touches share one equal run; join them
+        if let Some(prev) = merged.last_mut() {
+            let prev_last = prev.last().unwrap();
+            let first = &group[0];
+            if prev_last.tag == OpTag::Equal
+                && first.tag == OpTag::Equal
+                && prev_last.i2 >= first.i1
+            {
+                let joined = OpCode {
+                    tag: OpTag::Equal,
+                    i1: prev_last.i1,
+                    i2: first.i2,
+                    j1: prev_last.j1,
+                    j2: first.j2,
+                };
+                prev.pop();
+                prev.push(joined);
+                prev.extend(group.drain(1..));
+                continue;
+            }
+        }
+        merged.push(group);
+    }
+
+    merged
+}
+
+/// Returns `(open, unmatched_close)` for the lines a hunk would show,
+/// taking the worse of the two sides.
+fn scope_balance(a: &[String], b: &[String], group: &[OpCode], hints: &ScopeHints) -> (usize, usize) {
+    let scan = |lines: &[String]| {
+        let mut depth = 0usize;
+        let mut unmatched = 0usize;
+        for text in lines {
+            if (hints.is_end)(text) {
+                if depth > 0 {
+                    depth -= 1;
+                } else {
+                    unmatched += 1;
+                }
+            }
+            if (hints.is_start)(text) {
+                depth += 1;
+            }
+        }
+        (depth, unmatched)
+    };
+    let first = &group[0];
+    let last = &group[group.len() - 1];
+    let (open_a, close_a) = scan(&a[first.i1..last.i2]);
+    let (open_b, close_b) = scan(&b[first.j1..last.j2]);
+    (open_a.max(open_b), close_a.max(close_b))
+}
+
+/// Check that `groups` is a well-formed walk of `a[a_range]` and
+/// `b[b_range]`: every opcode is in bounds, shaped like its tag and
+/// contiguous with the previous one in its group; groups are in order
+/// without overlapping; and every line between, before and after them, like
+/// every `equal` opcode, is the same in both sequences.
+pub fn validate_grouped_opcodes(
+    a: &[String],
+    b: &[String],
+    groups: &[Vec<OpCode>],
+    a_range: Range<usize>,
+    b_range: Range<usize>,
+) -> Result<(), Error> {
+    validate_grouped_opcodes_by(a, b, groups, a_range, b_range, |x, y| x == y)
+}
+
+/// `validate_grouped_opcodes` with lines counted as the same when `same`
+/// says so, e.g. lines matched by their normalized keys or within a numeric
+/// tolerance
+pub fn validate_grouped_opcodes_by(
+    a: &[String],
+    b: &[String],
+    groups: &[Vec<OpCode>],
+    a_range: Range<usize>,
+    b_range: Range<usize>,
+    same: impl Fn(&str, &str) -> bool,
+) -> Result<(), Error> {
+    let invalid = |message: String| Err(Error::InvalidGroups(message));
+    let unchanged = |i1: usize, i2: usize, j1: usize, j2: usize| {
+        i2 - i1 == j2 - j1 && a[i1..i2].iter().zip(&b[j1..j2]).all(|(x, y)| same(x, y))
+    };
+
+    let (mut i, mut j) = (a_range.start, b_range.start);
+    for (index, group) in groups.iter().enumerate() {
+        if group.is_empty() {
+            return invalid(format!("group {} is empty", index));
+        }
+        for (k, op) in group.iter().enumerate() {
+            if op.i1 > op.i2 || op.j1 > op.j2 || op.i2 > a_range.end || op.j2 > b_range.end {
+                return invalid(format!("{:?} in group {} is out of bounds", op, index));
+            }
+            if k == 0 {
+                if op.i1 < i || op.j1 < j {
+                    return invalid(format!("group {} overlaps the one before it", index));
+                }
+                if !unchanged(i, op.i1, j, op.j1) {
+                    return invalid(format!("lines skipped before group {} differ between a and b", index));
+                }
+            } else if (op.i1, op.j1) != (i, j) {
+                return invalid(format!("{:?} in group {} isn't contiguous with the opcode before it", op, index));
+            }
+            let shaped = match op.tag {
+                OpTag::Equal => unchanged(op.i1, op.i2, op.j1, op.j2),
+                OpTag::Delete => op.i1 < op.i2 && op.j1 == op.j2,
+                OpTag::Insert => op.i1 == op.i2 && op.j1 < op.j2,
+                OpTag::Replace => op.i1 < op.i2 && op.j1 < op.j2,
+            };
+            if !shaped {
+                return invalid(format!("{:?} in group {} doesn't match its tag", op, index));
+            }
+            (i, j) = (op.i2, op.j2);
+        }
+    }
+    if !unchanged(i, a_range.end, j, b_range.end) {
+        return invalid("lines skipped after the last group differ between a and b".to_string());
+    }
+    Ok(())
+}
+
+/// `(added, removed, hunks)` counts of a diff
+pub type DiffStats = (usize, usize, usize);
+
+/// `(added, removed, hunks)` line counts of grouped opcodes
+pub fn diff_stats(groups: &[Vec<OpCode>]) -> DiffStats {
+    let mut added = 0;
+    let mut removed = 0;
+    for op in groups.iter().flatten() {
+        if op.tag != OpTag::Equal {
+            added += op.j2 - op.j1;
+            removed += op.i2 - op.i1;
+        }
+    }
+    (added, removed, groups.len())
+}
+
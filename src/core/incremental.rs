@@ -0,0 +1,126 @@
+//! Re-diffing after small edits to `b`, as in an editor diffing a buffer
+//! against its saved version on every keystroke.
+//!
+//! An edit only invalidates the matching blocks it overlaps. The blocks
+//! before it are kept as they are, those after it are shifted by the
+//! change in length, and only the gap between the two kept sets is
+//! matched again.
+
+use super::matcher::{check_range, opcodes_from_blocks, OpCode, OpTag, SequenceMatcher};
+use super::Error;
+use std::ops::Range;
+
+/// A diff of `a` -> `b` that follows edits to `b`, see the module docs.
+///
+/// The opcodes after an edit are a valid diff of `a` and the edited `b`,
+/// but since the kept blocks aren't reconsidered they can differ from
+/// those of a fresh `SequenceMatcher`, e.g. when the edit makes a longer
+/// match across its boundaries possible.
+#[derive(Debug, Clone)]
+pub struct IncrementalMatcher {
+    a: Vec<String>,
+    b: Vec<String>,
+    /// Matching blocks without the final sentinel
+    blocks: Vec<(usize, usize, usize)>,
+}
+
+impl IncrementalMatcher {
+    pub fn new(a: Vec<String>, b: Vec<String>) -> Self {
+        let blocks = SequenceMatcher::new(&a, &b).get_matching_blocks().to_vec();
+        Self::from_blocks(a, b, blocks)
+    }
+
+    /// Pick up a diff computed earlier from its `opcodes`, which must cover
+    /// `a` and `b` in order with `equal` opcodes over equal lines
+    pub fn from_opcodes(a: Vec<String>, b: Vec<String>, opcodes: &[OpCode]) -> Result<Self, Error> {
+        let (mut i, mut j) = (0, 0);
+        let mut blocks = Vec::new();
+        for op in opcodes {
+            let valid = (op.i1, op.j1) == (i, j)
+                && op.i1 <= op.i2
+                && op.j1 <= op.j2
+                && op.i2 <= a.len()
+                && op.j2 <= b.len()
+                && (op.tag != OpTag::Equal || a[op.i1..op.i2] == b[op.j1..op.j2]);
+            if !valid {
+                return Err(Error::InvalidArgument("opcodes don't describe a diff of a and b"));
+            }
+            if op.tag == OpTag::Equal {
+                blocks.push((op.i1, op.j1, op.i2 - op.i1));
+            }
+            (i, j) = (op.i2, op.j2);
+        }
+        if (i, j) != (a.len(), b.len()) {
+            return Err(Error::InvalidArgument("opcodes don't describe a diff of a and b"));
+        }
+        Ok(Self::from_blocks(a, b, blocks))
+    }
+
+    /// The matcher for `blocks`, in order and possibly with a sentinel
+    fn from_blocks(a: Vec<String>, b: Vec<String>, blocks: Vec<(usize, usize, usize)>) -> Self {
+        let mut collapsed: Vec<(usize, usize, usize)> = Vec::with_capacity(blocks.len());
+        for (i, j, k) in blocks.into_iter().filter(|&(_, _, k)| k > 0) {
+            match collapsed.last_mut() {
+                Some(last) if last.0 + last.2 == i && last.1 + last.2 == j => last.2 += k,
+                _ => collapsed.push((i, j, k)),
+            }
+        }
+        Self { a, b, blocks: collapsed }
+    }
+
+    pub fn a(&self) -> &[String] {
+        &self.a
+    }
+
+    pub fn b(&self) -> &[String] {
+        &self.b
+    }
+
+    /// Replace `b[range]` with `lines` and update the diff, matching only
+    /// the lines between the last block kept before the edit and the first
+    /// one kept after it
+    pub fn edit(&mut self, range: Range<usize>, lines: Vec<String>) -> Result<(), Error> {
+        check_range(&range, self.b.len())?;
+        let inserted = lines.len();
+        self.b.splice(range.clone(), lines);
+
+        let (mut before, mut after) = (Vec::new(), Vec::new());
+        for &(i, j, k) in &self.blocks {
+            if j + k <= range.start {
+                before.push((i, j, k));
+            } else if j >= range.end {
+                after.push((i, j - range.len() + inserted, k));
+            } else {
+                if j < range.start {
+                    before.push((i, j, range.start - j));
+                }
+                if j + k > range.end {
+                    let cut = range.end - j;
+                    after.push((i + cut, range.start + inserted, k - cut));
+                }
+            }
+        }
+
+        let (alo, blo) = before.last().map_or((0, 0), |&(i, j, k)| (i + k, j + k));
+        let (ahi, bhi) = after.first().map_or((self.a.len(), self.b.len()), |&(i, j, _)| (i, j));
+        let matcher = SequenceMatcher::new(&self.a[alo..ahi], &self.b[blo..bhi]);
+        let fresh = matcher.get_matching_blocks().iter().map(|&(i, j, k)| (alo + i, blo + j, k));
+
+        let blocks = before.into_iter().chain(fresh).chain(after).collect();
+        *self = Self::from_blocks(std::mem::take(&mut self.a), std::mem::take(&mut self.b), blocks);
+        Ok(())
+    }
+
+    /// Matching blocks of the current diff, ending with the `(len(a),
+    /// len(b), 0)` sentinel
+    pub fn get_matching_blocks(&self) -> Vec<(usize, usize, usize)> {
+        let mut blocks = self.blocks.clone();
+        blocks.push((self.a.len(), self.b.len(), 0));
+        blocks
+    }
+
+    /// Opcodes of the current diff
+    pub fn get_opcodes(&self) -> Vec<OpCode> {
+        opcodes_from_blocks(&self.get_matching_blocks())
+    }
+}
@@ -0,0 +1,167 @@
+//! Character-level diffs of single lines rendered inline, and character
+//! similarity for ranking completions.
+//!
+//! A character here is an extended grapheme cluster, what a reader sees
+//! as one character: a base letter with its combining marks (Hebrew
+//! points, Arabic harakat, accents), an emoji ZWJ sequence or a `\r\n`
+//! pair. Changes are marked around whole clusters, so a mark is never cut
+//! off from its letter.
+
+use super::matcher::{calculate_ratio, OpCode, OpTag, SequenceMatcher};
+use super::Error;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// First strong isolate and pop directional isolate, around a run of
+/// right-to-left text
+const ISOLATE: (&str, &str) = ("\u{2068}", "\u{2069}");
+
+/// Delimiters wrapped around deleted and inserted runs by `inline_char_diff_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InlineMarkers<'a> {
+    pub delete: (&'a str, &'a str),
+    pub insert: (&'a str, &'a str),
+    /// Wrap each marked run holding right-to-left text in U+2068 FIRST
+    /// STRONG ISOLATE and U+2069 POP DIRECTIONAL ISOLATE, inside its
+    /// markers, so bidi reordering keeps the markers around their run
+    pub isolate_rtl: bool,
+}
+
+impl Default for InlineMarkers<'_> {
+    /// wdiff-style `[-deleted-]` and `{+inserted+}`, without isolates
+    fn default() -> Self {
+        Self {
+            delete: ("[-", "-]"),
+            insert: ("{+", "+}"),
+            isolate_rtl: false,
+        }
+    }
+}
+
+impl<'a> InlineMarkers<'a> {
+    /// Markers for a run of `text` around a deletion (or insertion), with
+    /// the isolates added inside them if `text` needs them
+    pub(crate) fn around(&self, (open, close): (&'a str, &'a str), text: &str) -> [&'a str; 4] {
+        if self.isolate_rtl && has_rtl(text) {
+            [open, ISOLATE.0, ISOLATE.1, close]
+        } else {
+            [open, "", "", close]
+        }
+    }
+}
+
+/// Whether `text` has a character of a right-to-left script: Hebrew,
+/// Arabic, Syriac, Thaana, N'Ko and the others in their blocks, with their
+/// presentation forms
+pub(crate) fn has_rtl(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(
+            u32::from(c),
+            0x0590..=0x08ff | 0xfb1d..=0xfdff | 0xfe70..=0xfefe | 0x10800..=0x10fff | 0x1e800..=0x1efff
+        )
+    })
+}
+
+/// The extended grapheme clusters of `text`, in order
+pub(crate) fn graphemes(text: &str) -> Vec<String> {
+    text.graphemes(true).map(String::from).collect()
+}
+
+/// Inline character diff of `a` -> `b` with the default markers, e.g.
+/// `foo[-bar-]{+baz+}qux`
+pub fn inline_char_diff(a: &str, b: &str) -> String {
+    inline_char_diff_with(a, b, &InlineMarkers::default())
+}
+
+/// Inline character diff of `a` -> `b`. Equal runs are left bare; a replace
+/// is written as its deletion followed by its insertion.
+pub fn inline_char_diff_with(a: &str, b: &str, markers: &InlineMarkers) -> String {
+    let (a_chars, b_chars) = (graphemes(a), graphemes(b));
+    let matcher = SequenceMatcher::new(&a_chars, &b_chars);
+
+    let mut out = String::with_capacity(a.len() + b.len());
+    // Equal runs have no delimiters
+    let mut push_run = |chars: &[String], delimiters: Option<(&str, &str)>| {
+        let text = chars.concat();
+        let [open, isolate, pop, close] = delimiters.map_or([""; 4], |delimiters| markers.around(delimiters, &text));
+        for part in [open, isolate, &text, pop, close] {
+            out.push_str(part);
+        }
+    };
+    for op in matcher.get_opcodes() {
+        let deleted = &a_chars[op.i1..op.i2];
+        let inserted = &b_chars[op.j1..op.j2];
+        match op.tag {
+            OpTag::Equal => push_run(deleted, None),
+            OpTag::Delete => push_run(deleted, Some(markers.delete)),
+            OpTag::Insert => push_run(inserted, Some(markers.insert)),
+            OpTag::Replace => {
+                push_run(deleted, Some(markers.delete));
+                push_run(inserted, Some(markers.insert));
+            }
+        }
+    }
+    out
+}
+
+/// Default `decay` of `prefix_weighted_ratio`
+pub const DEFAULT_PREFIX_DECAY: f64 = 0.9;
+
+/// Character (grapheme cluster) similarity of `a` and `b` favoring
+/// matches near the start,
+/// e.g. to rank completions of a typed prefix.
+///
+/// Like `ratio`, twice the matched characters over the total length, but
+/// each matching block `(i, j, size)` counts `size * decay^max(i, j)`
+/// characters, so a block loses a factor of `decay` for every character
+/// it starts away from the beginning on either side. A `decay` of 1.0
+/// gives the plain `ratio`; 0.0 only counts a block at the very start.
+pub fn prefix_weighted_ratio(a: &str, b: &str, decay: f64) -> Result<f64, Error> {
+    if !(0.0..=1.0).contains(&decay) {
+        return Err(Error::InvalidArgument("decay must be in [0.0, 1.0]"));
+    }
+    let (a_chars, b_chars) = (graphemes(a), graphemes(b));
+    let matcher = SequenceMatcher::new(&a_chars, &b_chars);
+    let weighted: f64 = matcher
+        .get_matching_blocks()
+        .iter()
+        .map(|&(i, j, size)| size as f64 * decay.powf(i.max(j) as f64))
+        .sum();
+    let total = a_chars.len() + b_chars.len();
+    Ok(if total == 0 { calculate_ratio(0, 0) } else { 2.0 * weighted / total as f64 })
+}
+
+/// Length of `text` in UTF-16 code units, the unit LSP and JavaScript
+/// count string offsets in
+pub fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+/// Character-level opcodes of `a` -> `b` with every index a UTF-16 code
+/// unit offset instead of a character index, so a character outside the
+/// Basic Multilingual Plane spans two units, and one with combining marks
+/// spans one unit more per mark
+pub fn char_opcodes_utf16(a: &str, b: &str) -> Vec<OpCode> {
+    let (a_chars, b_chars) = (graphemes(a), graphemes(b));
+    // UTF-16 offset of every character index, plus the end of the text
+    let offsets = |chars: &[String]| {
+        let mut offsets = Vec::with_capacity(chars.len() + 1);
+        offsets.push(0);
+        offsets.extend(chars.iter().scan(0, |offset, c| {
+            *offset += utf16_len(c);
+            Some(*offset)
+        }));
+        offsets
+    };
+    let (a_offsets, b_offsets) = (offsets(&a_chars), offsets(&b_chars));
+    SequenceMatcher::new(&a_chars, &b_chars)
+        .get_opcodes()
+        .iter()
+        .map(|op| OpCode {
+            tag: op.tag,
+            i1: a_offsets[op.i1],
+            i2: a_offsets[op.i2],
+            j1: b_offsets[op.j1],
+            j2: b_offsets[op.j2],
+        })
+        .collect()
+}
@@ -0,0 +1,75 @@
+//! Diffs as JSON Patch (RFC 6902) documents, treating each side as a JSON
+//! array of line strings.
+//!
+//! JSON Patch operations apply one after another, so each index refers to
+//! the array as the operations before it left it. Emitting the changes
+//! from the end of the array backwards keeps every index in terms of `a`:
+//! nothing before a change has moved yet when it applies.
+
+use super::matcher::{OpTag, SequenceMatcher};
+use std::fmt::Write;
+
+/// Append `text` to `out` as a JSON string literal
+fn push_json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Append one operation object, with a `value` for `add` and `replace`
+fn push_operation(out: &mut String, op: &str, index: usize, value: Option<&str>) {
+    if out.len() > 1 {
+        out.push(',');
+    }
+    let _ = write!(out, "{{\"op\":\"{}\",\"path\":\"/{}\"", op, index);
+    if let Some(value) = value {
+        out.push_str(",\"value\":");
+        push_json_string(out, value);
+    }
+    out.push('}');
+}
+
+/// A JSON Patch turning the array of lines `a` into `b`, as a compact JSON
+/// array of `add`, `remove` and `replace` operations with `/N` paths.
+///
+/// Opcodes are emitted last one first, so removals come in descending
+/// index order and every path is an index into `a` as the operations
+/// before it left it. A `replace` opcode replaces the lines both sides
+/// have, then removes the rest of the old lines or adds the rest of the new
+/// ones after them.
+pub fn json_patch(a: &[String], b: &[String]) -> String {
+    let mut out = String::from("[");
+    let matcher = SequenceMatcher::new(a, b);
+    for op in matcher.get_opcodes().iter().rev() {
+        if op.tag == OpTag::Equal {
+            continue;
+        }
+        let common = (op.i2 - op.i1).min(op.j2 - op.j1);
+        let new_lines = b[op.j1..op.j2].iter().enumerate();
+        for (k, line) in new_lines.clone().take(common) {
+            push_operation(&mut out, "replace", op.i1 + k, Some(line));
+        }
+        for i in (op.i1 + common..op.i2).rev() {
+            push_operation(&mut out, "remove", i, None);
+        }
+        for (k, line) in new_lines.skip(common) {
+            push_operation(&mut out, "add", op.i1 + k, Some(line));
+        }
+    }
+    out.push(']');
+    out
+}
@@ -0,0 +1,1443 @@
+//! `SequenceMatcher` and the opcodes it produces.
+
+use super::grouping::validate_grouped_opcodes;
+use super::inline::graphemes;
+use super::Error;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpTag {
+    Equal,
+    Delete,
+    Insert,
+    Replace,
+}
+
+impl OpTag {
+    /// Tag name as used by Python's `SequenceMatcher.get_opcodes`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OpTag::Equal => "equal",
+            OpTag::Delete => "delete",
+            OpTag::Insert => "insert",
+            OpTag::Replace => "replace",
+        }
+    }
+
+    pub fn parse(tag: &str) -> Option<Self> {
+        match tag {
+            "equal" => Some(OpTag::Equal),
+            "delete" => Some(OpTag::Delete),
+            "insert" => Some(OpTag::Insert),
+            "replace" => Some(OpTag::Replace),
+            _ => None,
+        }
+    }
+
+    /// The tag describing the same edit applied from `b` back to `a`
+    pub fn inverted(self) -> Self {
+        match self {
+            OpTag::Delete => OpTag::Insert,
+            OpTag::Insert => OpTag::Delete,
+            tag => tag,
+        }
+    }
+}
+
+/// One edit step, like a tuple of Python's `SequenceMatcher.get_opcodes`:
+/// `tag` turns `a[i1..i2]` into `b[j1..j2]`.
+///
+/// Indices are 0-based and half-open, as in Python slicing, so an empty side
+/// has `i1 == i2`. Patch formats number lines from 1 with inclusive ends;
+/// `one_based` converts to that convention.
+#[derive(Debug, Clone)]
+pub struct OpCode {
+    pub tag: OpTag,
+    pub i1: usize,
+    pub i2: usize,
+    pub j1: usize,
+    pub j2: usize,
+}
+
+impl OpCode {
+    /// `(i1, i2, j1, j2)` as 1-based inclusive line ranges: `a[i1..i2]` is
+    /// lines `i1 + 1` through `i2`. An empty side becomes `(i1 + 1, i1)`, an
+    /// empty range starting after line `i1`.
+    pub fn one_based(&self) -> (usize, usize, usize, usize) {
+        (self.i1 + 1, self.i2, self.j1 + 1, self.j2)
+    }
+
+    pub fn inverted(&self) -> Self {
+        OpCode {
+            tag: self.tag.inverted(),
+            i1: self.j1,
+            i2: self.j2,
+            j1: self.i1,
+            j2: self.i2,
+        }
+    }
+}
+
+/// `Index::estimate_cost` of diffing `a` -> `b` with the default
+/// configuration, autojunk included
+pub fn estimate_cost(a: &[String], b: &[String]) -> u64 {
+    Index::build(b).estimate_cost(a)
+}
+
+/// Turn the opcodes of an `a` -> `b` diff into the opcodes of `b` -> `a`
+pub fn invert_opcodes(opcodes: &[OpCode]) -> Vec<OpCode> {
+    opcodes.iter().map(OpCode::inverted).collect()
+}
+
+/// `opcodes` with every `equal` opcode longer than `max_equal_run` lines
+/// split into consecutive `equal` opcodes of at most that many, e.g. to
+/// render a long unchanged stretch in bounded chunks
+pub fn split_equal_runs(opcodes: &[OpCode], max_equal_run: usize) -> Result<Vec<OpCode>, Error> {
+    if max_equal_run == 0 {
+        return Err(Error::InvalidArgument("max_equal_run must be positive"));
+    }
+    let mut split = Vec::with_capacity(opcodes.len());
+    for op in opcodes {
+        if op.tag != OpTag::Equal {
+            split.push(op.clone());
+            continue;
+        }
+        for start in (0..op.i2 - op.i1).step_by(max_equal_run) {
+            let len = max_equal_run.min(op.i2 - op.i1 - start);
+            split.push(OpCode {
+                tag: OpTag::Equal,
+                i1: op.i1 + start,
+                i2: op.i1 + start + len,
+                j1: op.j1 + start,
+                j2: op.j1 + start + len,
+            });
+        }
+    }
+    Ok(split)
+}
+
+/// `b`'s element-to-positions map and junk decision, built once and shared
+/// read-only by any number of matchers diffing against the same `b`, e.g.
+/// one document compared to many others, possibly across threads.
+#[derive(Debug, Clone)]
+pub struct Index<'a> {
+    b: &'a [String],
+    b2j: FxHashMap<&'a str, Vec<usize>>,
+    /// Elements that never anchor a match but may extend one, like CPython's
+    /// `bjunk`
+    junk: FxHashSet<&'a str>,
+    /// The junk the index was built with, before `symmetric_junk` added
+    /// popular elements to `junk`, for rebuilding it for other sequences
+    given_junk: FxHashSet<&'a str>,
+}
+
+impl<'a> Index<'a> {
+    pub fn build(b: &'a [String]) -> Self {
+        SequenceMatcherBuilder::new().build_index(b)
+    }
+
+    /// Index with `junk` elements. With `symmetric_junk`, elements popular in
+    /// `b` become junk instead of being dropped outright.
+    pub fn with_junk(b: &'a [String], junk: FxHashSet<&'a str>, symmetric_junk: bool) -> Self {
+        SequenceMatcherBuilder::new()
+            .junk(junk)
+            .symmetric_junk(symmetric_junk)
+            .build_index(b)
+    }
+
+    pub fn b(&self) -> &'a [String] {
+        self.b
+    }
+
+    /// Rough cost of diffing `a` against the indexed `b`: the number of
+    /// positions of `b` listed for the elements of `a`, which is how many
+    /// times the inner loop of a `find_longest_match` over everything runs.
+    /// An estimate for scheduling, not a bound: later searches only cover
+    /// the regions between matches, while a sequence with few matches is
+    /// searched again region by region.
+    pub fn estimate_cost(&self, a: &[String]) -> u64 {
+        a.iter()
+            .filter_map(|elt| self.b2j.get(elt.as_str()))
+            .map(|positions| positions.len() as u64)
+            .sum()
+    }
+
+    /// A copy of `b2j`, mapping each element of `b` that can anchor a match
+    /// to its positions in `b`, for seeing why a diff aligned the way it
+    /// did. Junk and elements dropped by `autojunk` as popular are already
+    /// gone. The copy is a snapshot: it doesn't follow later changes to the
+    /// index, e.g. by a matcher with `symmetric_junk`.
+    pub fn get_b2j(&self) -> HashMap<String, Vec<usize>> {
+        self.b2j
+            .iter()
+            .map(|(&elt, positions)| (elt.to_string(), positions.clone()))
+            .collect()
+    }
+
+    fn chain_b(&mut self, autojunk: bool, symmetric_junk: bool) {
+        let b = self.b;
+        
+        // Pre-size HashMap based on estimated unique elements (usually ~20-50% of total)
+        let estimated_unique = (b.len() / 3).max(16);
+        self.b2j.reserve(estimated_unique);
+        
+        // Build b2j mapping like Python's difflib
+        for (i, elt) in b.iter().enumerate() {
+            self.b2j.entry(elt.as_str()).or_default().push(i);
+        }
+
+        // Junk never anchors a match
+        if !self.junk.is_empty() {
+            let junk = &self.junk;
+            self.b2j.retain(|elt, _| !junk.contains(elt));
+        }
+        
+        // Apply popularity heuristic like Python's difflib
+        // Remove elements that appear too frequently (> 1% of total).
+        // `b2j` is iterated in hash order, but the popular set is decided
+        // before anything is removed and removal order can't change it, so
+        // the result is the same on every run and platform
+        let n = b.len();
+        if autojunk && n >= 200 {
+            let ntest = n / 100 + 1;
+            let mut popular_elements = Vec::new();
+            
+            for (&elt, indices) in &self.b2j {
+                if indices.len() > ntest {
+                    popular_elements.push(elt);
+                }
+            }
+            
+            for elt in popular_elements {
+                self.b2j.remove(elt);
+                if symmetric_junk {
+                    self.junk.insert(elt);
+                }
+            }
+        }
+    }
+}
+
+/// Progress reporting of `SequenceMatcherBuilder::progress`
+#[derive(Clone)]
+pub struct Progress {
+    /// Called with the estimated fraction of the work done, in `[0, 1]`
+    pub callback: Arc<dyn Fn(f64) + Send + Sync>,
+    /// Minimum time between two calls, except for the final `1.0`
+    pub interval: Duration,
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress").field("interval", &self.interval).finish_non_exhaustive()
+    }
+}
+
+/// How `find_longest_match` picks the match to anchor a region on
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AnchorScoring {
+    /// The longest match, the earliest in `a` then in `b` on ties, like
+    /// CPython
+    #[default]
+    Longest,
+    /// The match with the best length minus `penalty` per line of
+    /// imbalance it forces between the unmatched lines of `a` and `b`: a
+    /// match on a diagonal beyond those of its region's start and end
+    /// leaves more lines unmatched on one side than the other, each of
+    /// which becomes an edit. Prefers matches near the region's diagonal
+    /// over longer ones that would drag the rest of the diff out of
+    /// place, as in reordered blocks. Any match still beats none, and
+    /// `0.0` behaves like `Longest`.
+    GapPenalty(f64),
+}
+
+impl AnchorScoring {
+    /// Check that a gap penalty is finite and not negative
+    pub fn validate(&self) -> Result<(), Error> {
+        match self {
+            AnchorScoring::GapPenalty(penalty) if !(penalty.is_finite() && *penalty >= 0.0) => {
+                Err(Error::InvalidArgument("gap penalty must be finite and not negative"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Configuration for `SequenceMatcher` and `Index`, set fluently:
+///
+/// ```
+/// # use difflib_rs::core::SequenceMatcherBuilder;
+/// # let (a, b) = (vec!["x".to_string()], vec!["y".to_string()]);
+/// let matcher = SequenceMatcherBuilder::new()
+///     .isjunk(|line| line.trim().is_empty())
+///     .autojunk(false)
+///     .build(&a, &b);
+/// ```
+///
+/// The defaults match CPython's `SequenceMatcher(None, a, b)`: no junk,
+/// `autojunk` on, `symmetric_junk` off, no `max_occurrences_per_element`
+/// and `AnchorScoring::Longest`.
+#[derive(Debug, Clone)]
+pub struct SequenceMatcherBuilder<'a> {
+    junk: FxHashSet<&'a str>,
+    isjunk: Option<fn(&str) -> bool>,
+    autojunk: bool,
+    symmetric_junk: bool,
+    max_occurrences: Option<usize>,
+    unique_anchors: bool,
+    anchor_scoring: AnchorScoring,
+    neighbor_tiebreak: bool,
+    strip_common_affixes: bool,
+    progress: Option<Progress>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Default for SequenceMatcherBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            junk: FxHashSet::default(),
+            isjunk: None,
+            autojunk: true,
+            symmetric_junk: false,
+            max_occurrences: None,
+            unique_anchors: false,
+            anchor_scoring: AnchorScoring::Longest,
+            neighbor_tiebreak: false,
+            strip_common_affixes: false,
+            progress: None,
+            cancel: None,
+        }
+    }
+}
+
+impl<'a> SequenceMatcherBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Elements that never anchor a match but may extend one
+    pub fn junk(mut self, junk: FxHashSet<&'a str>) -> Self {
+        self.junk = junk;
+        self
+    }
+
+    /// Add `junk` to the elements set with `junk`
+    pub fn extend_junk(mut self, junk: impl IntoIterator<Item = &'a str>) -> Self {
+        self.junk.extend(junk);
+        self
+    }
+
+    /// Elements of `b` for which `isjunk` returns true are junk, on top of
+    /// those given to `junk`
+    pub fn isjunk(mut self, isjunk: fn(&str) -> bool) -> Self {
+        self.isjunk = Some(isjunk);
+        self
+    }
+
+    /// Drop elements making up more than 1% of a sequence of 200 or more
+    /// elements from `b2j`, like CPython's `autojunk`
+    pub fn autojunk(mut self, autojunk: bool) -> Self {
+        self.autojunk = autojunk;
+        self
+    }
+
+    /// Make elements popular in `a` or `b` junk rather than just dropping
+    /// them from `b2j`, so they can extend matches on both sides
+    pub fn symmetric_junk(mut self, symmetric_junk: bool) -> Self {
+        self.symmetric_junk = symmetric_junk;
+        self
+    }
+
+    /// Scan at most `max` positions of each element of `a` in `b` (the
+    /// first ones in the range being searched) when looking for the longest
+    /// match. Unlike `autojunk`, popular elements still anchor matches, so
+    /// long runs of repeated lines still line up, but `find_longest_match`
+    /// stays `O(len(a) * max)` per call on highly repetitive input. The
+    /// price is that a match starting at a later occurrence can be missed,
+    /// giving a less minimal diff. Must be positive.
+    pub fn max_occurrences_per_element(mut self, max: usize) -> Self {
+        self.max_occurrences = Some(max);
+        self
+    }
+
+    /// Before searching, match the lines occurring exactly once in `a` and
+    /// once in `b` (the anchors of patience diff), keeping the longest run
+    /// of them in the same order on both sides, and only search the gaps
+    /// between them. This keeps unique lines such as function signatures
+    /// aligned where a longer match of common lines like `}` would pull
+    /// the diff out of place. Junk lines are never anchors.
+    pub fn unique_anchors(mut self, unique_anchors: bool) -> Self {
+        self.unique_anchors = unique_anchors;
+        self
+    }
+
+    /// How `find_longest_match` picks the match each region is anchored
+    /// on. The default `AnchorScoring::Longest` matches CPython; see
+    /// `AnchorScoring::validate` for the valid penalties.
+    pub fn anchor_scoring(mut self, anchor_scoring: AnchorScoring) -> Self {
+        self.anchor_scoring = anchor_scoring;
+        self
+    }
+
+    /// Among matches `find_longest_match` scores equally, pick the one
+    /// whose neighboring lines, the pair just before it and the pair just
+    /// after it, are most alike character by character, instead of the
+    /// earliest. In repetitive code this anchors a run like `}` or a
+    /// blank line next to the lines that were edited rather than next to
+    /// unrelated ones. Costs a character diff of two line pairs per tied
+    /// match, so it is off by default.
+    pub fn neighbor_tiebreak(mut self, neighbor_tiebreak: bool) -> Self {
+        self.neighbor_tiebreak = neighbor_tiebreak;
+        self
+    }
+
+    /// Match the common prefix and suffix of the two sequences line by line
+    /// and search only the lines between them, a large speedup for a
+    /// small edit to a large file. Junk and popularity are still decided
+    /// over the whole of `b`, and the blocks are in whole-sequence
+    /// positions. Off by default since the result can differ from
+    /// CPython's: it may anchor a run that a longer match elsewhere would
+    /// have displaced, e.g. `[x, y]` at the start of `[x, y, x, y, z]`
+    /// against `[x, y, z]`.
+    pub fn strip_common_affixes(mut self, strip_common_affixes: bool) -> Self {
+        self.strip_common_affixes = strip_common_affixes;
+        self
+    }
+
+    /// Report progress while computing matching blocks, at most once per
+    /// `interval` and with a final `1.0`. The estimate is the fraction of
+    /// lines of both sequences already resolved as matched or changed; it
+    /// only approximates the time left, since the regions still to search
+    /// can be much slower or faster than the ones done.
+    pub fn progress(mut self, interval: Duration, callback: impl Fn(f64) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Progress {
+            callback: Arc::new(callback),
+            interval,
+        });
+        self
+    }
+
+    /// Give up the search for matching blocks once `cancel` is set, from
+    /// any thread. A cancelled search makes `try_get_matching_blocks` and
+    /// `try_get_opcodes` fail with `Error::Cancelled`; the infallible
+    /// getters panic instead, so a matcher that may be cancelled should
+    /// only be read through the `try_` ones.
+    pub fn cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub fn build(&self, a: &'a [String], b: &'a [String]) -> SequenceMatcher<'a> {
+        let index = self.build_index(b);
+        let mut matcher = SequenceMatcher::with_index(a, Cow::Owned(index), self.autojunk, self.symmetric_junk);
+        matcher.max_occurrences = self.max_occurrences;
+        matcher.unique_anchors = self.unique_anchors;
+        matcher.anchor_scoring = self.anchor_scoring;
+        matcher.neighbor_tiebreak = self.neighbor_tiebreak;
+        matcher.strip_common_affixes = self.strip_common_affixes;
+        matcher.progress = self.progress.clone();
+        matcher.cancel = self.cancel.clone();
+        matcher
+    }
+
+    pub fn build_index(&self, b: &'a [String]) -> Index<'a> {
+        let mut junk = self.junk.clone();
+        if let Some(isjunk) = self.isjunk {
+            junk.extend(b.iter().map(String::as_str).filter(|elt| isjunk(elt)));
+        }
+        let mut index = Index {
+            b,
+            b2j: FxHashMap::default(),
+            junk: junk.clone(),
+            given_junk: junk,
+        };
+        index.chain_b(self.autojunk, self.symmetric_junk);
+        index
+    }
+}
+
+pub struct SequenceMatcher<'a> {
+    a: &'a [String],
+    b: &'a [String],
+    /// Borrowed when the matcher was made from a shared index, owned otherwise
+    index: Cow<'a, Index<'a>>,
+    autojunk: bool,
+    /// Also treat elements popular in either sequence as junk
+    symmetric_junk: bool,
+    /// Positions of each element scanned by `find_longest_match`, all if
+    /// `None`
+    max_occurrences: Option<usize>,
+    /// Match lines unique to both sides before searching, see
+    /// `SequenceMatcherBuilder::unique_anchors`
+    unique_anchors: bool,
+    /// See `SequenceMatcherBuilder::anchor_scoring`
+    anchor_scoring: AnchorScoring,
+    /// See `SequenceMatcherBuilder::neighbor_tiebreak`
+    neighbor_tiebreak: bool,
+    /// See `SequenceMatcherBuilder::strip_common_affixes`
+    strip_common_affixes: bool,
+    progress: Option<Progress>,
+    /// See `SequenceMatcherBuilder::cancel`
+    cancel: Option<Arc<AtomicBool>>,
+    matching_blocks: OnceCell<Vec<(usize, usize, usize)>>,
+    opcodes: OnceCell<Vec<OpCode>>,
+    /// `a` and `b` interned for bulk match extension, built on first use
+    #[cfg(feature = "simd")]
+    ids: OnceCell<(Vec<u32>, Vec<u32>)>,
+}
+
+impl<'a> SequenceMatcher<'a> {
+    pub fn new(a: &'a [String], b: &'a [String]) -> Self {
+        Self::with_junk(a, b, FxHashSet::default(), false)
+    }
+
+    /// Matcher with `junk` elements (typically those an `isjunk` predicate
+    /// accepts). With `symmetric_junk`, elements popular in `a` or `b` become
+    /// junk too, so they can extend matches on both sides without anchoring.
+    pub fn with_junk(a: &'a [String], b: &'a [String], junk: FxHashSet<&'a str>, symmetric_junk: bool) -> Self {
+        SequenceMatcherBuilder::new()
+            .junk(junk)
+            .symmetric_junk(symmetric_junk)
+            .build(a, b)
+    }
+
+    /// Matcher of `a` against an already built `index`, without rebuilding
+    /// `b2j`
+    pub fn from_index(a: &'a [String], index: &'a Index<'a>) -> Self {
+        Self::with_index(a, Cow::Borrowed(index), true, false)
+    }
+
+    fn with_index(a: &'a [String], index: Cow<'a, Index<'a>>, autojunk: bool, symmetric_junk: bool) -> Self {
+        let mut matcher = Self {
+            a,
+            b: index.b,
+            index,
+            autojunk,
+            symmetric_junk,
+            max_occurrences: None,
+            unique_anchors: false,
+            anchor_scoring: AnchorScoring::Longest,
+            neighbor_tiebreak: false,
+            strip_common_affixes: false,
+            progress: None,
+            cancel: None,
+            matching_blocks: OnceCell::new(),
+            opcodes: OnceCell::new(),
+            #[cfg(feature = "simd")]
+            ids: OnceCell::new(),
+        };
+        matcher.apply_symmetric_junk();
+        matcher
+    }
+    
+    /// Diff a new `a` against the same `b`, keeping `b`'s index unless
+    /// `symmetric_junk` needs it rebuilt for the new `a`. As with
+    /// `set_seq2`, the rebuilt index starts again from the junk the matcher
+    /// was built with, so elements popular only in an earlier `a` or `b`
+    /// aren't junk for the new pair, and the matcher diffs like a new one:
+    ///
+    /// ```
+    /// # use difflib_rs::core::SequenceMatcherBuilder;
+    /// let lines = |prefix: &str| (0..300).map(|i| format!("{prefix}{i}")).collect::<Vec<_>>();
+    /// // `x` is popular in `a1`, so `symmetric_junk` makes it junk there
+    /// let mut a1 = lines("a");
+    /// (0..10).for_each(|i| a1[i * 30] = "x".to_string());
+    /// let (mut a2, mut b) = (lines("c"), lines("b"));
+    /// (a2[150], b[150]) = ("x".to_string(), "x".to_string());
+    ///
+    /// let builder = SequenceMatcherBuilder::new().symmetric_junk(true);
+    /// let mut reused = builder.build(&a1, &b);
+    /// reused.set_seq1(&a2);
+    /// let fresh = builder.build(&a2, &b);
+    /// assert_eq!(reused.get_matching_blocks(), fresh.get_matching_blocks());
+    /// assert_eq!(fresh.get_matching_blocks(), [(150, 150, 1), (300, 300, 0)]);
+    /// ```
+    pub fn set_seq1(&mut self, a: &'a [String]) {
+        if self.a.as_ptr() == a.as_ptr() && self.a.len() == a.len() {
+            return;
+        }
+        self.a = a;
+        self.clear_cache();
+        if self.autojunk && self.symmetric_junk {
+            self.rebuild_index();
+        }
+    }
+
+    pub fn set_seq2(&mut self, b: &'a [String]) {
+        if self.b.as_ptr() == b.as_ptr() && self.b.len() == b.len() {
+            return;
+        }
+        self.b = b;
+        self.clear_cache();
+        self.rebuild_index();
+    }
+
+    fn clear_cache(&mut self) {
+        self.matching_blocks.take();
+        self.opcodes.take();
+        #[cfg(feature = "simd")]
+        self.ids.take();
+    }
+
+    fn rebuild_index(&mut self) {
+        let mut index = Index {
+            b: self.b,
+            b2j: FxHashMap::default(),
+            junk: self.index.given_junk.clone(),
+            given_junk: self.index.given_junk.clone(),
+        };
+        index.chain_b(self.autojunk, self.symmetric_junk);
+        self.index = Cow::Owned(index);
+        self.apply_symmetric_junk();
+    }
+
+    /// Same popularity test as `Index::chain_b` on `a`, going beyond CPython
+    fn apply_symmetric_junk(&mut self) {
+        let n = self.a.len();
+        if !self.autojunk || !self.symmetric_junk || n < 200 {
+            return;
+        }
+        let ntest = n / 100 + 1;
+        let mut counts: FxHashMap<&str, usize> = FxHashMap::default();
+        for elt in self.a {
+            *counts.entry(elt.as_str()).or_default() += 1;
+        }
+        let index = self.index.to_mut();
+        for (elt, count) in counts {
+            if count > ntest {
+                index.b2j.remove(elt);
+                index.junk.insert(elt);
+            }
+        }
+    }
+
+    /// A copy of the `b2j` this matcher searches, see `Index::get_b2j`. With
+    /// `symmetric_junk`, elements popular in `a` are gone from it as well.
+    pub fn get_b2j(&self) -> HashMap<String, Vec<usize>> {
+        self.index.get_b2j()
+    }
+
+    /// Similarity in `[0, 1]`, `2 * matches / (len(a) + len(b))`, like
+    /// CPython's `ratio`
+    pub fn ratio(&self) -> f64 {
+        let matches = self.get_matching_blocks().iter().map(|&(_, _, k)| k).sum();
+        calculate_ratio(matches, self.a.len() + self.b.len())
+    }
+
+    /// `ratio` of just `a[a_range]` and `b[b_range]`, e.g. to score how
+    /// similar the two sides of one hunk are. Matches are searched within
+    /// the ranges against the whole-sequence index, so no new matcher is
+    /// built. `1.0` when both ranges are empty.
+    pub fn ratio_range(&self, a_range: Range<usize>, b_range: Range<usize>) -> Result<f64, Error> {
+        check_range(&a_range, self.a.len())?;
+        check_range(&b_range, self.b.len())?;
+        let blocks = self.matching_blocks_in(a_range.start, a_range.end, b_range.start, b_range.end);
+        let matches = blocks.iter().map(|&(_, _, k)| k).sum();
+        Ok(calculate_ratio(matches, a_range.len() + b_range.len()))
+    }
+
+    /// Local similarity along `b`: for each window of `window` consecutive
+    /// lines of `b`, in order, the `ratio` of the window and the lines of
+    /// `a` the opcodes align with it. A single value for the whole pair
+    /// when `b` is no longer than `window`.
+    ///
+    /// Unlike `ratio_range`, nothing is matched again: matches are the
+    /// window's lines in `equal` opcodes, and the aligned lines of `a` are
+    /// found by spreading each opcode's `a` lines evenly over its `b`
+    /// lines. Lines deleted after a line of `b` count in the windows with
+    /// that line, and those deleted before the first line in the first
+    /// window. Takes O(len(a) + len(b)) after matching.
+    pub fn similarity_profile(&self, window: usize) -> Result<Vec<f64>, Error> {
+        if window == 0 {
+            return Err(Error::InvalidArgument("window must be positive"));
+        }
+        let (la, lb) = (self.a.len(), self.b.len());
+        // a_at[j] is the number of lines of `a` aligned before line j of `b`,
+        // matched[j] the number of matched lines of `b` before line j
+        let mut a_at = vec![0; lb + 1];
+        let mut matched = vec![0; lb + 1];
+        for op in self.get_opcodes() {
+            for j in op.j1..op.j2 {
+                a_at[j] = op.i1 + (j - op.j1) * (op.i2 - op.i1) / (op.j2 - op.j1);
+                matched[j + 1] = matched[j] + usize::from(op.tag == OpTag::Equal);
+            }
+        }
+        if lb <= window {
+            return Ok(vec![calculate_ratio(matched[lb], la + lb)]);
+        }
+        (a_at[0], a_at[lb]) = (0, la);
+        let ratio = |start: usize, end: usize| {
+            calculate_ratio(matched[end] - matched[start], (a_at[end] - a_at[start]) + (end - start))
+        };
+        Ok((0..=lb - window).map(|start| ratio(start, start + window)).collect())
+    }
+
+    /// `ratio` if it is at least `cutoff`, `None` otherwise, stopping early
+    /// on inputs that can't reach it.
+    ///
+    /// The matching blocks are searched as for `ratio`, keeping an upper
+    /// bound on the total match: the lines matched so far plus the shorter
+    /// side of every region still to search. As soon as that bound's ratio
+    /// falls below `cutoff` the search is abandoned, so near-duplicate
+    /// filtering doesn't pay for the full diff of clearly different inputs.
+    /// A completed search is cached for `get_matching_blocks`.
+    pub fn ratio_streaming(&self, cutoff: f64) -> Option<f64> {
+        let length = self.a.len() + self.b.len();
+        let blocks = match self.matching_blocks.get() {
+            Some(blocks) => blocks,
+            None => {
+                let (la, lb) = (self.a.len(), self.b.len());
+                let blocks = self.matching_blocks_within(0, la, 0, lb, Some(cutoff), &mut MatchBuffers::default())?;
+                self.matching_blocks.get_or_init(|| blocks)
+            }
+        };
+        let ratio = calculate_ratio(blocks.iter().map(|&(_, _, k)| k).sum(), length);
+        (ratio >= cutoff).then_some(ratio)
+    }
+
+    /// Upper bound on `ratio` from the elements both sequences share,
+    /// ignoring their order
+    pub fn quick_ratio(&self) -> f64 {
+        let mut avail: FxHashMap<&str, usize> = FxHashMap::default();
+        for elt in self.b {
+            *avail.entry(elt.as_str()).or_default() += 1;
+        }
+        let mut matches = 0;
+        for elt in self.a {
+            if let Some(count) = avail.get_mut(elt.as_str()).filter(|count| **count > 0) {
+                *count -= 1;
+                matches += 1;
+            }
+        }
+        calculate_ratio(matches, self.a.len() + self.b.len())
+    }
+
+    /// Upper bound on `ratio` from the lengths alone
+    pub fn real_quick_ratio(&self) -> f64 {
+        let (la, lb) = (self.a.len(), self.b.len());
+        calculate_ratio(la.min(lb), la + lb)
+    }
+
+    /// `get_matching_blocks`, searching with the allocations pooled in
+    /// `buffers` instead of fresh ones, for diffing many pairs in a row
+    pub fn get_matching_blocks_with(&self, buffers: &mut MatchBuffers) -> &[(usize, usize, usize)] {
+        self.matching_blocks.get_or_init(|| {
+            self.matching_blocks_within(0, self.a.len(), 0, self.b.len(), None, buffers)
+                .expect("a search without cutoff completes unless cancelled")
+        })
+    }
+
+    /// Opcodes, computed on first use and cached
+    pub fn get_opcodes(&self) -> &[OpCode] {
+        self.opcodes.get_or_init(|| opcodes_from_blocks(self.get_matching_blocks()))
+    }
+
+    /// Opcodes grouped into hunks with up to `n` lines of context
+    pub fn get_grouped_opcodes(&self, n: usize) -> Vec<Vec<OpCode>> {
+        super::grouping::group_opcodes(self.get_opcodes().to_vec(), n)
+    }
+
+    /// `get_grouped_opcodes`, checked with `validate_grouped_opcodes` when
+    /// `validate` is set
+    pub fn get_grouped_opcodes_checked(&self, n: usize, validate: bool) -> Result<Vec<Vec<OpCode>>, Error> {
+        let groups = self.get_grouped_opcodes(n);
+        if validate {
+            validate_grouped_opcodes(self.a, self.b, &groups, 0..self.a.len(), 0..self.b.len())?;
+        }
+        Ok(groups)
+    }
+
+    /// Matching blocks ending in the sentinel, computed on first use and
+    /// cached. The zero-size `(len(a), len(b), 0)` sentinel is always last,
+    /// as in CPython, so that turning blocks into opcodes can close the
+    /// final change with one more loop step.
+    pub fn get_matching_blocks(&self) -> &[(usize, usize, usize)] {
+        self.matching_blocks.get_or_init(|| self.compute_matching_blocks())
+    }
+
+    /// `get_matching_blocks`, or `Error::Cancelled` if the search was
+    /// cancelled through `SequenceMatcherBuilder::cancel` before it
+    /// finished
+    pub fn try_get_matching_blocks(&self) -> Result<&[(usize, usize, usize)], Error> {
+        if let Some(blocks) = self.matching_blocks.get() {
+            return Ok(blocks);
+        }
+        let blocks = self
+            .matching_blocks_within(0, self.a.len(), 0, self.b.len(), None, &mut MatchBuffers::default())
+            .ok_or(Error::Cancelled)?;
+        Ok(self.matching_blocks.get_or_init(|| blocks))
+    }
+
+    /// `get_opcodes`, or `Error::Cancelled` like `try_get_matching_blocks`
+    pub fn try_get_opcodes(&self) -> Result<&[OpCode], Error> {
+        let blocks = self.try_get_matching_blocks()?;
+        Ok(self.opcodes.get_or_init(|| opcodes_from_blocks(blocks)))
+    }
+
+    /// Lines of each matching block, without the zero-length sentinel. The
+    /// two sides of a block are equal, so the lines come from `a`.
+    pub fn matching_slices(&self) -> Vec<&'a [String]> {
+        let a = self.a;
+        self.get_matching_blocks()
+            .iter()
+            .filter(|&&(_, _, k)| k > 0)
+            .map(|&(i, _, k)| &a[i..i + k])
+            .collect()
+    }
+
+    fn compute_matching_blocks(&self) -> Vec<(usize, usize, usize)> {
+        self.matching_blocks_in(0, self.a.len(), 0, self.b.len())
+    }
+
+    /// Opcodes covering only `a[alo..ahi]` and `b[blo..bhi]`, matched
+    /// against the whole-sequence index
+    pub fn get_opcodes_in(&self, alo: usize, ahi: usize, blo: usize, bhi: usize) -> Vec<OpCode> {
+        let blocks: Vec<_> = self
+            .matching_blocks_in(alo, ahi, blo, bhi)
+            .into_iter()
+            .map(|(i, j, k)| (i - alo, j - blo, k))
+            .collect();
+        iter_opcodes(&blocks)
+            .map(|op| OpCode {
+                tag: op.tag,
+                i1: op.i1 + alo,
+                i2: op.i2 + alo,
+                j1: op.j1 + blo,
+                j2: op.j2 + blo,
+            })
+            .collect()
+    }
+
+    /// Matching blocks of `a[alo..ahi]` and `b[blo..bhi]`, ending in the
+    /// sentinel `(ahi, bhi, 0)`
+    fn matching_blocks_in(&self, alo: usize, ahi: usize, blo: usize, bhi: usize) -> Vec<(usize, usize, usize)> {
+        self.matching_blocks_within(alo, ahi, blo, bhi, None, &mut MatchBuffers::default())
+            .expect("a search without cutoff completes unless cancelled")
+    }
+
+    /// `matching_blocks_in`, or `None` as soon as the ratio of the ranges
+    /// can't reach `cutoff` or the search is cancelled
+    fn matching_blocks_within(
+        &self,
+        alo: usize,
+        ahi: usize,
+        blo: usize,
+        bhi: usize,
+        cutoff: Option<f64>,
+        buffers: &mut MatchBuffers,
+    ) -> Option<Vec<(usize, usize, usize)>> {
+        let blocks = self.search_matching_blocks(alo, ahi, blo, bhi, cutoff, buffers)?;
+        if let Some(progress) = &self.progress {
+            (progress.callback)(1.0);
+        }
+        Some(blocks)
+    }
+
+    fn search_matching_blocks(
+        &self,
+        alo: usize,
+        ahi: usize,
+        blo: usize,
+        bhi: usize,
+        cutoff: Option<f64>,
+        buffers: &mut MatchBuffers,
+    ) -> Option<Vec<(usize, usize, usize)>> {
+        // Use queue-based approach like Python for better performance
+        
+        // Fast path for identical sequences
+        if self.a[alo..ahi] == self.b[blo..bhi] {
+            let whole = (ahi > alo).then_some((alo, blo, ahi - alo));
+            return Some(whole.into_iter().chain([(ahi, bhi, 0)]).collect());
+        }
+        
+        // Use queue instead of stack like Python's implementation
+        let MatchBuffers { scratch, queue, matches } = buffers;
+        matches.clear();
+        queue.clear();
+        // Search only between the common prefix and suffix when stripping
+        // them, with blocks and progress still over the whole ranges
+        let (total_alo, total_ahi, total_blo, total_bhi) = (alo, ahi, blo, bhi);
+        let (alo, ahi, blo, bhi) = if self.strip_common_affixes {
+            let prefix = self.a[alo..ahi].iter().zip(&self.b[blo..bhi]).take_while(|(x, y)| x == y).count();
+            let (alo, blo) = (alo + prefix, blo + prefix);
+            let suffix = self.a[alo..ahi]
+                .iter()
+                .rev()
+                .zip(self.b[blo..bhi].iter().rev())
+                .take_while(|(x, y)| x == y)
+                .count();
+            if prefix > 0 {
+                matches.push((alo - prefix, blo - prefix, prefix));
+            }
+            if suffix > 0 {
+                matches.push((ahi - suffix, bhi - suffix, suffix));
+            }
+            (alo, ahi - suffix, blo, bhi - suffix)
+        } else {
+            (alo, ahi, blo, bhi)
+        };
+        if self.unique_anchors {
+            let (mut i0, mut j0) = (alo, blo);
+            for (i, j) in self.unique_anchors_in(alo, ahi, blo, bhi) {
+                if i0 < i && j0 < j {
+                    queue.push_back((i0, i, j0, j));
+                }
+                matches.push((i, j, 1));
+                (i0, j0) = (i + 1, j + 1);
+            }
+            if i0 < ahi && j0 < bhi {
+                queue.push_back((i0, ahi, j0, bhi));
+            }
+        } else {
+            queue.push_back((alo, ahi, blo, bhi));
+        }
+
+        // Lines no longer in any queued region, for progress reports
+        let total = (total_ahi - total_alo) + (total_bhi - total_blo);
+        let queued: usize = queue.iter().map(|&(alo, ahi, blo, bhi)| (ahi - alo) + (bhi - blo)).sum();
+        let mut resolved = total - queued;
+        let mut last_report = Instant::now();
+
+        // Most matches still possible: those found, plus the shorter side
+        // of every queued region
+        let shorter_side = |(alo, ahi, blo, bhi): (usize, usize, usize, usize)| (ahi - alo).min(bhi - blo);
+        let mut max_matches = matches.iter().map(|&(_, _, k)| k).sum::<usize>() + queue.iter().copied().map(shorter_side).sum::<usize>();
+        let out_of_reach = |max_matches: usize| cutoff.is_some_and(|cutoff| calculate_ratio(max_matches, total) < cutoff);
+        if out_of_reach(max_matches) {
+            return None;
+        }
+
+        // One pair of maps for every search, cleared by each
+        while let Some((alo, ahi, blo, bhi)) = queue.pop_front() {
+            if self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+                return None;
+            }
+            let (i, j, k) = self.find_longest_match_with(alo, ahi, blo, bhi, scratch);
+            let mut requeued = 0;
+            max_matches -= shorter_side((alo, ahi, blo, bhi));
+            
+            // If we found a match, add it and queue the surrounding regions
+            if k > 0 {
+                matches.push((i, j, k));
+                max_matches += k;
+                if alo < i && blo < j {
+                    queue.push_back((alo, i, blo, j));
+                    requeued += (i - alo) + (j - blo);
+                    max_matches += shorter_side((alo, i, blo, j));
+                }
+                if i + k < ahi && j + k < bhi {
+                    queue.push_back((i + k, ahi, j + k, bhi));
+                    requeued += (ahi - i - k) + (bhi - j - k);
+                    max_matches += shorter_side((i + k, ahi, j + k, bhi));
+                }
+            }
+            if out_of_reach(max_matches) {
+                return None;
+            }
+
+            if let Some(progress) = &self.progress {
+                resolved += (ahi - alo) + (bhi - blo) - requeued;
+                if last_report.elapsed() >= progress.interval {
+                    (progress.callback)(resolved as f64 / total as f64);
+                    last_report = Instant::now();
+                }
+            }
+        }
+
+        // Sort by positions (i, j)
+        matches.sort_unstable_by_key(|&(i, j, _)| (i, j));
+
+        // Collapse adjacent matches
+        let mut collapsed: Vec<(usize, usize, usize)> = Vec::new();
+        for &(i, j, k) in matches.iter() {
+            if let Some(last) = collapsed.last_mut() {
+                if last.0 + last.2 == i && last.1 + last.2 == j {
+                    last.2 += k;
+                    continue;
+                }
+            }
+            collapsed.push((i, j, k));
+        }
+
+        // Add sentinel
+        collapsed.push((total_ahi, total_bhi, 0));
+        Some(collapsed)
+    }
+
+    /// `(i, j)` pairs of non-junk lines occurring exactly once in
+    /// `a[alo..ahi]` and once in `b[blo..bhi]`, cut down to the longest
+    /// subsequence increasing in both `i` and `j`
+    fn unique_anchors_in(&self, alo: usize, ahi: usize, blo: usize, bhi: usize) -> Vec<(usize, usize)> {
+        // Occurrences in `a`, position in `a`, occurrences in `b`, position in `b`
+        let mut seen: FxHashMap<&str, (usize, usize, usize, usize)> = FxHashMap::default();
+        for (i, line) in self.a[alo..ahi].iter().enumerate() {
+            let entry = seen.entry(line.as_str()).or_default();
+            (entry.0, entry.1) = (entry.0 + 1, alo + i);
+        }
+        for (j, line) in self.b[blo..bhi].iter().enumerate() {
+            if let Some(entry) = seen.get_mut(line.as_str()) {
+                (entry.2, entry.3) = (entry.2 + 1, blo + j);
+            }
+        }
+        let pairs: Vec<(usize, usize)> = self.a[alo..ahi]
+            .iter()
+            .filter_map(|line| match seen[line.as_str()] {
+                (1, i, 1, j) if !self.index.junk.contains(line.as_str()) => Some((i, j)),
+                _ => None,
+            })
+            .collect();
+
+        // Patience sorting: `tails[k]` ends the best increasing run of
+        // length `k + 1` found so far, and `previous` links each pair to the
+        // one before it in its run
+        let mut tails: Vec<usize> = Vec::new();
+        let mut previous: Vec<Option<usize>> = Vec::with_capacity(pairs.len());
+        for (index, &(_, j)) in pairs.iter().enumerate() {
+            let k = tails.partition_point(|&tail| pairs[tail].1 < j);
+            previous.push(k.checked_sub(1).map(|k| tails[k]));
+            if k == tails.len() {
+                tails.push(index);
+            } else {
+                tails[k] = index;
+            }
+        }
+        let mut anchors = Vec::with_capacity(tails.len());
+        let mut next = tails.last().copied();
+        while let Some(index) = next {
+            anchors.push(pairs[index]);
+            next = previous[index];
+        }
+        anchors.reverse();
+        anchors
+    }
+
+    /// Character similarity of the lines just before the match `(i, j, k)`
+    /// plus that of the lines just after it, where those are inside the
+    /// region `(alo, ahi, blo, bhi)`
+    fn neighbor_similarity(
+        &self,
+        (i, j, k): (usize, usize, usize),
+        (alo, ahi, blo, bhi): (usize, usize, usize, usize),
+    ) -> f64 {
+        let similarity = |x: &str, y: &str| {
+            let (x, y) = (graphemes(x), graphemes(y));
+            SequenceMatcher::new(&x, &y).ratio()
+        };
+        let before = if i > alo && j > blo { similarity(&self.a[i - 1], &self.b[j - 1]) } else { 0.0 };
+        let after = if i + k < ahi && j + k < bhi { similarity(&self.a[i + k], &self.b[j + k]) } else { 0.0 };
+        before + after
+    }
+
+    /// Longest matching block of `a[alo..ahi]` and `b[blo..bhi]`, like
+    /// CPython's `find_longest_match`, or the best scoring one under
+    /// `AnchorScoring::GapPenalty`. Ties go to the earliest match, or with
+    /// `neighbor_tiebreak` to the one with the most similar neighbors.
+    ///
+    /// The result is deterministic: it only depends on the sequences and
+    /// the configuration, never on hashing or allocation. Candidates are
+    /// visited in order of `i`, then of `j` as listed in `b2j`, whose
+    /// position lists are filled in order; the `j2len` maps are only looked
+    /// up, never iterated; and a candidate only replaces the best when it
+    /// scores strictly higher. So neither hash function, map capacity nor
+    /// reuse of the maps across searches (`get_matching_blocks_with`) can
+    /// change which match wins.
+    pub fn find_longest_match(&self, alo: usize, ahi: usize, blo: usize, bhi: usize) -> (usize, usize, usize) {
+        self.find_longest_match_with(alo, ahi, blo, bhi, &mut Scratch::default())
+    }
+
+    /// `find_longest_match` using the maps in `scratch`, so repeated calls
+    /// don't allocate new ones
+    #[inline]
+    fn find_longest_match_with(
+        &self,
+        alo: usize,
+        ahi: usize,
+        blo: usize,
+        bhi: usize,
+        scratch: &mut Scratch,
+    ) -> (usize, usize, usize) {
+        let mut besti = alo;
+        let mut bestj = blo;
+        let mut bestsize = 0;
+        
+        // Use FxHashMap for sparse representation like Python - maintains exact algorithm
+        let Scratch { j2len, newj2len } = scratch;
+        j2len.clear();
+        let max_occurrences = self.max_occurrences.unwrap_or(usize::MAX);
+        let penalty = match self.anchor_scoring {
+            AnchorScoring::Longest => None,
+            AnchorScoring::GapPenalty(penalty) => Some(penalty),
+        };
+        // Diagonals (j - i, relative to the region) that leave as many
+        // unmatched lines on both sides as the region's shape requires
+        let end_diagonal = (bhi - blo) as isize - (ahi - alo) as isize;
+        let (low_diagonal, high_diagonal) = (end_diagonal.min(0), end_diagonal.max(0));
+        let mut bestscore = f64::NEG_INFINITY;
+        // Later matches scoring the same as the best, for `neighbor_tiebreak`
+        let mut ties: Vec<(usize, usize, usize)> = Vec::new();
+        
+        for i in alo..ahi {
+            // Clear instead of allocating new HashMap - much faster!
+            newj2len.clear();
+            
+            // Get all positions where a[i] appears in b (like Python's b2j.get())
+            if let Some(indices) = self.index.b2j.get(self.a[i].as_str()) {
+                let mut scanned = 0;
+                for &j in indices {
+                    // Bounds check - exactly like Python
+                    if j < blo {
+                        continue;
+                    }
+                    if j >= bhi || scanned == max_occurrences {
+                        break;
+                    }
+                    scanned += 1;
+                    
+                    // k = length of longest match ending at (i-1, j-1)
+                    // Use sparse lookup - only non-zero values are stored
+                    let k = if j > 0 { 
+                        j2len.get(&(j - 1)).copied().unwrap_or(0) 
+                    } else { 
+                        0 
+                    };
+                    
+                    // Extend match by 1
+                    let newk = k + 1;
+                    newj2len.insert(j, newk);
+                    
+                    // Track best match found so far
+                    let (better, tied) = match penalty {
+                        None => (newk > bestsize, newk == bestsize),
+                        Some(penalty) => {
+                            let diagonal = (j - blo) as isize - (i - alo) as isize;
+                            let off = (low_diagonal - diagonal).max(diagonal - high_diagonal).max(0);
+                            let score = newk as f64 - penalty * off as f64;
+                            let better = score > bestscore;
+                            bestscore = bestscore.max(score);
+                            (better, !better && score == bestscore)
+                        }
+                    };
+                    if better {
+                        besti = i + 1 - newk;
+                        bestj = j + 1 - newk;
+                        bestsize = newk;
+                        ties.clear();
+                    } else if tied && self.neighbor_tiebreak {
+                        ties.push((i + 1 - newk, j + 1 - newk, newk));
+                    }
+                }
+            }
+            
+            // Swap HashMaps efficiently - no allocations
+            std::mem::swap(j2len, newj2len);
+        }
+        
+        if !ties.is_empty() {
+            let region = (alo, ahi, blo, bhi);
+            let mut best = ((besti, bestj, bestsize), self.neighbor_similarity((besti, bestj, bestsize), region));
+            for tie in ties {
+                let similarity = self.neighbor_similarity(tie, region);
+                if similarity > best.1 {
+                    best = (tie, similarity);
+                }
+            }
+            (besti, bestj, bestsize) = best.0;
+        }
+
+        // Extend the best match as far as possible in both directions
+        // This handles the case where the match can be extended beyond
+        // the initial finding (important for correctness)
+        
+        #[cfg(feature = "simd")]
+        if self.index.junk.is_empty() {
+            let (a_ids, b_ids) = self.ids.get_or_init(|| super::simd::intern(self.a, self.b));
+            let back = super::simd::common_suffix(&a_ids[alo..besti], &b_ids[blo..bestj]);
+            (besti, bestj, bestsize) = (besti - back, bestj - back, bestsize + back);
+            bestsize += super::simd::common_prefix(&a_ids[besti + bestsize..ahi], &b_ids[bestj + bestsize..bhi]);
+            return (besti, bestj, bestsize);
+        }
+
+        if self.index.junk.is_empty() {
+            // Extend backwards
+            while besti > alo && bestj > blo && self.a[besti - 1] == self.b[bestj - 1] {
+                besti -= 1;
+                bestj -= 1;
+                bestsize += 1;
+            }
+            
+            // Extend forwards
+            while besti + bestsize < ahi && bestj + bestsize < bhi && self.a[besti + bestsize] == self.b[bestj + bestsize] {
+                bestsize += 1;
+            }
+            
+            return (besti, bestj, bestsize);
+        }
+
+        // With junk, CPython first extends over non-junk elements only and
+        // then over junk elements only
+        for extend_with_junk in [false, true] {
+            while besti > alo
+                && bestj > blo
+                && self.index.junk.contains(self.b[bestj - 1].as_str()) == extend_with_junk
+                && self.a[besti - 1] == self.b[bestj - 1]
+            {
+                besti -= 1;
+                bestj -= 1;
+                bestsize += 1;
+            }
+            while besti + bestsize < ahi
+                && bestj + bestsize < bhi
+                && self.index.junk.contains(self.b[bestj + bestsize].as_str()) == extend_with_junk
+                && self.a[besti + bestsize] == self.b[bestj + bestsize]
+            {
+                bestsize += 1;
+            }
+        }
+        
+        (besti, bestj, bestsize)
+    }
+
+}
+
+/// Allocations of a matching block search: the maps of
+/// `find_longest_match`, the queue of regions and the matches found. Kept
+/// between searches by `SequenceMatcher::get_matching_blocks_with`, so
+/// diffing a batch of pairs allocates them once instead of per pair.
+#[derive(Default)]
+pub struct MatchBuffers {
+    scratch: Scratch,
+    queue: VecDeque<(usize, usize, usize, usize)>,
+    matches: Vec<(usize, usize, usize)>,
+}
+
+/// The `j2len` and `newj2len` maps of `find_longest_match`: lengths of the
+/// matches ending at each position of `b`, for the previous and current
+/// line of `a`. Only ever looked up by position, never iterated, so their
+/// capacity and hash order can't affect a result.
+#[derive(Default)]
+struct Scratch {
+    j2len: FxHashMap<usize, usize>,
+    newj2len: FxHashMap<usize, usize>,
+}
+
+/// Check that `range` is a valid range of a sequence of `len` elements
+pub(crate) fn check_range(range: &Range<usize>, len: usize) -> Result<(), Error> {
+    if range.start > range.end {
+        return Err(Error::InvalidArgument("range start is after its end"));
+    }
+    if range.end > len {
+        return Err(Error::InvalidArgument("range is out of bounds"));
+    }
+    Ok(())
+}
+
+/// `2 * matches / length`, `1.0` for two empty sequences like CPython
+pub(crate) fn calculate_ratio(matches: usize, length: usize) -> f64 {
+    if length > 0 {
+        2.0 * matches as f64 / length as f64
+    } else {
+        1.0
+    }
+}
+
+/// Opcodes for `a` -> the indexed `b`, reusing the index's `b2j`
+pub fn diff_against_index<'a>(a: &'a [String], index: &'a Index<'a>) -> Vec<OpCode> {
+    let matcher = SequenceMatcher::from_index(a, index);
+    opcodes_from_blocks(matcher.get_matching_blocks())
+}
+
+/// Turn matching blocks (ending in a sentinel) into opcodes, like Python's
+/// `SequenceMatcher.get_opcodes`
+pub fn opcodes_from_blocks(matches: &[(usize, usize, usize)]) -> Vec<OpCode> {
+    let mut opcodes = Vec::with_capacity(matches.len() * 2);
+    opcodes.extend(iter_opcodes(matches));
+    opcodes
+}
+
+/// Matching blocks of `opcodes`, the inverse of `opcodes_from_blocks`: one
+/// block per run of touching `equal` opcodes, then the `(len(a), len(b),
+/// 0)` sentinel, the sequence lengths being where the last opcode ends
+pub fn opcodes_to_matching_blocks(opcodes: &[OpCode]) -> Vec<(usize, usize, usize)> {
+    let mut blocks: Vec<(usize, usize, usize)> = Vec::with_capacity(opcodes.len() / 2 + 1);
+    for op in opcodes.iter().filter(|op| op.tag == OpTag::Equal && op.i2 > op.i1) {
+        match blocks.last_mut() {
+            Some(last) if last.0 + last.2 == op.i1 && last.1 + last.2 == op.j1 => last.2 += op.i2 - op.i1,
+            _ => blocks.push((op.i1, op.j1, op.i2 - op.i1)),
+        }
+    }
+    let (la, lb) = opcodes.last().map_or((0, 0), |op| (op.i2, op.j2));
+    blocks.push((la, lb, 0));
+    blocks
+}
+
+/// Lazily yield the opcodes of `matches`, see `opcodes_from_blocks`
+pub fn iter_opcodes(matches: &[(usize, usize, usize)]) -> impl Iterator<Item = OpCode> + '_ {
+    let mut i = 0usize;
+    let mut j = 0usize;
+
+    matches.iter().flat_map(move |&(ai, bj, size)| {
+        let change = if i < ai && j < bj {
+            Some(OpCode {
+                tag: OpTag::Replace,
+                i1: i,
+                i2: ai,
+                j1: j,
+                j2: bj,
+            })
+        } else if i < ai {
+            Some(OpCode {
+                tag: OpTag::Delete,
+                i1: i,
+                i2: ai,
+                j1: j,
+                j2: j,
+            })
+        } else if j < bj {
+            Some(OpCode {
+                tag: OpTag::Insert,
+                i1: i,
+                i2: i,
+                j1: j,
+                j2: bj,
+            })
+        } else {
+            None
+        };
+
+        let equal = (size > 0).then_some(OpCode {
+            tag: OpTag::Equal,
+            i1: ai,
+            i2: ai + size,
+            j1: bj,
+            j2: bj + size,
+        });
+
+        i = ai + size;
+        j = bj + size;
+        change.into_iter().chain(equal)
+    })
+}
+
+/// Append `op` to `ops`, merging it into the previous opcode when both are
+/// equal or both are changes and they touch
+pub fn push_opcode(ops: &mut Vec<OpCode>, op: OpCode) {
+    if let Some(last) = ops.last_mut() {
+        let touching = last.i2 == op.i1 && last.j2 == op.j1;
+        let both_equal = last.tag == OpTag::Equal && op.tag == OpTag::Equal;
+        let both_changes = last.tag != OpTag::Equal && op.tag != OpTag::Equal;
+        if touching && (both_equal || both_changes) {
+            last.i2 = op.i2;
+            last.j2 = op.j2;
+            if both_changes {
+                last.tag = match (last.i1 < last.i2, last.j1 < last.j2) {
+                    (true, true) => OpTag::Replace,
+                    (true, false) => OpTag::Delete,
+                    _ => OpTag::Insert,
+                };
+            }
+            return;
+        }
+    }
+    ops.push(op);
+}
+
+/// Opcodes for `a` -> `b` computed over overlapping windows of at most
+/// `window_size` lines per side, so the `b2j` index only ever covers one
+/// window instead of all of `b`.
+///
+/// Each window is diffed exactly, but only the part up to an anchor in its
+/// first three quarters is kept; the next window starts at that anchor. The
+/// anchor is preferably a matched line that is unique in both windows. This is
+/// an approximation: changes that straddle a window boundary can come out
+/// less minimal than a whole-file diff would make them. Each window's
+/// matcher is built from `config`.
+pub fn get_opcodes_windowed<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    window_size: usize,
+    config: &SequenceMatcherBuilder<'a>,
+) -> Vec<OpCode> {
+    let window_size = window_size.max(1);
+    let mut opcodes: Vec<OpCode> = Vec::new();
+    let mut pa = 0usize;
+    let mut pb = 0usize;
+
+    loop {
+        let ea = (pa + window_size).min(a.len());
+        let eb = (pb + window_size).min(b.len());
+        let wa = &a[pa..ea];
+        let wb = &b[pb..eb];
+        let last_window = ea == a.len() && eb == b.len();
+
+        let matcher = config.build(wa, wb);
+        let mut blocks = matcher.get_matching_blocks().to_vec();
+        blocks.pop();
+
+        let (ca, cb) = if last_window {
+            (wa.len(), wb.len())
+        } else {
+            // Keep the tail of the window as overlap for the next one
+            let keep = (window_size * 3 / 4).max(1);
+            let limit_a = if ea == a.len() { wa.len() } else { keep };
+            let limit_b = if eb == b.len() { wb.len() } else { keep };
+
+            let mut a_counts: FxHashMap<&str, usize> = FxHashMap::default();
+            for line in wa {
+                *a_counts.entry(line.as_str()).or_default() += 1;
+            }
+            let unique_end = |&(i, j, k): &(usize, usize, usize)| {
+                let line = wa[i + k - 1].as_str();
+                a_counts.get(line) == Some(&1)
+                    && matcher.index.b2j.get(line).is_some_and(|js| js.len() == 1)
+                    && wb[j + k - 1] == wa[i + k - 1]
+            };
+            let in_zone = |&&(i, j, k): &&(usize, usize, usize)| i + k <= limit_a && j + k <= limit_b;
+
+            let anchor = blocks
+                .iter()
+                .rev()
+                .filter(in_zone)
+                .find(|block| unique_end(block))
+                .or_else(|| blocks.iter().rev().find(in_zone))
+                .or_else(|| blocks.first());
+            match anchor {
+                Some(&(i, j, k)) => {
+                    blocks.retain(|&(bi, _, _)| bi <= i);
+                    (i + k, j + k)
+                }
+                None => (limit_a.min(wa.len()), limit_b.min(wb.len())),
+            }
+        };
+
+        blocks.push((ca, cb, 0));
+        for op in opcodes_from_blocks(&blocks) {
+            push_opcode(
+                &mut opcodes,
+                OpCode {
+                    tag: op.tag,
+                    i1: op.i1 + pa,
+                    i2: op.i2 + pa,
+                    j1: op.j1 + pb,
+                    j2: op.j2 + pb,
+                },
+            );
+        }
+
+        pa += ca;
+        pb += cb;
+        if last_window {
+            break;
+        }
+    }
+
+    opcodes
+}
+
@@ -0,0 +1,106 @@
+//! Three-way merges, and the base preamble that lets a patch fall back to
+//! one when it no longer applies, like `git apply --3way`.
+//!
+//! The preamble goes before the file headers, where patch tools skip
+//! anything that isn't a diff: a `base-lines: N` line followed by the `N`
+//! lines of the old file, each prefixed with `=`.
+
+use super::matcher::SequenceMatcher;
+use super::text::ends_with_newline;
+use std::ops::Range;
+
+/// Starts the line giving the number of embedded base lines
+const BASE_HEADER: &str = "base-lines: ";
+
+/// Marker lines around a conflict, git style
+const OURS_MARKER: &str = "<<<<<<< ours";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>> theirs";
+
+/// The preamble embedding `base` in a diff, its header ending with
+/// `lineterm`
+pub fn base_preamble(base: &[String], lineterm: &str) -> Vec<String> {
+    let mut preamble = Vec::with_capacity(base.len() + 1);
+    preamble.push(format!("{}{}{}", BASE_HEADER, base.len(), lineterm));
+    preamble.extend(base.iter().map(|line| format!("={}", line)));
+    preamble
+}
+
+/// Number of base lines announced by `line`, if it is a preamble header
+pub fn parse_base_header(line: &str) -> Option<usize> {
+    line.strip_prefix(BASE_HEADER)?.trim_end().parse().ok()
+}
+
+/// The base embedded in `patch` by `base_preamble`, if it has a complete
+/// one
+pub fn parse_base(patch: &[String]) -> Option<Vec<String>> {
+    let start = patch.iter().position(|line| parse_base_header(line).is_some())?;
+    let count = parse_base_header(&patch[start])?;
+    let lines = patch.get(start + 1..start + 1 + count)?;
+    lines.iter().map(|line| line.strip_prefix('=').map(String::from)).collect()
+}
+
+/// Aligned regions unchanged on both sides: `(base_start, base_end,
+/// ours_start, theirs_start)`, ending with an empty region at the end of
+/// all three
+fn sync_regions(base: &[String], ours: &[String], theirs: &[String]) -> Vec<(usize, usize, usize, usize)> {
+    let ours_blocks = SequenceMatcher::new(base, ours).get_matching_blocks().to_vec();
+    let theirs_blocks = SequenceMatcher::new(base, theirs).get_matching_blocks().to_vec();
+    let mut regions = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < ours_blocks.len() && j < theirs_blocks.len() {
+        let (ours_base, ours_start, ours_len) = ours_blocks[i];
+        let (theirs_base, theirs_start, theirs_len) = theirs_blocks[j];
+        let start = ours_base.max(theirs_base);
+        let end = (ours_base + ours_len).min(theirs_base + theirs_len);
+        if start < end {
+            regions.push((start, end, ours_start + start - ours_base, theirs_start + start - theirs_base));
+        }
+        if ours_base + ours_len < theirs_base + theirs_len {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    regions.push((base.len(), base.len(), ours.len(), theirs.len()));
+    regions
+}
+
+/// Merge the changes from `base` to `ours` and from `base` to `theirs`,
+/// returning the merged lines and the range of `base` each conflict
+/// replaces, empty when both sides inserted different lines at the same
+/// place.
+///
+/// A region changed on only one side, or the same way on both, takes that
+/// change. One changed differently on both sides becomes a conflict: our
+/// lines between `<<<<<<< ours` and `=======`, then theirs up to
+/// `>>>>>>> theirs`. The marker lines end in `\n` unless no line of `ours`
+/// has a line ending.
+pub fn merge3(base: &[String], ours: &[String], theirs: &[String]) -> (Vec<String>, Vec<Range<usize>>) {
+    let eol = if ours.iter().any(|line| ends_with_newline(line)) { "\n" } else { "" };
+    let mut result = Vec::with_capacity(ours.len().max(theirs.len()));
+    let mut conflicts = Vec::new();
+    let (mut base_pos, mut ours_pos, mut theirs_pos) = (0, 0, 0);
+
+    for (base_start, base_end, ours_start, theirs_start) in sync_regions(base, ours, theirs) {
+        let base_part = &base[base_pos..base_start];
+        let ours_part = &ours[ours_pos..ours_start];
+        let theirs_part = &theirs[theirs_pos..theirs_start];
+        if ours_part == theirs_part || theirs_part == base_part {
+            result.extend_from_slice(ours_part);
+        } else if ours_part == base_part {
+            result.extend_from_slice(theirs_part);
+        } else {
+            conflicts.push(base_pos..base_start);
+            result.push(format!("{}{}", OURS_MARKER, eol));
+            result.extend_from_slice(ours_part);
+            result.push(format!("{}{}", SEPARATOR_MARKER, eol));
+            result.extend_from_slice(theirs_part);
+            result.push(format!("{}{}", THEIRS_MARKER, eol));
+        }
+        result.extend_from_slice(&base[base_start..base_end]);
+        let len = base_end - base_start;
+        (base_pos, ours_pos, theirs_pos) = (base_end, ours_start + len, theirs_start + len);
+    }
+    (result, conflicts)
+}
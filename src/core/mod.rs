@@ -0,0 +1,114 @@
+//! The diff engine, free of any Python dependency.
+//!
+//! Everything here works on slices of lines and mirrors the behavior of
+//! Python's `difflib`; the `python` feature only adds thin wrappers on top.
+
+mod annotate;
+mod ansi;
+mod bytes;
+mod checksum;
+mod codec;
+mod comments;
+mod context;
+mod corpus;
+mod grouping;
+mod incremental;
+mod inline;
+mod json_patch;
+mod matcher;
+mod merge;
+mod normalize;
+mod numeric;
+mod patch;
+mod predicate;
+mod records;
+mod sections;
+#[cfg(feature = "simd")]
+mod simd;
+mod text;
+mod tsv;
+mod unified;
+mod words;
+
+use std::fmt;
+
+pub use annotate::{annotate_opcodes, annotated_hunks, render_keys, AnnotatedOpCode};
+pub use ansi::{ansi_line, LineStyle, ANSI_RESET};
+pub use bytes::{looks_binary, unified_diff_bytes, ByteDiffLabels, DEFAULT_BINARY_THRESHOLD};
+pub use checksum::{diff_fingerprint, lines_checksum, parse_checksum};
+pub use codec::{deserialize_opcodes, opcode_arrays, serialize_opcodes, OpcodeArrays};
+pub use comments::{drop_comment_groups, CommentSyntax};
+pub use context::{context_diff, format_range_context, ContextMarkers};
+pub use corpus::{best_base, get_close_matches_set, set_ratio, CorpusIndex};
+pub use grouping::{
+    apply_scope_hints, diff_stats, group_opcodes, group_opcodes_adaptive, group_opcodes_within, merge_small_groups,
+    rendered_line_count, validate_grouped_opcodes, validate_grouped_opcodes_by, AdaptiveContext, DiffStats,
+    ScopeHints,
+};
+pub use incremental::IncrementalMatcher;
+pub use inline::{
+    char_opcodes_utf16, inline_char_diff, inline_char_diff_with, prefix_weighted_ratio, utf16_len, InlineMarkers,
+    DEFAULT_PREFIX_DECAY,
+};
+pub use json_patch::json_patch;
+pub use matcher::{
+    diff_against_index, estimate_cost, get_opcodes_windowed, invert_opcodes, iter_opcodes, opcodes_from_blocks,
+    opcodes_to_matching_blocks, push_opcode, split_equal_runs, AnchorScoring, Index, MatchBuffers, OpCode, OpTag,
+    Progress, SequenceMatcher, SequenceMatcherBuilder,
+};
+pub use merge::{base_preamble, merge3, parse_base};
+pub use normalize::{Normalizer, Transform};
+pub use numeric::{get_opcodes_numeric, numeric_skeleton, NumericTolerance};
+pub use patch::{
+    apply_unified_diff, apply_unified_diff_3way, line_edits, merge_patches, verify_diff, HunkPlacement, LineEdit,
+};
+pub use predicate::{get_opcodes_with, PREDICATE_WARN_PAIRS};
+pub use records::{diff_records, keyed_diff, map_diff, parse_records, KeyedDiff, MapChange, RecordDiff};
+pub use sections::sectioned_diff;
+pub use text::{ends_with_newline, line_ending_report, split_lines, track_final_newline, LineEnding};
+pub use tsv::tsv_diff;
+pub use unified::{
+    add_hunk_checksums, add_line_numbers, added_lines, cap_output_bytes, change_mask_new, change_mask_old,
+    changed_bounds, diff_line_count, diff_str, diffstat_bar, diffstat_line, differs_by_at_most, first_difference,
+    format_range_unified, format_unified_diff, hunk_density, hunk_headers, invert_unified_diff, materialize_lines,
+    parse_range_unified, removed_lines, truncate_lines, unchanged_ranges_new, unchanged_ranges_old, unified_diff,
+    unified_diff_dual, unified_diff_groups, unified_diff_lines, unified_diff_range, unified_diff_range_groups,
+    unified_diff_with_stats, DiffLabels, DiffLine, HunkHeader, UnifiedDiffOptions, ELISION_KEEP, TRUNCATION_MARKER,
+};
+pub use words::{word_unified_diff, word_unified_diff_with};
+
+/// Errors reported by the diff engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A `@@ ... @@` line that couldn't be parsed
+    MalformedHunkHeader(String),
+    /// An option outside its valid range
+    InvalidArgument(&'static str),
+    /// Bytes that `deserialize_opcodes` couldn't decode
+    MalformedOpcodes(&'static str),
+    /// Grouped opcodes that fail `validate_grouped_opcodes`
+    InvalidGroups(String),
+    /// A hunk `apply_unified_diff` couldn't place, numbered from 1
+    HunkFailed(usize),
+    /// A hunk whose old lines don't match the checksum in its header,
+    /// numbered from 1
+    HunkChecksumMismatch(usize),
+    /// A search given up through `SequenceMatcherBuilder::cancel`
+    Cancelled,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MalformedHunkHeader(line) => write!(f, "malformed hunk header: {:?}", line),
+            Error::InvalidArgument(message) => f.write_str(message),
+            Error::MalformedOpcodes(reason) => write!(f, "malformed opcode bytes: {}", reason),
+            Error::InvalidGroups(reason) => write!(f, "invalid grouped opcodes: {}", reason),
+            Error::HunkFailed(number) => write!(f, "hunk #{} does not apply", number),
+            Error::HunkChecksumMismatch(number) => write!(f, "hunk #{} does not match its checksum", number),
+            Error::Cancelled => f.write_str("diff cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,148 @@
+//! Normalizing lines to comparison keys, so that differences that don't
+//! matter (case, indentation, line endings) don't show up in a diff.
+//!
+//! A `Normalizer` applies its transforms in order to derive the key of
+//! each line. Lines are matched on their keys, and the diff still shows
+//! the original lines. All transforms but `Dedent` look at one line at a
+//! time; `Dedent` looks at the whole input for its common indentation.
+
+use super::text::split_line_end;
+use super::Error;
+
+/// One step of a `Normalizer`. All but `StripCr` leave the line ending
+/// alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// Lowercase the line, `str.lower()`
+    Lowercase,
+    /// Drop leading and trailing whitespace
+    Trim,
+    /// Replace each run of whitespace with a single space
+    CollapseWs,
+    /// Turn a `\r\n` line ending into `\n`, and drop a lone `\r` one
+    StripCr,
+    /// Remove the longest run of spaces and tabs leading every line that
+    /// isn't blank, and empty the blank ones, like `textwrap.dedent`. The
+    /// margin is taken over all the lines given to `Normalizer::keys`, so
+    /// each input is dedented on its own; a single line loses all its
+    /// leading whitespace.
+    Dedent,
+}
+
+impl Transform {
+    /// The transform called `name`: `lowercase`, `trim`, `collapse_ws`,
+    /// `strip_cr` or `dedent`
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        Ok(match name {
+            "lowercase" => Transform::Lowercase,
+            "trim" => Transform::Trim,
+            "collapse_ws" => Transform::CollapseWs,
+            "strip_cr" => Transform::StripCr,
+            "dedent" => Transform::Dedent,
+            _ => {
+                return Err(Error::InvalidArgument(
+                    "unknown transform, expected lowercase, trim, collapse_ws, strip_cr or dedent",
+                ))
+            }
+        })
+    }
+
+    /// `line` with this transform applied, as the only line of its input
+    /// for `Dedent`
+    pub fn apply(self, line: &str) -> String {
+        let (content, end) = split_line_end(line);
+        match self {
+            Transform::Lowercase => format!("{}{}", content.to_lowercase(), end),
+            Transform::Trim => format!("{}{}", content.trim(), end),
+            Transform::CollapseWs => {
+                let mut key = String::with_capacity(line.len());
+                let mut in_space = false;
+                for c in content.chars() {
+                    if !c.is_whitespace() {
+                        key.push(c);
+                    } else if !in_space {
+                        key.push(' ');
+                    }
+                    in_space = c.is_whitespace();
+                }
+                key + end
+            }
+            Transform::StripCr => {
+                let end = match end {
+                    "\r\n" => "\n",
+                    "\r" => "",
+                    end => end,
+                };
+                format!("{}{}", content, end)
+            }
+            Transform::Dedent => format!("{}{}", content.trim_start_matches([' ', '\t']), end),
+        }
+    }
+}
+
+/// `lines` without the spaces and tabs leading all that aren't blank, and
+/// with the blank ones emptied, see `Transform::Dedent`
+fn dedent(lines: &[String]) -> Vec<String> {
+    let mut margin: Option<&str> = None;
+    for line in lines {
+        let (content, _) = split_line_end(line);
+        let indent = &content[..content.len() - content.trim_start_matches([' ', '\t']).len()];
+        if indent.len() == content.len() {
+            continue;
+        }
+        margin = Some(match margin {
+            None => indent,
+            Some(margin) => &margin[..margin.bytes().zip(indent.bytes()).take_while(|(x, y)| x == y).count()],
+        });
+    }
+    let margin = margin.map_or(0, str::len);
+    lines
+        .iter()
+        .map(|line| {
+            let (content, end) = split_line_end(line);
+            match content.trim_start_matches([' ', '\t']) {
+                "" => end.to_string(),
+                _ => format!("{}{}", &content[margin..], end),
+            }
+        })
+        .collect()
+}
+
+/// Transforms applied in order to each line to get the key it is compared
+/// by
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Normalizer {
+    pub transforms: Vec<Transform>,
+}
+
+impl Normalizer {
+    pub fn new(transforms: Vec<Transform>) -> Self {
+        Self { transforms }
+    }
+
+    /// The normalizer applying the transforms called `names` in order, see
+    /// `Transform::from_name`
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Result<Self, Error> {
+        let transforms = names.iter().map(|name| Transform::from_name(name.as_ref()));
+        Ok(Self::new(transforms.collect::<Result<_, _>>()?))
+    }
+
+    /// The comparison key of `line`
+    pub fn key(&self, line: &str) -> String {
+        self.transforms
+            .iter()
+            .fold(line.to_string(), |key, transform| transform.apply(&key))
+    }
+
+    /// The comparison keys of `lines`, in order, with `Dedent` taking its
+    /// margin over all of them
+    pub fn keys(&self, lines: &[String]) -> Vec<String> {
+        if !self.transforms.contains(&Transform::Dedent) {
+            return lines.iter().map(|line| self.key(line)).collect();
+        }
+        self.transforms.iter().fold(lines.to_vec(), |keys, transform| match transform {
+            Transform::Dedent => dedent(&keys),
+            transform => keys.iter().map(|key| transform.apply(key)).collect(),
+        })
+    }
+}
@@ -0,0 +1,135 @@
+//! Line equality that tolerates small differences between numbers.
+//!
+//! A numeric token is an optional sign, digits with an optional fraction
+//! (`12`, `1.5`, `.5`, `3.`) and an optional exponent (`1e-3`). A sign only
+//! belongs to the token when it doesn't follow a letter, digit, `_` or `.`,
+//! so `3-1` holds `3` and `1`. Digits touching a letter, `_` or a second
+//! `.` are part of a word rather than a number, so `v2`, `x_1`, `5px` and
+//! `1.2.3` have no numeric tokens and must match exactly.
+//!
+//! A line's skeleton is the line with each numeric token replaced by a
+//! placeholder. Two lines are equal within a tolerance when their
+//! skeletons are identical and each pair of numbers is close.
+
+use super::matcher::{push_opcode, OpCode, OpTag, SequenceMatcher};
+use super::Error;
+
+/// Stands in for a numeric token in a skeleton
+const PLACEHOLDER: char = '\0';
+
+/// How far apart two numbers may be and still count as equal, like
+/// Python's `math.isclose`: within `abs` of each other, or within `rel`
+/// times the larger magnitude
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NumericTolerance {
+    pub abs: f64,
+    pub rel: f64,
+}
+
+impl NumericTolerance {
+    /// Check that both tolerances are finite and not negative
+    pub fn validate(&self) -> Result<(), Error> {
+        if !(self.abs >= 0.0 && self.rel >= 0.0 && self.abs.is_finite() && self.rel.is_finite()) {
+            return Err(Error::InvalidArgument("numeric tolerances must be finite and not negative"));
+        }
+        Ok(())
+    }
+
+    /// Whether `x` and `y` are within the tolerance of each other
+    pub fn close(&self, x: f64, y: f64) -> bool {
+        x == y || (x - y).abs() <= self.abs.max(self.rel * x.abs().max(y.abs()))
+    }
+
+    /// Whether the lines have the same skeleton and close numbers
+    pub fn lines_equal(&self, a: &str, b: &str) -> bool {
+        let ((a_skeleton, a_numbers), (b_skeleton, b_numbers)) = (numeric_skeleton(a), numeric_skeleton(b));
+        a_skeleton == b_skeleton && a_numbers.iter().zip(&b_numbers).all(|(&x, &y)| self.close(x, y))
+    }
+}
+
+/// Whether `c` makes adjacent digits part of a word
+fn is_word(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'.'
+}
+
+/// End of the numeric token starting at `start`, if there is one
+fn numeric_token_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let digits = |mut i: usize| {
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        i
+    };
+    let mut i = start;
+    if matches!(bytes[i], b'+' | b'-') {
+        i += 1;
+    }
+    let integer_end = digits(i);
+    let mut end = integer_end;
+    if bytes.get(end) == Some(&b'.') {
+        end = digits(end + 1);
+    }
+    if end == i || (integer_end == i && end == i + 1) {
+        return None;
+    }
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+        let exponent_end = digits(end + 1 + sign);
+        if exponent_end > end + 1 + sign {
+            end = exponent_end;
+        }
+    }
+    (!bytes.get(end).copied().is_some_and(is_word)).then_some(end)
+}
+
+/// `line` with its numeric tokens replaced by a placeholder, and the
+/// numbers they held
+pub fn numeric_skeleton(line: &str) -> (String, Vec<f64>) {
+    let bytes = line.as_bytes();
+    let mut skeleton = String::with_capacity(line.len());
+    let mut numbers = Vec::new();
+    let (mut copied, mut i) = (0, 0);
+    while i < bytes.len() {
+        let starts_token = match bytes[i] {
+            b'0'..=b'9' | b'.' | b'+' | b'-' => i == 0 || !is_word(bytes[i - 1]),
+            _ => false,
+        };
+        let end = if starts_token { numeric_token_end(bytes, i) } else { None };
+        match end.and_then(|end| Some((end, line[i..end].parse::<f64>().ok()?))) {
+            Some((end, number)) => {
+                skeleton.push_str(&line[copied..i]);
+                skeleton.push(PLACEHOLDER);
+                numbers.push(number);
+                (copied, i) = (end, end);
+            }
+            None => i += 1,
+        }
+    }
+    skeleton.push_str(&line[copied..]);
+    (skeleton, numbers)
+}
+
+/// Opcodes for `a` -> `b` counting lines equal within `tolerance` as
+/// equal. Lines are matched on their skeletons, then each matched pair is
+/// confirmed by comparing its numbers, and pairs that aren't close become
+/// `replace` opcodes.
+pub fn get_opcodes_numeric(a: &[String], b: &[String], tolerance: &NumericTolerance) -> Vec<OpCode> {
+    let skeletons = |lines: &[String]| -> (Vec<String>, Vec<Vec<f64>>) {
+        lines.iter().map(|line| numeric_skeleton(line)).unzip()
+    };
+    let ((a_skeletons, a_numbers), (b_skeletons, b_numbers)) = (skeletons(a), skeletons(b));
+    let matcher = SequenceMatcher::new(&a_skeletons, &b_skeletons);
+    let mut ops = Vec::new();
+    for op in matcher.get_opcodes() {
+        if op.tag != OpTag::Equal {
+            push_opcode(&mut ops, op.clone());
+            continue;
+        }
+        for (i, j) in (op.i1..op.i2).zip(op.j1..op.j2) {
+            let close = a_numbers[i].iter().zip(&b_numbers[j]).all(|(&x, &y)| tolerance.close(x, y));
+            let tag = if close { OpTag::Equal } else { OpTag::Replace };
+            push_opcode(&mut ops, OpCode { tag, i1: i, i2: i + 1, j1: j, j2: j + 1 });
+        }
+    }
+    ops
+}
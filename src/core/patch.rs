@@ -0,0 +1,233 @@
+//! Applying unified diffs, like GNU `patch`.
+
+use super::checksum::{lines_checksum, parse_checksum};
+use super::matcher::{OpTag, SequenceMatcher};
+use super::merge::{merge3, parse_base};
+use super::unified::{unified_diff, DiffLabels, HunkHeader, UnifiedDiffOptions};
+use super::Error;
+use std::ops::Range;
+
+/// Where `apply_unified_diff` placed a hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HunkPlacement {
+    /// Lines between the position in the hunk's header and where it
+    /// applied, negative when it applied earlier
+    pub offset: isize,
+    /// Context lines ignored at each end of the hunk to make it apply
+    pub fuzz: usize,
+}
+
+/// One hunk of a patch: the 0-based position of its first old line and its
+/// body as `(prefix, content)` pairs
+struct Hunk<'p> {
+    old_start: usize,
+    lines: Vec<(u8, &'p str)>,
+    /// `lines_checksum` of the old lines from the header, see
+    /// `add_hunk_checksums`
+    checksum: Option<u32>,
+}
+
+impl<'p> Hunk<'p> {
+    /// The old and new lines of the hunk without `fuzz` context lines at
+    /// either end, and how many leading context lines were dropped
+    fn trimmed(&self, fuzz: usize) -> (Vec<&'p str>, Vec<&'p str>, usize) {
+        let leading = self.lines.iter().take_while(|(prefix, _)| *prefix == b' ').count();
+        let trailing = self.lines.iter().rev().take_while(|(prefix, _)| *prefix == b' ').count();
+        let drop_leading = fuzz.min(leading);
+        let drop_trailing = fuzz.min(trailing).min(self.lines.len() - drop_leading);
+        let body = &self.lines[drop_leading..self.lines.len() - drop_trailing];
+        let side = |skip: u8| body.iter().filter(|(prefix, _)| *prefix != skip).map(|(_, line)| *line).collect();
+        (side(b'+'), side(b'-'), drop_leading)
+    }
+}
+
+/// Split `patch` into its hunks, skipping file headers and anything else
+/// outside a hunk
+fn parse_hunks(patch: &[String]) -> Result<Vec<Hunk<'_>>, Error> {
+    let mut hunks = Vec::new();
+    let mut lines = patch.iter();
+    while let Some(line) = lines.next() {
+        if !line.starts_with("@@") {
+            continue;
+        }
+        let header = HunkHeader::parse(line).ok_or_else(|| Error::MalformedHunkHeader(line.clone()))?;
+        let (mut old_left, mut new_left) = (header.old.1, header.new.1);
+        let mut hunk = Hunk {
+            old_start: if old_left == 0 { header.old.0 } else { header.old.0.saturating_sub(1) },
+            lines: Vec::with_capacity(old_left + new_left),
+            checksum: parse_checksum(header.rest),
+        };
+        while old_left > 0 || new_left > 0 {
+            let body = lines.next().ok_or_else(|| Error::MalformedHunkHeader(line.clone()))?;
+            let (prefix, content) = match body.as_bytes().first() {
+                Some(b'\\') => continue,
+                Some(&prefix @ (b' ' | b'-' | b'+')) => (prefix, &body[1..]),
+                // Blank context lines whose space was stripped, e.g. by an editor
+                None | Some(b'\n' | b'\r') => (b' ', body.as_str()),
+                Some(_) => return Err(Error::MalformedHunkHeader(line.clone())),
+            };
+            if prefix != b'+' {
+                old_left = old_left.checked_sub(1).ok_or_else(|| Error::MalformedHunkHeader(line.clone()))?;
+            }
+            if prefix != b'-' {
+                new_left = new_left.checked_sub(1).ok_or_else(|| Error::MalformedHunkHeader(line.clone()))?;
+            }
+            hunk.lines.push((prefix, content));
+        }
+        hunks.push(hunk);
+    }
+    Ok(hunks)
+}
+
+/// First position at or after `cursor` where `old` matches `a`, trying
+/// `center`, then one line either side (earlier first), and so on up to
+/// `max_offset` lines away
+fn find_hunk(a: &[String], old: &[&str], center: isize, cursor: usize, max_offset: usize) -> Option<usize> {
+    let fits = |pos: isize| {
+        let pos = usize::try_from(pos).ok().filter(|&pos| pos >= cursor && pos + old.len() <= a.len())?;
+        a[pos..pos + old.len()].iter().zip(old).all(|(line, old)| line == old).then_some(pos)
+    };
+    for distance in 0..=max_offset as isize {
+        let (before, after) = (center - distance, center + distance);
+        if before < cursor as isize && after + old.len() as isize > a.len() as isize {
+            break;
+        }
+        if let Some(pos) = fits(before).or_else(|| fits(after)) {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+/// Apply the unified diff `patch` to `a`, returning the patched lines and
+/// where each hunk went.
+///
+/// Like GNU `patch`, a hunk whose lines aren't at the position in its
+/// header is searched for nearby, up to `max_offset` lines away (or
+/// anywhere after the previous hunk when `None`), starting from where the
+/// previous hunk's offset predicts it. When it isn't found, up to `fuzz`
+/// context lines are ignored at each end of the hunk (`patch -F`) and the
+/// search repeats. Hunks must apply in order without overlapping; the first
+/// one that can't be placed fails with `Error::HunkFailed`. A hunk whose
+/// header carries a checksum (see `add_hunk_checksums`) is first checked
+/// against it, failing with `Error::HunkChecksumMismatch`.
+pub fn apply_unified_diff(
+    a: &[String],
+    patch: &[String],
+    fuzz: usize,
+    max_offset: Option<usize>,
+) -> Result<(Vec<String>, Vec<HunkPlacement>), Error> {
+    let hunks = parse_hunks(patch)?;
+    let max_offset = max_offset.unwrap_or(a.len());
+    let mut result = Vec::with_capacity(a.len());
+    let mut placements = Vec::with_capacity(hunks.len());
+    let mut cursor = 0;
+    let mut last_offset = 0isize;
+
+    for (number, hunk) in hunks.iter().enumerate() {
+        if let Some(checksum) = hunk.checksum {
+            let (old, _, _) = hunk.trimmed(0);
+            if lines_checksum(old) != checksum {
+                return Err(Error::HunkChecksumMismatch(number + 1));
+            }
+        }
+        let placed = (0..=fuzz).find_map(|fuzz| {
+            let (old, new, dropped) = hunk.trimmed(fuzz);
+            let expected = (hunk.old_start + dropped) as isize;
+            let pos = find_hunk(a, &old, expected + last_offset, cursor, max_offset)?;
+            Some((pos, old.len(), new, HunkPlacement { offset: pos as isize - expected, fuzz }))
+        });
+        let (pos, old_len, new, placement) = placed.ok_or(Error::HunkFailed(number + 1))?;
+        result.extend_from_slice(&a[cursor..pos]);
+        result.extend(new.into_iter().map(String::from));
+        cursor = pos + old_len;
+        last_offset = placement.offset;
+        placements.push(placement);
+    }
+    result.extend_from_slice(&a[cursor..]);
+
+    Ok((result, placements))
+}
+
+/// Whether `unified_diff(a, b)` with `options` turns `a` back into exactly
+/// `b` when applied by `apply_unified_diff` without fuzz or offsets, for
+/// checking a patch before trusting it.
+///
+/// The matcher and grouping options never break this. Options that change
+/// what the diff shows, such as `show_line_numbers`, `max_line_width` or a
+/// `normalizer`, can, and make this return false rather than an error; an
+/// option outside its valid range is still an error.
+pub fn verify_diff<'a>(a: &'a [String], b: &'a [String], options: &UnifiedDiffOptions<'a>) -> Result<bool, Error> {
+    let patch = unified_diff(a, b, &DiffLabels::default(), options)?;
+    Ok(apply_unified_diff(a, &patch, 0, Some(0)).is_ok_and(|(patched, _)| patched == b))
+}
+
+/// `apply_unified_diff` falling back to a three-way merge, like
+/// `git apply --3way`, returning the patched lines and how many conflicts
+/// they contain.
+///
+/// When a hunk fails to apply and `patch` embeds its base (see
+/// `UnifiedDiffOptions::embed_base`), the patch is applied to that base
+/// exactly and the result merged with `a` by `merge3`, leaving conflict
+/// markers where both changed the same lines differently. Without a base
+/// the error is returned as is.
+pub fn apply_unified_diff_3way(
+    a: &[String],
+    patch: &[String],
+    fuzz: usize,
+    max_offset: Option<usize>,
+) -> Result<(Vec<String>, usize), Error> {
+    let err = match apply_unified_diff(a, patch, fuzz, max_offset) {
+        Ok((lines, _)) => return Ok((lines, 0)),
+        Err(err @ (Error::HunkFailed(_) | Error::HunkChecksumMismatch(_))) => err,
+        Err(err) => return Err(err),
+    };
+    let Some(base) = parse_base(patch) else {
+        return Err(err);
+    };
+    let (theirs, _) = apply_unified_diff(&base, patch, 0, Some(0))?;
+    let (merged, conflicts) = merge3(&base, a, &theirs);
+    Ok((merged, conflicts.len()))
+}
+
+/// Combine two patches of the same `base`, returning the merged lines and
+/// the range of `base` each conflict replaces.
+///
+/// Each patch is applied to `base` like `apply_unified_diff` without fuzz,
+/// then the two results are merged by `merge3`: changes to different lines
+/// are both kept, and lines changed differently by the two patches become
+/// a conflict, `patch1`'s version between `<<<<<<< ours` and `=======`,
+/// then `patch2`'s up to `>>>>>>> theirs`.
+pub fn merge_patches(
+    base: &[String],
+    patch1: &[String],
+    patch2: &[String],
+) -> Result<(Vec<String>, Vec<Range<usize>>), Error> {
+    let (ours, _) = apply_unified_diff(base, patch1, 0, None)?;
+    let (theirs, _) = apply_unified_diff(base, patch2, 0, None)?;
+    Ok(merge3(base, &ours, &theirs))
+}
+
+/// `(start, end, lines)`: replace `a[start..end]` by `lines`
+pub type LineEdit = (usize, usize, Vec<String>);
+
+/// The fewest line edits turning `a` into `b`, one per changed region of
+/// the opcodes, in ascending order. A deletion directly followed by an
+/// insertion is a single edit. Applying them from last to first leaves the
+/// ranges of the ones still to apply valid, and gives `b`.
+pub fn line_edits(a: &[String], b: &[String]) -> Vec<LineEdit> {
+    let matcher = SequenceMatcher::new(a, b);
+    let mut edits: Vec<LineEdit> = Vec::new();
+    for op in matcher.get_opcodes().iter().filter(|op| op.tag != OpTag::Equal) {
+        match edits.last_mut() {
+            // Equal opcodes are never empty, so touching edits had no
+            // unchanged lines between them
+            Some((_, end, lines)) if *end == op.i1 => {
+                *end = op.i2;
+                lines.extend_from_slice(&b[op.j1..op.j2]);
+            }
+            _ => edits.push((op.i1, op.i2, b[op.j1..op.j2].to_vec())),
+        }
+    }
+    edits
+}
@@ -0,0 +1,83 @@
+//! Opcodes under a caller-supplied equality that may depend on where the
+//! lines are, for aligning tables whose header rows must always match
+//! while data rows are compared loosely.
+//!
+//! The equality is asked about pairs of positions rather than hashed per
+//! line, so there is no `b2j` index and no junk heuristic: finding each
+//! anchor compares every pair of lines in its region, O(n·m) for the
+//! first one.
+
+use super::matcher::{opcodes_from_blocks, OpCode};
+
+/// Number of line pairs above which comparing them all gets slow enough to
+/// be worth a warning
+pub const PREDICATE_WARN_PAIRS: usize = 10_000_000;
+
+/// Longest run of pairs `eq` holds for in `a[alo..ahi]` x `b[blo..bhi]`,
+/// earliest in `a` then in `b` on ties, like `find_longest_match`
+fn longest_run<F>(
+    a: &[String],
+    b: &[String],
+    eq: &mut F,
+    (alo, ahi): (usize, usize),
+    (blo, bhi): (usize, usize),
+) -> (usize, usize, usize)
+where
+    F: FnMut(usize, &str, usize, &str) -> bool,
+{
+    let (mut besti, mut bestj, mut bestsize) = (alo, blo, 0);
+    // run[k] is the length of the run ending at (i - 1, blo + k - 1)
+    let mut run = vec![0usize; bhi - blo + 1];
+    let mut next = vec![0usize; bhi - blo + 1];
+    for (i, a_line) in a.iter().enumerate().take(ahi).skip(alo) {
+        for (j, b_line) in b.iter().enumerate().take(bhi).skip(blo) {
+            let k = j - blo + 1;
+            next[k] = if eq(i, a_line, j, b_line) { run[k - 1] + 1 } else { 0 };
+            if next[k] > bestsize {
+                (besti, bestj, bestsize) = (i + 1 - next[k], j + 1 - next[k], next[k]);
+            }
+        }
+        std::mem::swap(&mut run, &mut next);
+    }
+    (besti, bestj, bestsize)
+}
+
+/// Opcodes for `a` -> `b` with lines `a[i]` and `b[j]` equal when
+/// `eq(i, a[i], j, b[j])` holds, found the way `SequenceMatcher` finds
+/// them: the longest run of equal pairs, then recursively the regions on
+/// either side of it.
+///
+/// `eq` may treat the same two lines differently at different positions,
+/// so it can't be hashed: every pair of lines in a region is compared,
+/// O(n·m) calls for `a` and `b` of `n` and `m` lines instead of the
+/// near-linear matching of `get_opcodes`.
+pub fn get_opcodes_with<F>(a: &[String], b: &[String], mut eq: F) -> Vec<OpCode>
+where
+    F: FnMut(usize, &str, usize, &str) -> bool,
+{
+    let mut blocks = Vec::new();
+    let mut queue = vec![(0, a.len(), 0, b.len())];
+    while let Some((alo, ahi, blo, bhi)) = queue.pop() {
+        let (i, j, k) = longest_run(a, b, &mut eq, (alo, ahi), (blo, bhi));
+        if k > 0 {
+            blocks.push((i, j, k));
+            if alo < i && blo < j {
+                queue.push((alo, i, blo, j));
+            }
+            if i + k < ahi && j + k < bhi {
+                queue.push((i + k, ahi, j + k, bhi));
+            }
+        }
+    }
+    blocks.sort_unstable();
+
+    let mut collapsed: Vec<(usize, usize, usize)> = Vec::with_capacity(blocks.len() + 1);
+    for (i, j, k) in blocks {
+        match collapsed.last_mut() {
+            Some((i1, j1, k1)) if *i1 + *k1 == i && *j1 + *k1 == j => *k1 += k,
+            _ => collapsed.push((i, j, k)),
+        }
+    }
+    collapsed.push((a.len(), b.len(), 0));
+    opcodes_from_blocks(&collapsed)
+}
@@ -0,0 +1,165 @@
+//! Key-oriented diffs of `key=value` records, e.g. config files.
+
+use super::inline::inline_char_diff;
+use super::matcher::{OpTag, SequenceMatcher};
+use super::unified::diff_str;
+use std::collections::{BTreeMap, HashMap};
+
+/// Keys added, removed and changed between two sets of records, each sorted
+/// by key
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordDiff {
+    /// `(key, value)` of keys only in the new records
+    pub added: Vec<(String, String)>,
+    /// `(key, value)` of keys only in the old records
+    pub removed: Vec<(String, String)>,
+    /// `(key, old, new)` of keys whose value differs
+    pub changed: Vec<(String, String, String)>,
+}
+
+/// Parse `key=value` lines, splitting at the first `=` and trimming
+/// whitespace around both parts. Blank lines are skipped and a line without
+/// `=` is a key with an empty value.
+pub fn parse_records(lines: &[String]) -> Vec<(String, String)> {
+    lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (key, value) = line.split_once('=').unwrap_or((line, ""));
+            (key.trim().to_string(), value.trim().to_string())
+        })
+        .collect()
+}
+
+/// Diff two sets of records by key. Records are sorted by key first, so
+/// their order doesn't matter, and like a dict a repeated key keeps its
+/// last value.
+///
+/// The sorted keys are compared with `SequenceMatcher`: keys in `equal`
+/// runs whose values differ are changed, and the keys of the other opcodes
+/// are removed or added.
+pub fn diff_records(a: Vec<(String, String)>, b: Vec<(String, String)>) -> RecordDiff {
+    let a: BTreeMap<String, String> = a.into_iter().collect();
+    let b: BTreeMap<String, String> = b.into_iter().collect();
+    let (a_keys, a_values): (Vec<String>, Vec<String>) = a.into_iter().unzip();
+    let (b_keys, b_values): (Vec<String>, Vec<String>) = b.into_iter().unzip();
+
+    let mut diff = RecordDiff::default();
+    let matcher = SequenceMatcher::new(&a_keys, &b_keys);
+    for op in matcher.get_opcodes() {
+        if op.tag == OpTag::Equal {
+            for (i, j) in (op.i1..op.i2).zip(op.j1..op.j2) {
+                if a_values[i] != b_values[j] {
+                    diff.changed.push((a_keys[i].clone(), a_values[i].clone(), b_values[j].clone()));
+                }
+            }
+            continue;
+        }
+        // Keys are unique and sorted, so no key is on both sides of a change
+        for i in op.i1..op.i2 {
+            diff.removed.push((a_keys[i].clone(), a_values[i].clone()));
+        }
+        for j in op.j1..op.j2 {
+            diff.added.push((b_keys[j].clone(), b_values[j].clone()));
+        }
+    }
+    diff
+}
+
+/// `RecordDiff` with an inline character diff of each changed value
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyedDiff {
+    /// `(key, value)` of keys only in the new records
+    pub added: Vec<(String, String)>,
+    /// `(key, value)` of keys only in the old records
+    pub removed: Vec<(String, String)>,
+    /// `(key, old, new, inline)` of keys whose value differs, `inline`
+    /// being `inline_char_diff(old, new)`
+    pub changed: Vec<(String, String, String, String)>,
+}
+
+/// Align two sets of `(key, text)` records by key, then diff the texts of
+/// the keys on both sides character by character, e.g. for translation
+/// files. Matching is by key as in `diff_records`, so the order of the
+/// records doesn't matter and the results are sorted by key.
+pub fn keyed_diff(a: Vec<(String, String)>, b: Vec<(String, String)>) -> KeyedDiff {
+    let RecordDiff { added, removed, changed } = diff_records(a, b);
+    let changed = changed
+        .into_iter()
+        .map(|(key, old, new)| {
+            let inline = inline_char_diff(&old, &new);
+            (key, old, new, inline)
+        })
+        .collect();
+    KeyedDiff { added, removed, changed }
+}
+
+/// How one key of an ordered map changed, see `map_diff`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapChange {
+    Added { key: String, value: String },
+    Removed { key: String, value: String },
+    /// `diff` is the unified diff of the values from `diff_str`, labelled
+    /// with the key, if asked for and either value has several lines
+    Changed { key: String, old: String, new: String, diff: Option<String> },
+    Unchanged { key: String, value: String },
+}
+
+/// Keys in first-seen order, each with its last value, like inserting the
+/// pairs into a dict
+fn dedup_keys(pairs: Vec<(String, String)>) -> (Vec<String>, HashMap<String, String>) {
+    let mut keys = Vec::with_capacity(pairs.len());
+    let mut values = HashMap::with_capacity(pairs.len());
+    for (key, value) in pairs {
+        if values.insert(key.clone(), value).is_none() {
+            keys.push(key);
+        }
+    }
+    (keys, values)
+}
+
+/// Diff two ordered maps key by key, keeping their order: unlike
+/// `diff_records` every key of either map gets a `MapChange`, ordered by
+/// how `SequenceMatcher` interleaves the two key sequences. A key that
+/// moved is reported once, at whichever of its positions comes first. Like
+/// a dict, a repeated key keeps its first position and its last value.
+///
+/// With `value_context`, a changed key whose old or new value spans
+/// several lines also gets a unified diff of the values with that many
+/// context lines.
+pub fn map_diff(a: Vec<(String, String)>, b: Vec<(String, String)>, value_context: Option<usize>) -> Vec<MapChange> {
+    let (a_keys, mut a_values) = dedup_keys(a);
+    let (b_keys, mut b_values) = dedup_keys(b);
+
+    let compare = |key: &String, old: String, new: String| {
+        if old == new {
+            return MapChange::Unchanged { key: key.clone(), value: new };
+        }
+        let diff = value_context
+            .filter(|_| old.contains('\n') || new.contains('\n'))
+            .map(|n| diff_str(&old, &new, key, key, n));
+        MapChange::Changed { key: key.clone(), old, new, diff }
+    };
+
+    let mut changes = Vec::with_capacity(a_keys.len().max(b_keys.len()));
+    let matcher = SequenceMatcher::new(&a_keys, &b_keys);
+    for op in matcher.get_opcodes() {
+        // A key on both sides of changes is reported where it comes first,
+        // after which it is gone from both maps
+        for key in &a_keys[op.i1..op.i2] {
+            let Some(old) = a_values.remove(key) else { continue };
+            changes.push(match b_values.remove(key) {
+                Some(new) => compare(key, old, new),
+                None => MapChange::Removed { key: key.clone(), value: old },
+            });
+        }
+        for key in &b_keys[op.j1..op.j2] {
+            let Some(new) = b_values.remove(key) else { continue };
+            changes.push(match a_values.remove(key) {
+                Some(old) => compare(key, old, new),
+                None => MapChange::Added { key: key.clone(), value: new },
+            });
+        }
+    }
+    changes
+}
@@ -0,0 +1,106 @@
+//! Diffs of documents made of titled sections, e.g. markdown changelogs.
+
+use super::matcher::SequenceMatcher;
+use super::unified::{unified_diff, DiffLabels, UnifiedDiffOptions};
+use super::Error;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Split `lines` into sections, each starting at a line that starts with
+/// `prefix`, as `(title, lines)` with the title being the heading line
+/// without its line ending. Lines before the first heading form a section
+/// titled `""`, omitted when there are none.
+fn split_sections(lines: &[String], prefix: &str) -> Vec<(String, Range<usize>)> {
+    let mut sections: Vec<(String, Range<usize>)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with(prefix) {
+            let title = line.trim_end_matches(['\r', '\n']).to_string();
+            sections.push((title, i..i + 1));
+        } else {
+            match sections.last_mut() {
+                Some((_, range)) => range.end = i + 1,
+                None => sections.push((String::new(), i..i + 1)),
+            }
+        }
+    }
+    sections
+}
+
+/// A section title and which occurrence of it in its document a section is
+type SectionKey = (String, usize);
+
+/// Sections keyed by `(title, occurrence)`, so the k-th section of a
+/// repeated title pairs with the k-th one of the same title on the other
+/// side, plus the keys in order
+fn key_sections(sections: Vec<(String, Range<usize>)>) -> (Vec<SectionKey>, HashMap<SectionKey, Range<usize>>) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut keys = Vec::with_capacity(sections.len());
+    let mut ranges = HashMap::with_capacity(sections.len());
+    for (title, range) in sections {
+        let occurrence = seen.entry(title.clone()).or_insert(0);
+        let key = (title, *occurrence);
+        *occurrence += 1;
+        keys.push(key.clone());
+        ranges.insert(key, range);
+    }
+    (keys, ranges)
+}
+
+/// Diff two documents section by section. Both are split at the lines
+/// starting with `section_pattern`, e.g. `"## "` for markdown headings;
+/// the pattern is a literal prefix, not a regular expression. Sections are
+/// paired by title and each pair is diffed with `unified_diff`, so hunks
+/// stay within their section and count lines from its heading. A section
+/// only on one side is reported wholesale, as all removed or all added.
+///
+/// Returns `(title, occurrence, diff)` for the sections that changed,
+/// ordered by how `SequenceMatcher` interleaves the two title sequences,
+/// like `map_diff`. Both file labels of a diff are its title, ended with
+/// `lineterm`. A title repeated in a document pairs its occurrences in
+/// order, `occurrence` counting them from 0, and each changed pair gets
+/// its own diff.
+pub fn sectioned_diff<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    section_pattern: &str,
+    lineterm: &str,
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<Vec<(String, usize, Vec<String>)>, Error> {
+    if section_pattern.is_empty() {
+        return Err(Error::InvalidArgument("section_pattern must not be empty"));
+    }
+    let (a_keys, mut a_ranges) = key_sections(split_sections(a, section_pattern));
+    let (b_keys, mut b_ranges) = key_sections(split_sections(b, section_pattern));
+    let mut diffs = Vec::new();
+    let mut compare = |key: &SectionKey, a_range: Range<usize>, b_range: Range<usize>| {
+        if a[a_range.clone()] == b[b_range.clone()] {
+            return Ok(());
+        }
+        let labels = DiffLabels {
+            fromfile: &key.0,
+            tofile: &key.0,
+            lineterm,
+            ..DiffLabels::default()
+        };
+        let diff = unified_diff(&a[a_range], &b[b_range], &labels, options)?;
+        diffs.push((key.0.clone(), key.1, diff));
+        Ok::<(), Error>(())
+    };
+
+    let titles = |keys: &[SectionKey]| keys.iter().map(|(title, _)| title.clone()).collect::<Vec<_>>();
+    let (a_titles, b_titles) = (titles(&a_keys), titles(&b_keys));
+    let matcher = SequenceMatcher::new(&a_titles, &b_titles);
+    for op in matcher.get_opcodes() {
+        // A section on both sides is reported where it comes first, after
+        // which it is gone from both documents
+        for key in &a_keys[op.i1..op.i2] {
+            let Some(a_range) = a_ranges.remove(key) else { continue };
+            compare(key, a_range, b_ranges.remove(key).unwrap_or(0..0))?;
+        }
+        for key in &b_keys[op.j1..op.j2] {
+            let Some(b_range) = b_ranges.remove(key) else { continue };
+            compare(key, a_ranges.remove(key).unwrap_or(0..0), b_range)?;
+        }
+    }
+    Ok(diffs)
+}
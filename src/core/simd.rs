@@ -0,0 +1,54 @@
+//! Bulk equality for the match extension loops (`simd` feature).
+//!
+//! Both sequences are interned to integer ids once per matcher, so
+//! extending a match compares fixed-size blocks of ids, which the compiler
+//! lowers to vector compares, instead of one `String` at a time.
+
+use rustc_hash::FxHashMap;
+
+/// Ids compared per step before falling back to one at a time
+const LANES: usize = 16;
+
+/// `a` and `b` with each distinct line replaced by the same small integer
+pub fn intern<'a>(a: &'a [String], b: &'a [String]) -> (Vec<u32>, Vec<u32>) {
+    let mut ids: FxHashMap<&'a str, u32> = FxHashMap::default();
+    let mut intern_all = |lines: &'a [String]| -> Vec<u32> {
+        lines
+            .iter()
+            .map(|line| {
+                let next = ids.len() as u32;
+                *ids.entry(line.as_str()).or_insert(next)
+            })
+            .collect()
+    };
+    let a_ids = intern_all(a);
+    let b_ids = intern_all(b);
+    (a_ids, b_ids)
+}
+
+/// Length of the longest common prefix of `a` and `b`
+pub fn common_prefix(a: &[u32], b: &[u32]) -> usize {
+    let len = a.len().min(b.len());
+    let mut n = 0;
+    while n + LANES <= len && a[n..n + LANES] == b[n..n + LANES] {
+        n += LANES;
+    }
+    while n < len && a[n] == b[n] {
+        n += 1;
+    }
+    n
+}
+
+/// Length of the longest common suffix of `a` and `b`
+pub fn common_suffix(a: &[u32], b: &[u32]) -> usize {
+    let len = a.len().min(b.len());
+    let (a, b) = (&a[a.len() - len..], &b[b.len() - len..]);
+    let mut n = 0;
+    while n + LANES <= len && a[len - n - LANES..len - n] == b[len - n - LANES..len - n] {
+        n += LANES;
+    }
+    while n < len && a[len - n - 1] == b[len - n - 1] {
+        n += 1;
+    }
+    n
+}
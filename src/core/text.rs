@@ -0,0 +1,129 @@
+//! Helpers for turning raw text into lines.
+
+/// Line boundaries recognized by Python's `str.splitlines`, besides `\r\n`
+const LINE_BREAKS: [char; 10] = [
+    '\n', '\r', '\x0b', '\x0c', '\x1c', '\x1d', '\x1e', '\u{85}', '\u{2028}', '\u{2029}',
+];
+
+/// Length in bytes of the line break starting at `bytes[i]`, 0 if there is
+/// none there
+#[inline]
+fn line_break_len(bytes: &[u8], i: usize) -> usize {
+    match bytes[i] {
+        b'\r' if bytes.get(i + 1) == Some(&b'\n') => 2,
+        b'\n' | b'\r' | 0x0b | 0x0c | 0x1c..=0x1e => 1,
+        // U+0085 NEXT LINE
+        0xc2 if bytes.get(i + 1) == Some(&0x85) => 2,
+        // U+2028 LINE SEPARATOR and U+2029 PARAGRAPH SEPARATOR
+        0xe2 if bytes.get(i + 1) == Some(&0x80) && matches!(bytes.get(i + 2), Some(0xa8 | 0xa9)) => 3,
+        _ => 0,
+    }
+}
+
+/// Split a string into lines like Python's `str.splitlines(keepends)`:
+/// `\n`, `\r\n` and `\r` end a line, as do the other ASCII and Unicode
+/// boundaries Python recognizes (`\v`, `\f`, `\x1c`-`\x1e`, U+0085,
+/// U+2028 and U+2029). A final line break doesn't start an empty line.
+pub fn split_lines(text: &str, keepends: bool) -> Vec<String> {
+    // Fast path for empty strings
+    if text.is_empty() {
+        return Vec::new();
+    }
+    
+    // Pre-allocate based on estimated line count (similar to CPython's approach)
+    // Estimate: assume average line length of 80 chars
+    let estimated_lines = (text.len() / 80).max(1).min(text.len());
+    let mut lines = Vec::with_capacity(estimated_lines);
+    
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    
+    while i < bytes.len() {
+        let line_start = i;
+        
+        // Scan for the next line break (byte-level; every break is a whole
+        // UTF-8 sequence, so the slices below stay on char boundaries)
+        let mut break_len = 0;
+        while i < bytes.len() {
+            break_len = line_break_len(bytes, i);
+            if break_len > 0 {
+                break;
+            }
+            i += 1;
+        }
+        
+        let eol = if keepends { i + break_len } else { i };
+        lines.push(text[line_start..eol].to_string());
+        i += break_len;
+    }
+    
+    lines
+}
+
+
+/// Whether `text` ends in a line break
+pub fn ends_with_newline(text: &str) -> bool {
+    text.ends_with(LINE_BREAKS)
+}
+
+/// `line` split into its content and its line break, `""` if it has none
+pub fn split_line_end(line: &str) -> (&str, &str) {
+    if let Some(content) = line.strip_suffix("\r\n") {
+        return (content, &line[content.len()..]);
+    }
+    match line.char_indices().next_back() {
+        Some((i, c)) if LINE_BREAKS.contains(&c) => line.split_at(i),
+        _ => (line, ""),
+    }
+}
+
+/// Append an empty sentinel line to non-empty `lines` whose text didn't end
+/// in a line break, so adding or removing a final newline shows up in the
+/// opcodes as an insert or delete of that line. The sentinel is an ordinary
+/// empty line and so can also match a real blank line.
+pub fn track_final_newline(lines: &mut Vec<String>, ends_with_newline: bool) {
+    if !lines.is_empty() && !ends_with_newline {
+        lines.push(String::new());
+    }
+}
+
+/// Kind of line break a line ends with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    Cr,
+    /// One of the rarer breaks `split_lines` recognizes, e.g. `\x0c` or
+    /// U+2028
+    Other,
+    None,
+}
+
+impl LineEnding {
+    /// The line break `line` ends with
+    pub fn of(line: &str) -> Self {
+        match split_line_end(line).1 {
+            "\n" => LineEnding::Lf,
+            "\r\n" => LineEnding::Crlf,
+            "\r" => LineEnding::Cr,
+            "" => LineEnding::None,
+            _ => LineEnding::Other,
+        }
+    }
+
+    /// Lowercase name: `lf`, `crlf`, `cr`, `other` or `none`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "lf",
+            LineEnding::Crlf => "crlf",
+            LineEnding::Cr => "cr",
+            LineEnding::Other => "other",
+            LineEnding::None => "none",
+        }
+    }
+}
+
+/// The line break each of `lines` ends with, e.g. to flag mixed endings
+pub fn line_ending_report(lines: &[String]) -> Vec<LineEnding> {
+    lines.iter().map(|line| LineEnding::of(line)).collect()
+}
@@ -0,0 +1,51 @@
+//! Tab-separated diff rows for spreadsheet import.
+
+use super::matcher::{OpTag, SequenceMatcher};
+
+/// One row per line of `a` -> `b`: `op\told_lineno\tnew_lineno\tcontent`,
+/// with `op` one of `equal`, `delete`, `insert`, `replace-del` and
+/// `replace-ins`. Line numbers are 1-based and left blank for the side a row
+/// doesn't exist on. Backslashes, tabs and line breaks in `content` are
+/// escaped as `\\`, `\t`, `\n` and `\r`.
+pub fn tsv_diff(a: &[String], b: &[String]) -> Vec<String> {
+    let matcher = SequenceMatcher::new(a, b);
+    let mut rows = Vec::with_capacity(a.len().max(b.len()));
+    for op in matcher.get_opcodes() {
+        match op.tag {
+            OpTag::Equal => {
+                for (i, j) in (op.i1..op.i2).zip(op.j1..op.j2) {
+                    rows.push(tsv_row("equal", Some(i), Some(j), &a[i]));
+                }
+            }
+            OpTag::Delete | OpTag::Replace | OpTag::Insert => {
+                let (del, ins) = match op.tag {
+                    OpTag::Replace => ("replace-del", "replace-ins"),
+                    _ => ("delete", "insert"),
+                };
+                for (i, line) in (op.i1..).zip(&a[op.i1..op.i2]) {
+                    rows.push(tsv_row(del, Some(i), None, line));
+                }
+                for (j, line) in (op.j1..).zip(&b[op.j1..op.j2]) {
+                    rows.push(tsv_row(ins, None, Some(j), line));
+                }
+            }
+        }
+    }
+    rows
+}
+
+fn tsv_row(op: &str, old: Option<usize>, new: Option<usize>, content: &str) -> String {
+    let lineno = |index: Option<usize>| index.map(|i| (i + 1).to_string()).unwrap_or_default();
+    let mut row = format!("{}\t{}\t{}\t", op, lineno(old), lineno(new));
+    row.reserve(content.len());
+    for c in content.chars() {
+        match c {
+            '\\' => row.push_str("\\\\"),
+            '\t' => row.push_str("\\t"),
+            '\n' => row.push_str("\\n"),
+            '\r' => row.push_str("\\r"),
+            c => row.push(c),
+        }
+    }
+    row
+}
@@ -0,0 +1,1075 @@
+//! Rendering, parsing and transforming unified diffs.
+
+use super::ansi::{ansi_line, LineStyle};
+use super::checksum::{format_checksum, lines_checksum, strip_checksum};
+use super::comments::{drop_comment_groups, CommentSyntax};
+use super::grouping::{
+    apply_scope_hints, diff_stats, group_opcodes, group_opcodes_adaptive, group_opcodes_within, merge_small_groups,
+    rendered_line_count, validate_grouped_opcodes, validate_grouped_opcodes_by, AdaptiveContext, DiffStats,
+    ScopeHints,
+};
+use super::matcher::{
+    calculate_ratio, check_range, get_opcodes_windowed, OpCode, OpTag, SequenceMatcher, SequenceMatcherBuilder,
+};
+use super::merge::{base_preamble, parse_base_header};
+use super::normalize::Normalizer;
+use super::numeric::{get_opcodes_numeric, NumericTolerance};
+use super::text::{ends_with_newline, split_line_end, split_lines};
+use super::Error;
+use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::ops::Range;
+
+pub fn format_range_unified(start: usize, stop: usize) -> String {
+    let beginning = start + 1;
+    let length = stop.saturating_sub(start);
+    if length == 1 {
+        format!("{}", beginning)
+    } else if length == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", beginning, length)
+    }
+}
+
+/// Inverse of `format_range_unified`, returns `(start, length)` as written
+pub fn parse_range_unified(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, length)) => Some((start.parse().ok()?, length.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// A parsed `@@ -<range> +<range> @@` line. Ranges are kept exactly as
+/// written (`start,length`), `rest` is everything after the ranges: the
+/// closing `@@`, any section heading and the line terminator.
+#[derive(Debug, Clone, Copy)]
+pub struct HunkHeader<'a> {
+    pub old: (usize, usize),
+    pub new: (usize, usize),
+    pub rest: &'a str,
+}
+
+impl<'a> HunkHeader<'a> {
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let rest = line.strip_prefix("@@ -")?;
+        let (old, rest) = rest.split_once(" +")?;
+        let end = rest.find(" @@")?;
+        Some(Self {
+            old: parse_range_unified(old)?,
+            new: parse_range_unified(&rest[..end])?,
+            rest: &rest[end..],
+        })
+    }
+
+    pub fn format(&self) -> String {
+        let range = |(start, length): (usize, usize)| {
+            if length == 1 {
+                start.to_string()
+            } else {
+                format!("{},{}", start, length)
+            }
+        };
+        format!("@@ -{} +{}{}", range(self.old), range(self.new), self.rest)
+    }
+}
+
+/// Scale `added`/`removed` into a `+`/`-` bar of at most `max_width`
+/// characters, keeping at least one mark for any non-zero count like git
+/// as far as the width allows
+pub fn diffstat_bar(added: usize, removed: usize, max_width: usize) -> String {
+    let total = added + removed;
+    let (plus, minus) = if total <= max_width {
+        (added, removed)
+    } else {
+        let mut plus = (added * max_width + total / 2) / total;
+        if added > 0 && plus == 0 {
+            plus = 1;
+        }
+        if removed > 0 && plus == max_width {
+            plus -= 1;
+        }
+        // A bar of no width has no room for the mark kept above
+        let plus = plus.min(max_width);
+        (plus, max_width - plus)
+    };
+    let mut bar = String::with_capacity(plus + minus);
+    bar.extend(std::iter::repeat_n('+', plus));
+    bar.extend(std::iter::repeat_n('-', minus));
+    bar
+}
+
+/// File labels and line terminator for the header lines of a unified diff
+#[derive(Debug, Clone, Copy)]
+pub struct DiffLabels<'a> {
+    pub fromfile: &'a str,
+    pub tofile: &'a str,
+    pub fromfiledate: &'a str,
+    pub tofiledate: &'a str,
+    pub lineterm: &'a str,
+}
+
+impl Default for DiffLabels<'_> {
+    /// Empty labels and `"\n"`, the defaults of Python's `unified_diff`
+    fn default() -> Self {
+        Self {
+            fromfile: "",
+            tofile: "",
+            fromfiledate: "",
+            tofiledate: "",
+            lineterm: "\n",
+        }
+    }
+}
+
+/// Exact number of lines `unified_diff_lines` renders for `groups`, so the
+/// output is allocated once
+pub fn diff_line_count(groups: &[Vec<OpCode>]) -> usize {
+    if groups.is_empty() {
+        return 0;
+    }
+    let body: usize = groups
+        .iter()
+        .flatten()
+        .map(|op| match op.tag {
+            OpTag::Equal => op.i2 - op.i1,
+            _ => (op.i2 - op.i1) + (op.j2 - op.j1),
+        })
+        .sum();
+    2 + groups.len() + body
+}
+
+/// A unified diff line as `(prefix, text)`. Content lines borrow their text
+/// from the inputs and carry `b' '`, `b'-'` or `b'+'` as prefix; file and
+/// hunk headers own their text and have prefix 0.
+pub type DiffLine<'a> = (u8, Cow<'a, str>);
+
+/// Render grouped opcodes as unified diff lines without copying any input
+/// line; see `materialize_lines` to get owned strings.
+pub fn unified_diff_lines<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    groups: &[Vec<OpCode>],
+    labels: &DiffLabels,
+) -> Vec<DiffLine<'a>> {
+    let mut result: Vec<DiffLine<'a>> = Vec::with_capacity(diff_line_count(groups));
+
+    for (index, group) in groups.iter().enumerate() {
+        if index == 0 {
+            let fromdate = if labels.fromfiledate.is_empty() {
+                String::new()
+            } else {
+                format!("\t{}", labels.fromfiledate)
+            };
+            let todate = if labels.tofiledate.is_empty() {
+                String::new()
+            } else {
+                format!("\t{}", labels.tofiledate)
+            };
+
+            result.push((0, Cow::Owned(format!("--- {}{}{}", labels.fromfile, fromdate, labels.lineterm))));
+            result.push((0, Cow::Owned(format!("+++ {}{}{}", labels.tofile, todate, labels.lineterm))));
+        }
+
+        let first = &group[0];
+        let last = &group[group.len() - 1];
+
+        let file1_range = format_range_unified(first.i1, last.i2);
+        let file2_range = format_range_unified(first.j1, last.j2);
+
+        result.push((0, Cow::Owned(format!("@@ -{} +{} @@{}", file1_range, file2_range, labels.lineterm))));
+
+        for opcode in group {
+            match opcode.tag {
+                OpTag::Equal => {
+                    result.extend(a[opcode.i1..opcode.i2].iter().map(|text| (b' ', Cow::Borrowed(text.as_str()))));
+                }
+                OpTag::Delete | OpTag::Replace => {
+                    result.extend(a[opcode.i1..opcode.i2].iter().map(|text| (b'-', Cow::Borrowed(text.as_str()))));
+                    if opcode.tag == OpTag::Replace {
+                        result.extend(b[opcode.j1..opcode.j2].iter().map(|text| (b'+', Cow::Borrowed(text.as_str()))));
+                    }
+                }
+                OpTag::Insert => {
+                    result.extend(b[opcode.j1..opcode.j2].iter().map(|text| (b'+', Cow::Borrowed(text.as_str()))));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Appended to content cut short by `truncate_lines`
+pub const TRUNCATION_MARKER: &str = "…";
+
+/// Cut the content of diff lines longer than `max_width` characters down
+/// to `max_width` characters followed by `TRUNCATION_MARKER`, keeping
+/// their line break. Headers are left alone.
+pub fn truncate_lines(lines: &mut [DiffLine], max_width: usize) {
+    for (prefix, text) in lines.iter_mut() {
+        if *prefix == 0 {
+            continue;
+        }
+        let (content, line_end) = split_line_end(text);
+        let Some((cut, _)) = content.char_indices().nth(max_width) else {
+            continue;
+        };
+        *text = Cow::Owned(format!("{}{}{}", &content[..cut], TRUNCATION_MARKER, line_end));
+    }
+}
+
+/// Append a `crc32=` comment to each hunk header of `lines`, rendered from
+/// `groups`, holding `lines_checksum` of the lines of `a` the hunk expects
+/// (its context and removed lines). `apply_unified_diff` checks it.
+pub fn add_hunk_checksums(a: &[String], groups: &[Vec<OpCode>], lines: &mut [DiffLine], lineterm: &str) {
+    let headers = lines.iter_mut().filter(|(prefix, text)| *prefix == 0 && text.starts_with("@@"));
+    for ((_, header), group) in headers.zip(groups) {
+        let old = &a[group[0].i1..group[group.len() - 1].i2];
+        let checksum = format_checksum(lines_checksum(old.iter().map(String::as_str)));
+        let text = header.strip_suffix(lineterm).unwrap_or(header);
+        *header = Cow::Owned(format!("{} {}{}", text, checksum, lineterm));
+    }
+}
+
+/// Prefix each content line of `lines` with its old and new line numbers,
+/// right-aligned in columns of `width` characters and followed by a space,
+/// as in `  3    foo` for a removed line 3. Removed lines only show the old
+/// number and added lines the new one. Numbers follow the hunk headers.
+pub fn add_line_numbers(lines: &mut [DiffLine], width: usize) {
+    let (mut old, mut new) = (0, 0);
+    for line in lines.iter_mut() {
+        let (prefix, text) = line;
+        if *prefix == 0 {
+            if let Some(header) = HunkHeader::parse(text) {
+                (old, new) = (header.old.0, header.new.0);
+            }
+            continue;
+        }
+        // The number of the line on one side, and the next one
+        let column = |shown: bool, next: &mut usize| {
+            if !shown {
+                return String::new();
+            }
+            *next += 1;
+            (*next - 1).to_string()
+        };
+        let old_number = column(*prefix != b'+', &mut old);
+        let new_number = column(*prefix != b'-', &mut new);
+        *line = (
+            0,
+            Cow::Owned(format!("{:>width$} {:>width$} {}{}", old_number, new_number, *prefix as char, text)),
+        );
+    }
+}
+
+/// Equal lines kept at each end of a run hidden by `elide_equal_over`
+pub const ELISION_KEEP: usize = 2;
+
+/// Ranges of `lines` to hide behind an elision marker: the middle of every
+/// run of more than `over` context lines between two changed lines of a
+/// hunk, leaving `ELISION_KEEP` lines at each end. Runs that would hide
+/// fewer than two lines are left alone. `lines` must still have their
+/// prefixes, i.e. not have gone through `add_line_numbers`.
+fn elided_ranges(lines: &[DiffLine], over: usize) -> Vec<Range<usize>> {
+    let changed = |line: Option<&DiffLine>| matches!(line, Some((b'-' | b'+', _)));
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        if lines[start].0 != b' ' {
+            start += 1;
+            continue;
+        }
+        let end = start + lines[start..].iter().take_while(|(prefix, _)| *prefix == b' ').count();
+        let internal = start > 0 && changed(lines.get(start - 1)) && changed(lines.get(end));
+        if internal && end - start > over && end - start >= 2 * ELISION_KEEP + 2 {
+            ranges.push(start + ELISION_KEEP..end - ELISION_KEEP);
+        }
+        start = end;
+    }
+    ranges
+}
+
+/// Replace each of the ascending, non-overlapping `ranges` of `lines` with
+/// a line like `… 12 unchanged lines …`
+fn elide_ranges(lines: &mut Vec<DiffLine>, ranges: &[Range<usize>], lineterm: &str) {
+    for range in ranges.iter().rev() {
+        let marker = format!("… {} unchanged lines …{}", range.len(), lineterm);
+        lines.splice(range.clone(), [(0, Cow::Owned(marker))]);
+    }
+}
+
+/// Turn borrowed diff lines into the owned strings Python's difflib yields,
+/// allocating only for lines that need a prefix
+pub fn materialize_lines(lines: Vec<DiffLine>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|(prefix, text)| {
+            if prefix == 0 {
+                return text.into_owned();
+            }
+            let mut line = String::with_capacity(text.len() + 1);
+            line.push(prefix as char);
+            line.push_str(&text);
+            line
+        })
+        .collect()
+}
+
+/// Render grouped opcodes as unified diff lines
+pub fn format_unified_diff(a: &[String], b: &[String], groups: &[Vec<OpCode>], labels: &DiffLabels) -> Vec<String> {
+    materialize_lines(unified_diff_lines(a, b, groups, labels))
+}
+
+/// Matcher and grouping options of `unified_diff`
+#[derive(Debug, Clone)]
+pub struct UnifiedDiffOptions<'a> {
+    /// Number of context lines
+    pub n: usize,
+    /// Extra context lines allowed to keep brace scopes together, see
+    /// `ScopeHints::braces`
+    pub brace_scope_context: Option<usize>,
+    /// Diff in overlapping windows of this many lines, see
+    /// `get_opcodes_windowed`
+    pub window_size: Option<usize>,
+    /// Junk and popularity settings of the underlying matcher
+    pub matcher: SequenceMatcherBuilder<'a>,
+    /// Also merge hunks with up to `n` hidden lines between them
+    pub compact: bool,
+    /// Check the hunks with `validate_grouped_opcodes` before rendering
+    pub validate: bool,
+    /// Render a single notice line instead of the diff when the inputs'
+    /// similarity ratio is below this
+    pub min_ratio: Option<f64>,
+    /// Size each hunk's context by how dense its changes are instead of
+    /// using `n`, see `AdaptiveContext`
+    pub adaptive_context: Option<AdaptiveContext>,
+    /// Keep comment-only lines from anchoring matches, and drop hunks that
+    /// only change comments if `CommentSyntax::ignore_changes` is set
+    pub comments: Option<CommentSyntax>,
+    /// Show at most this many characters of each line's content, see
+    /// `truncate_lines`. Matching still compares whole lines.
+    pub max_line_width: Option<usize>,
+    /// Count lines whose numbers are within this tolerance as equal, see
+    /// `get_opcodes_numeric`. Lines are then matched with the default
+    /// matcher settings, ignoring `matcher` and `comments` junk.
+    pub numeric_tolerance: Option<NumericTolerance>,
+    /// Append a checksum of the expected old lines to each hunk header,
+    /// see `add_hunk_checksums`
+    pub hunk_checksums: bool,
+    /// Put the whole of `a` before a non-empty diff, so that
+    /// `apply_unified_diff_3way` can merge when the hunks don't apply, see
+    /// `base_preamble`
+    pub embed_base: bool,
+    /// Match lines by their `Normalizer::key` instead of as they are. The
+    /// diff shows the original lines, and inputs whose keys are all equal
+    /// have an empty diff. `matcher` junk and `comments` apply to the keys.
+    pub normalizer: Option<Normalizer>,
+    /// Prefix content lines with their old and new line numbers, see
+    /// `add_line_numbers`. The output is then no longer a valid patch.
+    pub show_line_numbers: bool,
+    /// Render the hunks last one first, each hunk's lines still in order,
+    /// e.g. to show the changes at the bottom of a log first. The output
+    /// is then no longer a valid patch.
+    pub reverse_hunks: bool,
+    /// Stop the output before it grows past this many bytes and end it
+    /// with a notice of what was left out, see `cap_output_bytes`
+    pub max_output_bytes: Option<usize>,
+    /// Merge hunks of fewer lines than this with a neighbor, see
+    /// `merge_small_groups`
+    pub min_hunk_lines: Option<usize>,
+    /// Show runs of more than this many context lines between two changes
+    /// of a hunk as a marker line with the number of lines hidden, keeping
+    /// `ELISION_KEEP` lines at each end. Hunk headers still count the
+    /// hidden lines, so the output is then no longer a valid patch.
+    pub elide_equal_over: Option<usize>,
+    /// Pick the context size instead of using `n`: the largest one whose
+    /// diff renders to at most this many lines, or no context if even
+    /// that is too long, see `auto_context_groups`
+    pub auto_context: Option<usize>,
+}
+
+impl Default for UnifiedDiffOptions<'_> {
+    fn default() -> Self {
+        Self {
+            n: 3,
+            brace_scope_context: None,
+            window_size: None,
+            matcher: SequenceMatcherBuilder::default(),
+            compact: false,
+            validate: false,
+            min_ratio: None,
+            adaptive_context: None,
+            comments: None,
+            max_line_width: None,
+            numeric_tolerance: None,
+            hunk_checksums: false,
+            embed_base: false,
+            normalizer: None,
+            show_line_numbers: false,
+            reverse_hunks: false,
+            max_output_bytes: None,
+            min_hunk_lines: None,
+            elide_equal_over: None,
+            auto_context: None,
+        }
+    }
+}
+
+/// Grouped opcodes behind `unified_diff`
+pub fn unified_diff_groups<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<Vec<Vec<OpCode>>, Error> {
+    unified_diff_range_groups(a, b, 0..a.len(), 0..b.len(), options)
+}
+
+/// Grouped opcodes behind `unified_diff_range`
+pub fn unified_diff_range_groups<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    a_range: Range<usize>,
+    b_range: Range<usize>,
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<Vec<Vec<OpCode>>, Error> {
+    let codes = range_opcodes(a, b, a_range.clone(), b_range.clone(), options)?;
+    group_range_opcodes(a, b, codes, a_range, b_range, options)
+}
+
+/// Opcodes of `a[a_range]` -> `b[b_range]`, empty when the two are identical
+fn range_opcodes<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    a_range: Range<usize>,
+    b_range: Range<usize>,
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<Vec<OpCode>, Error> {
+    check_range(&a_range, a.len())?;
+    check_range(&b_range, b.len())?;
+    if options.min_ratio.is_some_and(|min_ratio| !(0.0..=1.0).contains(&min_ratio)) {
+        return Err(Error::InvalidArgument("min_ratio must be in [0.0, 1.0]"));
+    }
+    if options.adaptive_context.is_some_and(|context| context.min > context.max) {
+        return Err(Error::InvalidArgument("adaptive_context minimum is above its maximum"));
+    }
+    if options.adaptive_context.is_some() && options.auto_context.is_some() {
+        return Err(Error::InvalidArgument("adaptive_context can't be combined with auto_context"));
+    }
+    if let Some(comments) = &options.comments {
+        comments.validate()?;
+    }
+    if let Some(tolerance) = &options.numeric_tolerance {
+        tolerance.validate()?;
+        if options.window_size.is_some() {
+            return Err(Error::InvalidArgument("numeric_tolerance can't be combined with window_size"));
+        }
+    }
+
+    if let Some(normalizer) = &options.normalizer {
+        let (a_keys, b_keys) = (normalizer.keys(a), normalizer.keys(b));
+        let options = UnifiedDiffOptions {
+            normalizer: None,
+            ..options.clone()
+        };
+        return range_opcodes(&a_keys, &b_keys, a_range, b_range, &options);
+    }
+
+    // If sequences are identical, return empty result like Python's difflib
+    if a[a_range.clone()] == b[b_range.clone()] {
+        return Ok(Vec::new());
+    }
+
+    let matcher = match &options.comments {
+        Some(comments) => Cow::Owned(options.matcher.clone().extend_junk(comments.junk(b))),
+        None => Cow::Borrowed(&options.matcher),
+    };
+    if let Some(tolerance) = &options.numeric_tolerance {
+        let (alo, blo) = (a_range.start, b_range.start);
+        let mut codes = get_opcodes_numeric(&a[a_range], &b[b_range], tolerance);
+        for op in &mut codes {
+            (op.i1, op.i2, op.j1, op.j2) = (op.i1 + alo, op.i2 + alo, op.j1 + blo, op.j2 + blo);
+        }
+        return Ok(codes);
+    }
+    Ok(match options.window_size {
+        Some(0) => return Err(Error::InvalidArgument("window_size must be positive")),
+        Some(window_size) => {
+            let (alo, blo) = (a_range.start, b_range.start);
+            let mut codes = get_opcodes_windowed(&a[a_range], &b[b_range], window_size, &matcher);
+            for op in &mut codes {
+                (op.i1, op.i2, op.j1, op.j2) = (op.i1 + alo, op.i2 + alo, op.j1 + blo, op.j2 + blo);
+            }
+            codes
+        }
+        None => matcher
+            .build(a, b)
+            .get_opcodes_in(a_range.start, a_range.end, b_range.start, b_range.end),
+    })
+}
+
+fn group_range_opcodes<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    codes: Vec<OpCode>,
+    a_range: Range<usize>,
+    b_range: Range<usize>,
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<Vec<Vec<OpCode>>, Error> {
+    let groups = match options.auto_context {
+        Some(target_lines) => {
+            let max_n = a_range.len().max(b_range.len());
+            auto_context_groups(target_lines, max_n, |n| group_with_context(a, b, &codes, n, options))
+        }
+        None => group_with_context(a, b, &codes, options.n, options),
+    };
+    if options.validate {
+        // Lines were matched by their normalized keys, and numbers within
+        // the tolerance, so check them the same way
+        let keys = options.normalizer.as_ref().map(|normalizer| (normalizer.keys(a), normalizer.keys(b)));
+        let (a, b) = keys.as_ref().map_or((a, b), |(a_keys, b_keys)| (a_keys.as_slice(), b_keys.as_slice()));
+        match &options.numeric_tolerance {
+            Some(tolerance) => {
+                validate_grouped_opcodes_by(a, b, &groups, a_range, b_range, |x, y| tolerance.lines_equal(x, y))?
+            }
+            None => validate_grouped_opcodes(a, b, &groups, a_range, b_range)?,
+        }
+    }
+    Ok(match &options.comments {
+        Some(comments) if comments.ignore_changes => drop_comment_groups(a, b, groups, comments),
+        _ => groups,
+    })
+}
+
+/// `codes` grouped into hunks with `n` lines of context and the other
+/// grouping options of `options`
+fn group_with_context(
+    a: &[String],
+    b: &[String],
+    codes: &[OpCode],
+    n: usize,
+    options: &UnifiedDiffOptions,
+) -> Vec<Vec<OpCode>> {
+    // Compact mode also merges hunks with up to `n` hidden lines between them,
+    // trading those lines for one less `@@` header
+    let max_gap = if options.compact { 3 * n } else { 2 * n };
+    let group = |codes: Vec<OpCode>| match &options.adaptive_context {
+        Some(context) => group_opcodes_adaptive(codes, context, options.compact),
+        None => group_opcodes_within(codes, n, max_gap),
+    };
+    let groups = match options.brace_scope_context {
+        Some(max_extra) => apply_scope_hints(a, b, codes, group(codes.to_vec()), &ScopeHints::braces(max_extra)),
+        None => group(codes.to_vec()),
+    };
+    match options.min_hunk_lines {
+        Some(min_hunk_lines) => merge_small_groups(groups, min_hunk_lines),
+        None => groups,
+    }
+}
+
+/// Hunks of `group(n)` for the largest `n` up to `max_n` whose diff has at
+/// most `target_lines` lines by `rendered_line_count`, or those of
+/// `group(0)` if none fits. More context never makes a diff shorter, so
+/// `n` is found by binary search.
+///
+/// Only grouping is repeated: `group` regroups opcodes matched once,
+/// which is cheap next to the matching, so a large `max_n` costs a few
+/// dozen regroupings at most.
+pub fn auto_context_groups(
+    target_lines: usize,
+    max_n: usize,
+    mut group: impl FnMut(usize) -> Vec<Vec<OpCode>>,
+) -> Vec<Vec<OpCode>> {
+    let mut best = group(0);
+    if rendered_line_count(&best) > target_lines {
+        return best;
+    }
+    // group(lo) fits; find the last n in lo..=hi that does
+    let (mut lo, mut hi) = (0, max_n);
+    while lo < hi {
+        let mid = lo + (hi - lo).div_ceil(2);
+        let groups = group(mid);
+        if rendered_line_count(&groups) <= target_lines {
+            (lo, best) = (mid, groups);
+        } else {
+            hi = mid - 1;
+        }
+    }
+    best
+}
+
+/// `format_unified_diff` with `options.hunk_checksums`, the content cut to
+/// `options.max_line_width`, `options.show_line_numbers`, the runs elided
+/// by `options.elide_equal_over` and the base preamble of
+/// `options.embed_base`
+fn render_groups(
+    a: &[String],
+    b: &[String],
+    groups: &[Vec<OpCode>],
+    labels: &DiffLabels,
+    options: &UnifiedDiffOptions,
+) -> Vec<String> {
+    render_groups_styled(a, b, groups, labels, options).0
+}
+
+/// `render_groups` along with the `LineStyle` of each line, told from the
+/// prefixes before `add_line_numbers` moves them
+fn render_groups_styled(
+    a: &[String],
+    b: &[String],
+    groups: &[Vec<OpCode>],
+    labels: &DiffLabels,
+    options: &UnifiedDiffOptions,
+) -> (Vec<String>, Vec<LineStyle>) {
+    let reversed: Vec<Vec<OpCode>>;
+    let groups = if options.reverse_hunks {
+        reversed = groups.iter().rev().cloned().collect();
+        &reversed
+    } else {
+        groups
+    };
+    let mut lines = unified_diff_lines(a, b, groups, labels);
+    let mut styles: Vec<LineStyle> = lines.iter().map(|(prefix, text)| LineStyle::of(*prefix, text)).collect();
+    if options.hunk_checksums {
+        add_hunk_checksums(a, groups, &mut lines, labels.lineterm);
+    }
+    let elided = options.elide_equal_over.map(|over| elided_ranges(&lines, over));
+    if let Some(max_width) = options.max_line_width {
+        truncate_lines(&mut lines, max_width);
+    }
+    if options.show_line_numbers {
+        add_line_numbers(&mut lines, a.len().max(b.len()).max(1).to_string().len());
+    }
+    if let Some(ranges) = elided {
+        elide_ranges(&mut lines, &ranges, labels.lineterm);
+        for range in ranges.iter().rev() {
+            styles.splice(range.clone(), [LineStyle::Context]);
+        }
+    }
+    let mut result = if options.embed_base && !groups.is_empty() {
+        base_preamble(a, labels.lineterm)
+    } else {
+        Vec::new()
+    };
+    styles.splice(0..0, vec![LineStyle::Header; result.len()]);
+    result.extend(materialize_lines(lines));
+    if let Some(max_bytes) = options.max_output_bytes {
+        if cap_output_bytes(&mut result, max_bytes, labels.lineterm) {
+            styles.truncate(result.len() - 1);
+            styles.push(LineStyle::Hunk);
+        }
+    }
+    (result, styles)
+}
+
+/// The line ending output cut short by `cap_output_bytes`, e.g.
+/// `@@ output truncated: 40 lines omitted (2 hunks) @@`
+fn truncation_notice(lines: usize, hunks: usize, lineterm: &str) -> String {
+    format!("@@ output truncated: {} lines omitted ({} hunks) @@{}", lines, hunks, lineterm)
+}
+
+/// Keep the longest run of leading `lines` that fits in `max_bytes`
+/// together with a `truncation_notice` of the lines after it, counting
+/// each line's UTF-8 length with its prefix and line ending. The notice
+/// counts the hunks whose header was cut; a hunk cut partway keeps its
+/// header and is not counted.
+///
+/// Lines that fit are left alone. The notice is kept even when it alone
+/// doesn't fit, so a cut is never silent. Returns whether `lines` were
+/// cut.
+pub fn cap_output_bytes(lines: &mut Vec<String>, max_bytes: usize, lineterm: &str) -> bool {
+    let total: usize = lines.iter().map(String::len).sum();
+    if total <= max_bytes {
+        return false;
+    }
+    // hunks_after[k] is the number of hunk headers in lines[k..]
+    let mut hunks_after = vec![0; lines.len() + 1];
+    for (k, line) in lines.iter().enumerate().rev() {
+        hunks_after[k] = hunks_after[k + 1] + usize::from(line.starts_with("@@"));
+    }
+    let notice_after = |kept: usize| truncation_notice(lines.len() - kept, hunks_after[kept], lineterm);
+    let (mut kept, mut used) = (0, 0);
+    while kept < lines.len() && used + lines[kept].len() + notice_after(kept + 1).len() <= max_bytes {
+        used += lines[kept].len();
+        kept += 1;
+    }
+    let notice = notice_after(kept);
+    lines.truncate(kept);
+    lines.push(notice);
+    true
+}
+
+/// The line rendered instead of the diff when `codes` are less similar than
+/// `options.min_ratio`, e.g. `@@ files differ substantially (ratio=0.12) @@`
+fn min_ratio_notice(
+    codes: &[OpCode],
+    a_range: &Range<usize>,
+    b_range: &Range<usize>,
+    options: &UnifiedDiffOptions,
+    lineterm: &str,
+) -> Option<String> {
+    let min_ratio = options.min_ratio?;
+    if codes.is_empty() {
+        return None;
+    }
+    let matches = codes
+        .iter()
+        .filter(|op| op.tag == OpTag::Equal)
+        .map(|op| op.i2 - op.i1)
+        .sum();
+    let ratio = calculate_ratio(matches, a_range.len() + b_range.len());
+    (ratio < min_ratio).then(|| format!("@@ files differ substantially (ratio={:.2}) @@{}", ratio, lineterm))
+}
+
+/// Compare two sequences of lines and generate the unified diff, like
+/// Python's `difflib.unified_diff`
+pub fn unified_diff<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    labels: &DiffLabels,
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<Vec<String>, Error> {
+    unified_diff_range(a, b, 0..a.len(), 0..b.len(), labels, options)
+}
+
+/// `unified_diff` plus its `(added, removed, hunks)` counts, from a single
+/// computation of the grouped opcodes
+pub fn unified_diff_with_stats<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    labels: &DiffLabels,
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<(Vec<String>, DiffStats), Error> {
+    let (a_range, b_range) = (0..a.len(), 0..b.len());
+    let codes = range_opcodes(a, b, a_range.clone(), b_range.clone(), options)?;
+    let notice = min_ratio_notice(&codes, &a_range, &b_range, options, labels.lineterm);
+    let groups = group_range_opcodes(a, b, codes, a_range, b_range, options)?;
+    let stats = diff_stats(&groups);
+    let lines = match notice {
+        Some(notice) => vec![notice],
+        None => render_groups(a, b, &groups, labels, options),
+    };
+    Ok((lines, stats))
+}
+
+/// `unified_diff` as plain lines and as lines colored for a terminal with
+/// `ansi_line`, from a single computation of the grouped opcodes, e.g. to
+/// log the plain diff and show the colored one
+pub fn unified_diff_dual<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    labels: &DiffLabels,
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<(Vec<String>, Vec<String>), Error> {
+    let (a_range, b_range) = (0..a.len(), 0..b.len());
+    let codes = range_opcodes(a, b, a_range.clone(), b_range.clone(), options)?;
+    let (lines, styles) = match min_ratio_notice(&codes, &a_range, &b_range, options, labels.lineterm) {
+        Some(notice) => (vec![notice], vec![LineStyle::Hunk]),
+        None => {
+            let groups = group_range_opcodes(a, b, codes, a_range, b_range, options)?;
+            render_groups_styled(a, b, &groups, labels, options)
+        }
+    };
+    let colored = lines.iter().zip(styles).map(|(line, style)| ansi_line(line, style)).collect();
+    Ok((lines, colored))
+}
+
+/// `unified_diff` of only `a[a_range]` and `b[b_range]`, for re-diffing a
+/// region known to contain every change. Hunk headers keep the line numbers
+/// of the whole sequences.
+pub fn unified_diff_range<'a>(
+    a: &'a [String],
+    b: &'a [String],
+    a_range: Range<usize>,
+    b_range: Range<usize>,
+    labels: &DiffLabels,
+    options: &UnifiedDiffOptions<'a>,
+) -> Result<Vec<String>, Error> {
+    let codes = range_opcodes(a, b, a_range.clone(), b_range.clone(), options)?;
+    if let Some(notice) = min_ratio_notice(&codes, &a_range, &b_range, options, labels.lineterm) {
+        return Ok(vec![notice]);
+    }
+    let groups = group_range_opcodes(a, b, codes, a_range, b_range, options)?;
+    Ok(render_groups(a, b, &groups, labels, options))
+}
+
+/// Unified diff of two texts as one printable string, empty when they are
+/// equal. Both are split with `split_lines`, keeping line endings, and a
+/// `\n` is added to every diff line that doesn't already end in a line
+/// break, such as the last line of a text without a final newline.
+pub fn diff_str(a: &str, b: &str, fromfile: &str, tofile: &str, n: usize) -> String {
+    let (a, b) = (split_lines(a, true), split_lines(b, true));
+    let labels = DiffLabels {
+        fromfile,
+        tofile,
+        ..DiffLabels::default()
+    };
+    let groups = group_opcodes(SequenceMatcher::new(&a, &b).get_opcodes().to_vec(), n);
+    let lines = unified_diff_lines(&a, &b, &groups, &labels);
+    let mut out = String::with_capacity(lines.iter().map(|(_, text)| text.len() + 2).sum());
+    for (prefix, text) in lines {
+        if prefix != 0 {
+            out.push(prefix as char);
+        }
+        out.push_str(&text);
+        if !ends_with_newline(&text) {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Reverse a unified diff so it turns `b` back into `a`, like `patch -R`.
+///
+/// File headers and hunk ranges are swapped and `-`/`+` lines trade places;
+/// within each run of changes the new removals are emitted before the new
+/// additions so the output reads like a freshly computed diff.
+pub fn invert_unified_diff(patch: Vec<String>) -> Result<Vec<String>, Error> {
+    let mut result = Vec::with_capacity(patch.len());
+    let mut removed: Vec<String> = Vec::new();
+    let mut added: Vec<String> = Vec::new();
+    let mut old_left = 0usize;
+    let mut new_left = 0usize;
+
+    let flush = |result: &mut Vec<String>, removed: &mut Vec<String>, added: &mut Vec<String>| {
+        for line in added.drain(..) {
+            result.push(format!("-{}", &line[1..]));
+        }
+        for line in removed.drain(..) {
+            result.push(format!("+{}", &line[1..]));
+        }
+    };
+
+    let mut lines = patch.into_iter().peekable();
+    while let Some(line) = lines.next() {
+        if old_left > 0 || new_left > 0 {
+            match line.as_bytes().first() {
+                Some(b' ') => {
+                    flush(&mut result, &mut removed, &mut added);
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                    result.push(line);
+                }
+                Some(b'-') => {
+                    old_left = old_left.saturating_sub(1);
+                    removed.push(line);
+                }
+                Some(b'+') => {
+                    new_left = new_left.saturating_sub(1);
+                    added.push(line);
+                }
+                _ => {
+                    flush(&mut result, &mut removed, &mut added);
+                    result.push(line);
+                }
+            }
+            continue;
+        }
+        flush(&mut result, &mut removed, &mut added);
+
+        if line.starts_with("--- ") && lines.peek().is_some_and(|next| next.starts_with("+++ ")) {
+            let to_header = lines.next().unwrap();
+            result.push(format!("--- {}", &to_header[4..]));
+            result.push(format!("+++ {}", &line[4..]));
+        } else if line.starts_with("@@") {
+            let header = HunkHeader::parse(&line)
+                .ok_or_else(|| Error::MalformedHunkHeader(line.clone()))?;
+            old_left = header.old.1;
+            new_left = header.new.1;
+            // A checksum describes the old side, which is now the new one
+            let rest = strip_checksum(header.rest);
+            result.push(HunkHeader { old: header.new, new: header.old, rest: &rest }.format());
+        } else if let Some(count) = parse_base_header(&line) {
+            // The embedded base is the old side, which the inverse doesn't have
+            for _ in 0..count {
+                lines.next();
+            }
+        } else {
+            result.push(line);
+        }
+    }
+    flush(&mut result, &mut removed, &mut added);
+
+    Ok(result)
+}
+
+/// Just the `@@ -l,s +l,s @@` header of each hunk of `a` -> `b` with up
+/// to `n` lines of context, as an outline of where the changes are
+pub fn hunk_headers(a: &[String], b: &[String], n: usize) -> Vec<String> {
+    SequenceMatcher::new(a, b)
+        .get_grouped_opcodes(n)
+        .iter()
+        .map(|group| {
+            let (first, last) = (&group[0], &group[group.len() - 1]);
+            format!(
+                "@@ -{} +{} @@",
+                format_range_unified(first.i1, last.i2),
+                format_range_unified(first.j1, last.j2)
+            )
+        })
+        .collect()
+}
+
+/// One git-style diffstat line, e.g. `src/lib.rs | 12 +++++++-----`
+pub fn diffstat_line(a: &[String], b: &[String], filename: &str, max_width: usize) -> String {
+    let groups = group_opcodes(SequenceMatcher::new(a, b).get_opcodes().to_vec(), 3);
+    let (added, removed, _) = diff_stats(&groups);
+    let bar = diffstat_bar(added, removed, max_width);
+    if bar.is_empty() {
+        format!("{} | {}", filename, added + removed)
+    } else {
+        format!("{} | {} {}", filename, added + removed, bar)
+    }
+}
+
+/// `((old_start, old_end), (new_start, new_end))` spanning every change
+/// between `a` and `b`, as 0-based half-open ranges like an opcode's: from
+/// the start of the first non-equal opcode to the end of the last. A side
+/// only inserted into (or only deleted from) gets an empty range at the
+/// insertion point. `None` when nothing changed.
+pub fn changed_bounds(a: &[String], b: &[String]) -> Option<((usize, usize), (usize, usize))> {
+    let matcher = SequenceMatcher::new(a, b);
+    let mut changes = matcher.get_opcodes().iter().filter(|op| op.tag != OpTag::Equal);
+    let first = changes.next()?;
+    let last = changes.next_back().unwrap_or(first);
+    Some(((first.i1, last.i2), (first.j1, last.j2)))
+}
+
+/// Per-line flags of `a`: whether the line was deleted or replaced on the
+/// way to `b`, e.g. for a change gutter
+pub fn change_mask_old(a: &[String], b: &[String]) -> Vec<bool> {
+    let mut mask = vec![false; a.len()];
+    for op in SequenceMatcher::new(a, b).get_opcodes() {
+        if op.tag != OpTag::Equal {
+            mask[op.i1..op.i2].fill(true);
+        }
+    }
+    mask
+}
+
+/// Per-line flags of `b`: whether the line was inserted or replaced on the
+/// way from `a`
+pub fn change_mask_new(a: &[String], b: &[String]) -> Vec<bool> {
+    let mut mask = vec![false; b.len()];
+    for op in SequenceMatcher::new(a, b).get_opcodes() {
+        if op.tag != OpTag::Equal {
+            mask[op.j1..op.j2].fill(true);
+        }
+    }
+    mask
+}
+
+/// 1-based inclusive ranges of the 0-based half-open `spans`, merging
+/// spans that touch
+fn merged_line_ranges(spans: impl Iterator<Item = (usize, usize)>) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in spans {
+        match ranges.last_mut() {
+            Some((_, last)) if *last == start => *last = end,
+            _ => ranges.push((start + 1, end)),
+        }
+    }
+    ranges
+}
+
+/// 1-based inclusive `(first, last)` line ranges of `a` that are unchanged
+/// in `b`, in order. Runs of equal lines only separated by lines inserted
+/// in `b` are merged.
+pub fn unchanged_ranges_old(a: &[String], b: &[String]) -> Vec<(usize, usize)> {
+    let matcher = SequenceMatcher::new(a, b);
+    let equal = matcher.get_opcodes().iter().filter(|op| op.tag == OpTag::Equal);
+    merged_line_ranges(equal.map(|op| (op.i1, op.i2)))
+}
+
+/// 1-based inclusive `(first, last)` line ranges of `b` that were already
+/// in `a`, e.g. to skip re-linting untouched code. Runs of equal lines only
+/// separated by lines deleted from `a` are merged.
+pub fn unchanged_ranges_new(a: &[String], b: &[String]) -> Vec<(usize, usize)> {
+    let matcher = SequenceMatcher::new(a, b);
+    let equal = matcher.get_opcodes().iter().filter(|op| op.tag == OpTag::Equal);
+    merged_line_ranges(equal.map(|op| (op.j1, op.j2)))
+}
+
+/// Lines of `b` inserted or replaced on the way from `a`, in order and as
+/// they are, without prefixes or headers
+pub fn added_lines(a: &[String], b: &[String]) -> Vec<String> {
+    let matcher = SequenceMatcher::new(a, b);
+    let changes = matcher.get_opcodes().iter().filter(|op| op.tag != OpTag::Equal);
+    changes.flat_map(|op| b[op.j1..op.j2].iter().cloned()).collect()
+}
+
+/// Lines of `a` deleted or replaced on the way to `b`, in order and as
+/// they are
+pub fn removed_lines(a: &[String], b: &[String]) -> Vec<String> {
+    let matcher = SequenceMatcher::new(a, b);
+    let changes = matcher.get_opcodes().iter().filter(|op| op.tag != OpTag::Equal);
+    changes.flat_map(|op| a[op.i1..op.i2].iter().cloned()).collect()
+}
+
+/// Index of the first position where `a` and `b` differ, the same in both
+/// since it is the length of their common prefix, or `None` when they are
+/// equal. A sequence that is a proper prefix of the other differs at its
+/// end. Only the common prefix is compared, so this returns as soon as
+/// the sequences part, without diffing.
+pub fn first_difference(a: &[String], b: &[String]) -> Option<(usize, usize)> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    (prefix < a.len().max(b.len())).then_some((prefix, prefix))
+}
+
+/// Whether `a` -> `b` changes at most `max_diff_lines` lines, counting
+/// removed and added lines like `diff_stats`, for cheap gating.
+///
+/// Returns `false` before matching anything when a lower bound is already
+/// over budget: the length difference, then the lines of either side with
+/// no equal line left on the other. Otherwise the opcodes are counted,
+/// stopping at the first change over budget. The count follows difflib's
+/// opcodes, which aren't always the smallest diff, so the answer is
+/// conservative: it can be `false` although a smaller edit fits.
+pub fn differs_by_at_most(a: &[String], b: &[String], max_diff_lines: usize) -> bool {
+    if a.len().abs_diff(b.len()) > max_diff_lines {
+        return false;
+    }
+    let mut available: FxHashMap<&str, usize> = FxHashMap::default();
+    for line in b {
+        *available.entry(line.as_str()).or_default() += 1;
+    }
+    let mut shared = 0;
+    for line in a {
+        if let Some(count) = available.get_mut(line.as_str()).filter(|count| **count > 0) {
+            *count -= 1;
+            shared += 1;
+        }
+    }
+    if a.len() + b.len() - 2 * shared > max_diff_lines {
+        return false;
+    }
+
+    let mut changed = 0;
+    for op in SequenceMatcher::new(a, b).get_opcodes() {
+        if op.tag != OpTag::Equal {
+            changed += (op.i2 - op.i1) + (op.j2 - op.j1);
+            if changed > max_diff_lines {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Changed hunks per line of input: the number of hunks with `n` lines of
+/// context divided by `a.len() + b.len()`. High values mean changes are
+/// spread all over the files, low values mean a few tight hunks. `0.0` when
+/// nothing changed.
+pub fn hunk_density(a: &[String], b: &[String], n: usize) -> f64 {
+    let total = a.len() + b.len();
+    if total == 0 {
+        return 0.0;
+    }
+    let groups = group_opcodes(SequenceMatcher::new(a, b).get_opcodes().to_vec(), n);
+    let hunks = groups
+        .iter()
+        .filter(|group| group.iter().any(|op| op.tag != OpTag::Equal))
+        .count();
+    hunks as f64 / total as f64
+}
+
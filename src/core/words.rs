@@ -0,0 +1,103 @@
+//! Word-level unified diffs of prose.
+//!
+//! A word is a maximal run of non-whitespace characters, so word `k` of a
+//! text is `text.split_whitespace().nth(k)` (`text.split()[k]` in Python
+//! for ordinary whitespace). Whitespace only separates words: changing it
+//! alone doesn't change the diff.
+//!
+//! Text is scanned by extended grapheme clusters, so a combining mark
+//! after a space stays with the space rather than starting a word.
+
+use super::grouping::group_opcodes;
+use super::inline::InlineMarkers;
+use super::matcher::{OpTag, SequenceMatcher};
+use super::unified::format_range_unified;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Words of `text` and the whitespace before each
+fn words(text: &str) -> (Vec<String>, Vec<&str>) {
+    let mut words = Vec::new();
+    let mut spaces = Vec::new();
+    let mut space_start = 0;
+    let mut word_start = None;
+    let clusters = text.grapheme_indices(true).chain([(text.len(), " ")]);
+    for (i, cluster) in clusters {
+        match (word_start, cluster.starts_with(char::is_whitespace)) {
+            (None, false) => {
+                spaces.push(&text[space_start..i]);
+                word_start = Some(i);
+            }
+            (Some(start), true) => {
+                words.push(text[start..i].to_string());
+                (space_start, word_start) = (i, None);
+            }
+            _ => {}
+        }
+    }
+    (words, spaces)
+}
+
+/// Append `words` to `body`, rejoined with the whitespace in `spaces` and
+/// wrapped in `open` and `close`, with `isolate` and `pop` inside them.
+/// `separate` keeps the whitespace before the first word too, unless
+/// `body` is empty.
+fn push_words(body: &mut String, words: &[String], spaces: &[&str], delimiters: [&str; 4], separate: bool) {
+    let [open, isolate, pop, close] = delimiters;
+    for (k, (word, space)) in words.iter().zip(spaces).enumerate() {
+        if k > 0 || (separate && !body.is_empty()) {
+            body.push_str(space);
+        }
+        if k == 0 {
+            body.push_str(open);
+            body.push_str(isolate);
+        }
+        body.push_str(word);
+    }
+    if !words.is_empty() {
+        body.push_str(pop);
+        body.push_str(close);
+    }
+}
+
+/// Unified-style diff of the words of `a` and `b` with up to `n` words of
+/// context per hunk.
+///
+/// Each hunk is two strings: an `@@ -start,count +start,count @@` header
+/// counting words (1-based, like line numbers in a unified diff), and the
+/// hunk's words with the whitespace between them as in the source, deleted
+/// runs in `[-...-]` and inserted ones in `{+...+}`. Context and deleted
+/// words keep the whitespace of `a`, inserted words that of `b`.
+pub fn word_unified_diff(a: &str, b: &str, n: usize) -> Vec<String> {
+    word_unified_diff_with(a, b, n, &InlineMarkers::default())
+}
+
+/// `word_unified_diff` with changed runs wrapped in `markers`
+pub fn word_unified_diff_with(a: &str, b: &str, n: usize, markers: &InlineMarkers) -> Vec<String> {
+    let ((a_words, a_spaces), (b_words, b_spaces)) = (words(a), words(b));
+    let groups = group_opcodes(SequenceMatcher::new(&a_words, &b_words).get_opcodes().to_vec(), n);
+
+    let mut result = Vec::with_capacity(2 * groups.len());
+    for group in &groups {
+        let (first, last) = (&group[0], &group[group.len() - 1]);
+        result.push(format!(
+            "@@ -{} +{} @@",
+            format_range_unified(first.i1, last.i2),
+            format_range_unified(first.j1, last.j2)
+        ));
+        let mut body = String::new();
+        for op in group {
+            let deleted = (&a_words[op.i1..op.i2], &a_spaces[op.i1..op.i2]);
+            let inserted = (&b_words[op.j1..op.j2], &b_spaces[op.j1..op.j2]);
+            if op.tag == OpTag::Equal {
+                push_words(&mut body, deleted.0, deleted.1, [""; 4], true);
+                continue;
+            }
+            let deleted_markers = markers.around(markers.delete, &deleted.0.concat());
+            let inserted_markers = markers.around(markers.insert, &inserted.0.concat());
+            push_words(&mut body, deleted.0, deleted.1, deleted_markers, true);
+            push_words(&mut body, inserted.0, inserted.1, inserted_markers, op.tag == OpTag::Insert);
+        }
+        result.push(body);
+    }
+    result
+}
@@ -0,0 +1,118 @@
+//! `difflib-rs FROM TO`: print the unified diff of two files, like
+//! `diff -u`. Built with the `cli` feature.
+//!
+//! Exits with 0 when the files are identical, 1 when they differ and 2 on
+//! errors, like GNU diff.
+
+use difflib_rs::core::{split_lines, unified_diff, DiffLabels, UnifiedDiffOptions};
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: difflib-rs [-u | -U N | --unified[=N] | --context N] [--color] FROM TO";
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const CYAN: &str = "\x1b[36m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+
+/// Parsed command line
+struct Args {
+    context: usize,
+    color: bool,
+    from: String,
+    to: String,
+}
+
+fn parse_context(value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or("missing number of context lines")?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid number of context lines: {:?}", value))
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut args = args.into_iter();
+    let mut context = 3;
+    let mut color = false;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-u" | "--unified" => {}
+            "-U" | "--context" => context = parse_context(args.next())?,
+            "--color" => color = true,
+            "--" => paths.extend(args.by_ref()),
+            _ => {
+                if let Some(value) = arg.strip_prefix("--unified=").or_else(|| arg.strip_prefix("--context=")) {
+                    context = parse_context(Some(value.to_string()))?;
+                } else if arg.starts_with('-') && arg != "-" {
+                    return Err(format!("unknown option: {}", arg));
+                } else {
+                    paths.push(arg);
+                }
+            }
+        }
+    }
+    let [from, to]: [String; 2] = paths.try_into().map_err(|_| "expected two files to compare".to_string())?;
+    Ok(Args { context, color, from, to })
+}
+
+/// Contents of `path`, `-` being standard input
+fn read(path: &str) -> Result<String, String> {
+    let text = if path == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        std::fs::read_to_string(path)
+    };
+    text.map_err(|err| format!("{}: {}", path, err))
+}
+
+/// Print `diff`, marking content lines without a line break like GNU diff
+fn print_diff(out: &mut impl Write, diff: &[String], color: bool) -> io::Result<()> {
+    for (index, line) in diff.iter().enumerate() {
+        let style = match line.as_bytes().first() {
+            _ if !color => "",
+            Some(b'-' | b'+') if index < 2 => BOLD,
+            Some(b'@') => CYAN,
+            Some(b'-') => RED,
+            Some(b'+') => GREEN,
+            _ => "",
+        };
+        let (content, line_end) = match line.strip_suffix('\n') {
+            Some(content) => (content, ""),
+            None => (line.as_str(), "\n\\ No newline at end of file"),
+        };
+        let reset = if style.is_empty() { "" } else { RESET };
+        writeln!(out, "{}{}{}{}", style, content, reset, line_end)?;
+    }
+    Ok(())
+}
+
+fn run() -> Result<bool, String> {
+    let args = parse_args(std::env::args().skip(1)).map_err(|err| format!("{}\n{}", err, USAGE))?;
+    let (a, b) = (read(&args.from)?, read(&args.to)?);
+    let (a, b) = (split_lines(&a, true), split_lines(&b, true));
+    let labels = DiffLabels {
+        fromfile: &args.from,
+        tofile: &args.to,
+        ..DiffLabels::default()
+    };
+    let options = UnifiedDiffOptions {
+        n: args.context,
+        ..UnifiedDiffOptions::default()
+    };
+    let diff = unified_diff(&a, &b, &labels, &options).map_err(|err| err.to_string())?;
+    print_diff(&mut io::stdout().lock(), &diff, args.color).map_err(|err| err.to_string())?;
+    Ok(!diff.is_empty())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("difflib-rs: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
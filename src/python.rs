@@ -0,0 +1,1679 @@
+//! PyO3 bindings, the `difflib_rs` Python module.
+
+// pyo3 0.20's `#[pymethods]` expansion trips this lint on newer compilers
+#![allow(non_local_definitions)]
+
+use crate::core::{self, DiffLabels, InlineMarkers, OpCode, OpTag, SequenceMatcherBuilder, UnifiedDiffOptions};
+use pyo3::exceptions::{PyRuntimeWarning, PyTimeoutError, PyTypeError, PyValueError};
+use pyo3::import_exception;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyIterator, PyList, PyString, PyTuple};
+use rustc_hash::FxHashSet;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+impl From<core::Error> for PyErr {
+    fn from(err: core::Error) -> PyErr {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+/// Python-facing opcode tuple, `(tag, i1, i2, j1, j2)`
+type OpCodeTuple = (String, usize, usize, usize, usize);
+
+impl From<&OpCode> for OpCodeTuple {
+    fn from(op: &OpCode) -> Self {
+        (op.tag.as_str().to_string(), op.i1, op.i2, op.j1, op.j2)
+    }
+}
+
+/// `op` as a tuple, with `OpCode::one_based` ranges if `one_based`
+fn opcode_tuple(op: &OpCode, one_based: bool) -> OpCodeTuple {
+    if !one_based {
+        return op.into();
+    }
+    let (i1, i2, j1, j2) = op.one_based();
+    (op.tag.as_str().to_string(), i1, i2, j1, j2)
+}
+
+impl TryFrom<OpCodeTuple> for OpCode {
+    type Error = PyErr;
+
+    fn try_from((tag, i1, i2, j1, j2): OpCodeTuple) -> PyResult<Self> {
+        let tag = OpTag::parse(&tag)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown opcode tag: {:?}", tag)))?;
+        Ok(OpCode { tag, i1, i2, j1, j2 })
+    }
+}
+
+/// Lines passed as any iterable of `str`, e.g. a generator or a file
+/// object. Both sides of a diff are collected into a `Vec` up front: `b` to
+/// index it, and `a` because matching revisits its ranges.
+struct Lines(Vec<String>);
+
+impl<'source> FromPyObject<'source> for Lines {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if ob.is_instance_of::<PyList>() || ob.is_instance_of::<PyTuple>() {
+            return Ok(Lines(ob.extract()?));
+        }
+        if ob.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err("expected an iterable of lines, not a str"));
+        }
+        ob.iter()?.map(|line| line?.extract()).collect::<PyResult<_>>().map(Lines)
+    }
+}
+
+/// An iterator over the lines of `ob`, rejecting a `str` like `Lines`
+fn lines_iter(ob: &PyAny) -> PyResult<&PyIterator> {
+    if ob.is_instance_of::<PyString>() {
+        return Err(PyTypeError::new_err("expected an iterable of lines, not a str"));
+    }
+    ob.iter()
+}
+
+/// Elements of `b` accepted by a Python `isjunk` callable, asking once per
+/// distinct element like CPython's `__chain_b`
+fn junk_from_callable<'a>(isjunk: Option<&PyAny>, b: &'a [String]) -> PyResult<FxHashSet<&'a str>> {
+    let mut junk = FxHashSet::default();
+    let Some(isjunk) = isjunk else {
+        return Ok(junk);
+    };
+    let mut seen: FxHashSet<&str> = FxHashSet::default();
+    for elt in b {
+        if seen.insert(elt.as_str()) && isjunk.call1((elt.as_str(),))?.is_true()? {
+            junk.insert(elt.as_str());
+        }
+    }
+    Ok(junk)
+}
+
+/// Minimum time between two calls of a Python `progress` callback
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Matcher configuration from the keyword arguments shared by the diff
+/// functions
+fn matcher_config<'a>(
+    isjunk: Option<&PyAny>,
+    b: &'a [String],
+    autojunk: bool,
+    symmetric_junk: bool,
+    max_occurrences_per_element: Option<usize>,
+    progress: Option<&PyAny>,
+) -> PyResult<SequenceMatcherBuilder<'a>> {
+    let mut builder = SequenceMatcherBuilder::new()
+        .junk(junk_from_callable(isjunk, b)?)
+        .autojunk(autojunk)
+        .symmetric_junk(symmetric_junk);
+    if let Some(progress) = progress {
+        let progress: PyObject = progress.into();
+        builder = builder.progress(PROGRESS_INTERVAL, move |fraction| {
+            Python::with_gil(|py| {
+                if let Err(err) = progress.call1(py, (fraction,)) {
+                    err.write_unraisable(py, Some(progress.as_ref(py)));
+                }
+            })
+        });
+    }
+    Ok(match max_occurrences_per_element {
+        Some(0) => return Err(PyValueError::new_err("max_occurrences_per_element must be positive")),
+        Some(max) => builder.max_occurrences_per_element(max),
+        None => builder,
+    })
+}
+
+/// `core::AnchorScoring::GapPenalty` with `gap_penalty`, if given
+fn anchor_scoring(gap_penalty: Option<f64>) -> PyResult<core::AnchorScoring> {
+    let scoring = gap_penalty.map_or(core::AnchorScoring::Longest, core::AnchorScoring::GapPenalty);
+    scoring.validate()?;
+    Ok(scoring)
+}
+
+/// The lines `isjunk` is called on: `b`, or its keys when lines are
+/// matched by `normalizer`'s keys
+fn junk_lines<'a>(b: &'a [String], normalizer: Option<&core::Normalizer>, isjunk: Option<&PyAny>) -> Cow<'a, [String]> {
+    match normalizer {
+        Some(normalizer) if isjunk.is_some() => Cow::Owned(normalizer.keys(b)),
+        _ => Cow::Borrowed(b),
+    }
+}
+
+/// The normalizer of the transforms named in `normalize`, after a
+/// `Dedent` when `dedent` is set
+fn normalizer(normalize: Option<Vec<String>>, dedent: bool) -> PyResult<Option<core::Normalizer>> {
+    let mut normalizer = normalize.map(|names| core::Normalizer::from_names(&names)).transpose()?;
+    if dedent {
+        normalizer.get_or_insert_with(core::Normalizer::default).transforms.insert(0, core::Transform::Dedent);
+    }
+    Ok(normalizer)
+}
+
+/// `name` removed from the `**options` of a diff function and extracted,
+/// `default` when it wasn't given. Extraction errors name the argument like
+/// PyO3's own.
+fn take_option<'py, T: FromPyObject<'py>>(options: Option<&'py PyDict>, name: &str, default: T) -> PyResult<T> {
+    let Some(value) = options.map(|options| options.get_item(name)).transpose()?.flatten() else {
+        return Ok(default);
+    };
+    options.into_iter().try_for_each(|options| options.del_item(name))?;
+    value.extract().map_err(|err| {
+        if err.is_instance_of::<PyTypeError>(value.py()) {
+            PyTypeError::new_err(format!("argument '{}': {}", name, err.value(value.py())))
+        } else {
+            err
+        }
+    })
+}
+
+/// The keyword arguments the unified diff functions share beyond CPython's,
+/// taken from their `**options` with their defaults. Each option is named
+/// here and mapped onto `UnifiedDiffOptions` in `unified`, and nowhere else.
+struct DiffOptions<'py> {
+    brace_scope_context: Option<usize>,
+    window_size: Option<usize>,
+    isjunk: Option<&'py PyAny>,
+    symmetric_junk: bool,
+    compact: bool,
+    autojunk: bool,
+    validate: bool,
+    min_ratio: Option<f64>,
+    adaptive_context: Option<(usize, usize)>,
+    max_occurrences_per_element: Option<usize>,
+    comment_prefixes: Option<Vec<String>>,
+    block_comment: Option<(String, String)>,
+    ignore_comment_changes: bool,
+    progress: Option<&'py PyAny>,
+    max_line_width: Option<usize>,
+    numeric_tolerance: Option<(f64, f64)>,
+    hunk_checksums: bool,
+    unique_anchors: bool,
+    embed_base: bool,
+    /// From the `normalize` and `dedent` arguments
+    normalizer: Option<core::Normalizer>,
+    show_line_numbers: bool,
+    gap_penalty: Option<f64>,
+    reverse_hunks: bool,
+    max_output_bytes: Option<usize>,
+    min_hunk_lines: Option<usize>,
+    elide_equal_over: Option<usize>,
+    neighbor_tiebreak: bool,
+    auto_context: Option<usize>,
+    strip_common_affixes: bool,
+}
+
+impl<'py> DiffOptions<'py> {
+    /// Parse the `**options` of `function`, rejecting unknown keywords with
+    /// the `TypeError` Python raises for them
+    fn extract(function: &str, options: Option<&'py PyDict>) -> PyResult<Self> {
+        let normalize = take_option(options, "normalize", None)?;
+        let dedent = take_option(options, "dedent", false)?;
+        let parsed = Self {
+            brace_scope_context: take_option(options, "brace_scope_context", None)?,
+            window_size: take_option(options, "window_size", None)?,
+            isjunk: take_option(options, "isjunk", None)?,
+            symmetric_junk: take_option(options, "symmetric_junk", false)?,
+            compact: take_option(options, "compact", false)?,
+            autojunk: take_option(options, "autojunk", true)?,
+            validate: take_option(options, "validate", false)?,
+            min_ratio: take_option(options, "min_ratio", None)?,
+            adaptive_context: take_option(options, "adaptive_context", None)?,
+            max_occurrences_per_element: take_option(options, "max_occurrences_per_element", None)?,
+            comment_prefixes: take_option(options, "comment_prefixes", None)?,
+            block_comment: take_option(options, "block_comment", None)?,
+            ignore_comment_changes: take_option(options, "ignore_comment_changes", false)?,
+            progress: take_option(options, "progress", None)?,
+            max_line_width: take_option(options, "max_line_width", None)?,
+            numeric_tolerance: take_option(options, "numeric_tolerance", None)?,
+            hunk_checksums: take_option(options, "hunk_checksums", false)?,
+            unique_anchors: take_option(options, "unique_anchors", false)?,
+            embed_base: take_option(options, "embed_base", false)?,
+            normalizer: normalizer(normalize, dedent)?,
+            show_line_numbers: take_option(options, "show_line_numbers", false)?,
+            gap_penalty: take_option(options, "gap_penalty", None)?,
+            reverse_hunks: take_option(options, "reverse_hunks", false)?,
+            max_output_bytes: take_option(options, "max_output_bytes", None)?,
+            min_hunk_lines: take_option(options, "min_hunk_lines", None)?,
+            elide_equal_over: take_option(options, "elide_equal_over", None)?,
+            neighbor_tiebreak: take_option(options, "neighbor_tiebreak", false)?,
+            auto_context: take_option(options, "auto_context", None)?,
+            strip_common_affixes: take_option(options, "strip_common_affixes", false)?,
+        };
+        if let Some(name) = options.and_then(|options| options.keys().iter().next()) {
+            return Err(PyTypeError::new_err(format!(
+                "{}() got an unexpected keyword argument '{}'",
+                function, name
+            )));
+        }
+        Ok(parsed)
+    }
+
+    /// `UnifiedDiffOptions` with `n` context lines, calling `isjunk` on
+    /// `junk_lines` (see `junk_lines`)
+    fn unified(self, n: usize, junk_lines: &[String]) -> PyResult<UnifiedDiffOptions<'_>> {
+        let matcher = matcher_config(
+            self.isjunk,
+            junk_lines,
+            self.autojunk,
+            self.symmetric_junk,
+            self.max_occurrences_per_element,
+            self.progress,
+        )?;
+        Ok(UnifiedDiffOptions {
+            n,
+            brace_scope_context: self.brace_scope_context,
+            window_size: self.window_size,
+            matcher: matcher
+                .unique_anchors(self.unique_anchors)
+                .anchor_scoring(anchor_scoring(self.gap_penalty)?)
+                .neighbor_tiebreak(self.neighbor_tiebreak)
+                .strip_common_affixes(self.strip_common_affixes),
+            compact: self.compact,
+            validate: self.validate,
+            min_ratio: self.min_ratio,
+            adaptive_context: self.adaptive_context.map(|(min, max)| core::AdaptiveContext { min, max }),
+            comments: comment_syntax(self.comment_prefixes, self.block_comment, self.ignore_comment_changes),
+            max_line_width: self.max_line_width,
+            numeric_tolerance: self.numeric_tolerance.map(|(abs, rel)| core::NumericTolerance { abs, rel }),
+            hunk_checksums: self.hunk_checksums,
+            embed_base: self.embed_base,
+            normalizer: self.normalizer,
+            show_line_numbers: self.show_line_numbers,
+            reverse_hunks: self.reverse_hunks,
+            max_output_bytes: self.max_output_bytes,
+            min_hunk_lines: self.min_hunk_lines,
+            elide_equal_over: self.elide_equal_over,
+            auto_context: self.auto_context,
+        })
+    }
+}
+
+/// `core::track_final_newline` for lists of lines that keep their endings,
+/// e.g. from `readlines()`
+fn track_final_newlines(a: &mut Vec<String>, b: &mut Vec<String>) {
+    for lines in [a, b] {
+        let ends_with_newline = lines.last().is_some_and(|line| core::ends_with_newline(line));
+        core::track_final_newline(lines, ends_with_newline);
+    }
+}
+
+/// `core::CommentSyntax` from the comment keyword arguments, `None` when
+/// neither comment prefixes nor block delimiters are given
+fn comment_syntax(
+    prefixes: Option<Vec<String>>,
+    block: Option<(String, String)>,
+    ignore_changes: bool,
+) -> Option<core::CommentSyntax> {
+    if prefixes.is_none() && block.is_none() {
+        return None;
+    }
+    Some(core::CommentSyntax {
+        prefixes: prefixes.unwrap_or_default(),
+        block,
+        ignore_changes,
+    })
+}
+
+/// Lines of `text` like `str.splitlines(keepends)`, see `core::split_lines`
+#[pyfunction]
+#[pyo3(signature = (text, keepends=true))]
+fn split_lines(text: &str, keepends: bool) -> Vec<String> {
+    core::split_lines(text, keepends)
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, fromfile="", tofile="", fromfiledate="", tofiledate="", n=3, lineterm="\n", keepends=false, track_final_newline=false, **options))]
+fn unified_diff_str(
+    a: String,
+    b: String,
+    fromfile: &str,
+    tofile: &str,
+    fromfiledate: &str,
+    tofiledate: &str,
+    n: usize,
+    lineterm: &str,
+    keepends: bool,
+    track_final_newline: bool,
+    options: Option<&PyDict>,
+) -> PyResult<Vec<String>> {
+    // Split the strings into lines
+    let mut a_lines = core::split_lines(&a, keepends);
+    let mut b_lines = core::split_lines(&b, keepends);
+    if track_final_newline {
+        core::track_final_newline(&mut a_lines, core::ends_with_newline(&a));
+        core::track_final_newline(&mut b_lines, core::ends_with_newline(&b));
+    }
+    let options = DiffOptions::extract("unified_diff_str", options)?;
+    let junk_lines = junk_lines(&b_lines, options.normalizer.as_ref(), options.isjunk);
+    let labels = DiffLabels {
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        lineterm,
+    };
+    Ok(core::unified_diff(&a_lines, &b_lines, &labels, &options.unified(n, &junk_lines)?)?)
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, fromfile="", tofile="", fromfiledate="", tofiledate="", n=3, lineterm="\n", track_final_newline=false, **options))]
+fn unified_diff(
+    a: Lines,
+    b: Lines,
+    fromfile: &str,
+    tofile: &str,
+    fromfiledate: &str,
+    tofiledate: &str,
+    n: usize,
+    lineterm: &str,
+    track_final_newline: bool,
+    options: Option<&PyDict>,
+) -> PyResult<Vec<String>> {
+    let (mut a, mut b) = (a.0, b.0);
+    if track_final_newline {
+        track_final_newlines(&mut a, &mut b);
+    }
+    let options = DiffOptions::extract("unified_diff", options)?;
+    let junk_lines = junk_lines(&b, options.normalizer.as_ref(), options.isjunk);
+    let labels = DiffLabels {
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        lineterm,
+    };
+    Ok(core::unified_diff(&a, &b, &labels, &options.unified(n, &junk_lines)?)?)
+}
+
+/// Unified diff of lists of `bytes` lines, with `bytes` labels and output,
+/// see `core::unified_diff_bytes`
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, fromfile=&b""[..], tofile=&b""[..], fromfiledate=&b""[..], tofiledate=&b""[..], n=3, lineterm=&b"\n"[..], text_only=false, binary_threshold=core::DEFAULT_BINARY_THRESHOLD))]
+fn unified_diff_bytes<'py>(
+    py: Python<'py>,
+    a: Vec<&[u8]>,
+    b: Vec<&[u8]>,
+    fromfile: &[u8],
+    tofile: &[u8],
+    fromfiledate: &[u8],
+    tofiledate: &[u8],
+    n: usize,
+    lineterm: &[u8],
+    text_only: bool,
+    binary_threshold: f64,
+) -> PyResult<Vec<&'py PyBytes>> {
+    let labels = core::ByteDiffLabels {
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        lineterm,
+    };
+    let binary_threshold = (!text_only).then_some(binary_threshold);
+    let diff = core::unified_diff_bytes(&a, &b, &labels, n, binary_threshold)?;
+    Ok(diff.iter().map(|line| PyBytes::new(py, line)).collect())
+}
+
+/// Context diff of `a` and `b` like `difflib.context_diff`, with each of
+/// the `!`, `-`, `+` and space line markers replaceable by another single
+/// character
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, fromfile="", tofile="", fromfiledate="", tofiledate="", n=3, lineterm="\n", change_marker='!', delete_marker='-', insert_marker='+', context_marker=' '))]
+fn context_diff(
+    a: Vec<String>,
+    b: Vec<String>,
+    fromfile: &str,
+    tofile: &str,
+    fromfiledate: &str,
+    tofiledate: &str,
+    n: usize,
+    lineterm: &str,
+    change_marker: char,
+    delete_marker: char,
+    insert_marker: char,
+    context_marker: char,
+) -> PyResult<Vec<String>> {
+    let labels = DiffLabels {
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        lineterm,
+    };
+    let markers = core::ContextMarkers {
+        change: change_marker,
+        delete: delete_marker,
+        insert: insert_marker,
+        context: context_marker,
+    };
+    Ok(core::context_diff(&a, &b, &labels, n, &markers)?)
+}
+
+/// Unified diff of two texts as one string, see `core::diff_str`
+#[pyfunction]
+#[pyo3(signature = (a, b, fromfile="", tofile="", n=3))]
+fn diff_str(a: &str, b: &str, fromfile: &str, tofile: &str, n: usize) -> String {
+    core::diff_str(a, b, fromfile, tofile, n)
+}
+
+/// `unified_diff` of only `a[a_range[0]:a_range[1]]` and
+/// `b[b_range[0]:b_range[1]]`, with hunk headers numbered as in the whole
+/// files
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, a_range, b_range, fromfile="", tofile="", fromfiledate="", tofiledate="", n=3, lineterm="\n", **options))]
+fn unified_diff_range(
+    a: Vec<String>,
+    b: Vec<String>,
+    a_range: (usize, usize),
+    b_range: (usize, usize),
+    fromfile: &str,
+    tofile: &str,
+    fromfiledate: &str,
+    tofiledate: &str,
+    n: usize,
+    lineterm: &str,
+    options: Option<&PyDict>,
+) -> PyResult<Vec<String>> {
+    let options = DiffOptions::extract("unified_diff_range", options)?;
+    let junk_lines = junk_lines(&b, options.normalizer.as_ref(), options.isjunk);
+    let options = options.unified(n, &junk_lines)?;
+    let labels = DiffLabels {
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        lineterm,
+    };
+    Ok(core::unified_diff_range(&a, &b, a_range.0..a_range.1, b_range.0..b_range.1, &labels, &options)?)
+}
+
+/// `unified_diff` plus its `(added, removed, hunks)` counts, from a single
+/// computation of the grouped opcodes
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, fromfile="", tofile="", fromfiledate="", tofiledate="", n=3, lineterm="\n", track_final_newline=false, **options))]
+fn unified_diff_with_stats(
+    a: Lines,
+    b: Lines,
+    fromfile: &str,
+    tofile: &str,
+    fromfiledate: &str,
+    tofiledate: &str,
+    n: usize,
+    lineterm: &str,
+    track_final_newline: bool,
+    options: Option<&PyDict>,
+) -> PyResult<(Vec<String>, core::DiffStats)> {
+    let (mut a, mut b) = (a.0, b.0);
+    if track_final_newline {
+        track_final_newlines(&mut a, &mut b);
+    }
+    let options = DiffOptions::extract("unified_diff_with_stats", options)?;
+    let junk_lines = junk_lines(&b, options.normalizer.as_ref(), options.isjunk);
+    let labels = DiffLabels {
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        lineterm,
+    };
+    Ok(core::unified_diff_with_stats(&a, &b, &labels, &options.unified(n, &junk_lines)?)?)
+}
+
+/// `unified_diff` as plain lines and as lines colored for a terminal,
+/// from a single computation of the grouped opcodes
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, fromfile="", tofile="", fromfiledate="", tofiledate="", n=3, lineterm="\n"))]
+fn unified_diff_dual(
+    a: Lines,
+    b: Lines,
+    fromfile: &str,
+    tofile: &str,
+    fromfiledate: &str,
+    tofiledate: &str,
+    n: usize,
+    lineterm: &str,
+) -> PyResult<(Vec<String>, Vec<String>)> {
+    let (a, b) = (a.0, b.0);
+    let options = UnifiedDiffOptions {
+        n,
+        ..UnifiedDiffOptions::default()
+    };
+    let labels = DiffLabels {
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        lineterm,
+    };
+    Ok(core::unified_diff_dual(&a, &b, &labels, &options)?)
+}
+
+/// The lines of a file-like object, read by iterating it
+fn read_lines(file: &PyAny) -> PyResult<Vec<String>> {
+    lines_iter(file)?.map(|line| line?.extract()).collect()
+}
+
+/// Unified diff of two open text files, read line by line straight into
+/// Rust without building Python lists. `fb` is read first since its index
+/// is built before matching starts; `fa` is read whole too, as matching
+/// revisits the regions of `a` between matches. With `out`, the diff lines
+/// are written to it one by one and `None` is returned.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (fa, fb, fromfile="", tofile="", fromfiledate="", tofiledate="", n=3, lineterm="\n", out=None))]
+fn unified_diff_readers(
+    fa: &PyAny,
+    fb: &PyAny,
+    fromfile: &str,
+    tofile: &str,
+    fromfiledate: &str,
+    tofiledate: &str,
+    n: usize,
+    lineterm: &str,
+    out: Option<&PyAny>,
+) -> PyResult<Option<Vec<String>>> {
+    let b = read_lines(fb)?;
+    let a = read_lines(fa)?;
+    let labels = DiffLabels {
+        fromfile,
+        tofile,
+        fromfiledate,
+        tofiledate,
+        lineterm,
+    };
+    let options = UnifiedDiffOptions { n, ..UnifiedDiffOptions::default() };
+    let lines = core::unified_diff(&a, &b, &labels, &options)?;
+    let Some(out) = out else {
+        return Ok(Some(lines));
+    };
+    for line in lines {
+        out.call_method1("write", (line,))?;
+    }
+    Ok(None)
+}
+
+/// `matcher` with its matching blocks searched while the GIL is released,
+/// so other Python threads run meanwhile. `isjunk` has been called by
+/// now, and a `progress` callback takes the GIL back for each report.
+fn match_without_gil<'a>(py: Python, matcher: core::SequenceMatcher<'a>) -> core::SequenceMatcher<'a> {
+    py.allow_threads(move || {
+        matcher.get_matching_blocks();
+        matcher
+    })
+}
+
+/// Opcodes turning `a` into `b`, like `SequenceMatcher(isjunk, a, b).get_opcodes()`.
+/// With `one_based`, ranges are 1-based and inclusive instead, see
+/// `OpCode::one_based`.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, isjunk=None, autojunk=true, one_based=false, max_occurrences_per_element=None, progress=None, unique_anchors=false, gap_penalty=None, neighbor_tiebreak=false, strip_common_affixes=false))]
+fn get_opcodes(
+    py: Python,
+    a: Lines,
+    b: Lines,
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+    one_based: bool,
+    max_occurrences_per_element: Option<usize>,
+    progress: Option<&PyAny>,
+    unique_anchors: bool,
+    gap_penalty: Option<f64>,
+    neighbor_tiebreak: bool,
+    strip_common_affixes: bool,
+) -> PyResult<Vec<OpCodeTuple>> {
+    let (a, b) = (a.0, b.0);
+    let matcher = matcher_config(isjunk, &b, autojunk, false, max_occurrences_per_element, progress)?
+        .unique_anchors(unique_anchors)
+        .anchor_scoring(anchor_scoring(gap_penalty)?)
+        .neighbor_tiebreak(neighbor_tiebreak)
+        .strip_common_affixes(strip_common_affixes)
+        .build(&a, &b);
+    let matcher = match_without_gil(py, matcher);
+    Ok(matcher.get_opcodes().iter().map(|op| opcode_tuple(op, one_based)).collect())
+}
+
+/// `get_opcodes` with equal opcodes split into runs of at most
+/// `max_equal_run` lines, see `core::split_equal_runs`, and with `keys` a
+/// list of line keys appended to each tuple, see `core::render_keys`
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, max_equal_run=None, isjunk=None, autojunk=true, one_based=false, keys=false))]
+fn get_opcodes_for_render(
+    py: Python,
+    a: Vec<String>,
+    b: Vec<String>,
+    max_equal_run: Option<usize>,
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+    one_based: bool,
+    keys: bool,
+) -> PyResult<Vec<PyObject>> {
+    let matcher = matcher_config(isjunk, &b, autojunk, false, None, None)?.build(&a, &b);
+    let mut opcodes = matcher.get_opcodes().to_vec();
+    if let Some(max_equal_run) = max_equal_run {
+        opcodes = core::split_equal_runs(&opcodes, max_equal_run)?;
+    }
+    let tuples = opcodes.iter().map(|op| opcode_tuple(op, one_based));
+    if !keys {
+        return Ok(tuples.map(|tuple| tuple.into_py(py)).collect());
+    }
+    let keys = core::render_keys(&a, &b, &opcodes);
+    Ok(tuples
+        .zip(keys)
+        .map(|((tag, i1, i2, j1, j2), keys)| (tag, i1, i2, j1, j2, keys).into_py(py))
+        .collect())
+}
+
+/// Opcodes turning `a` into `b` with lines whose numbers are within
+/// `abs_tol` or `rel_tol` counted as equal, see `core::get_opcodes_numeric`
+#[pyfunction]
+#[pyo3(signature = (a, b, abs_tol=0.0, rel_tol=0.0))]
+fn get_opcodes_numeric(a: Vec<String>, b: Vec<String>, abs_tol: f64, rel_tol: f64) -> PyResult<Vec<OpCodeTuple>> {
+    let tolerance = core::NumericTolerance { abs: abs_tol, rel: rel_tol };
+    tolerance.validate()?;
+    Ok(core::get_opcodes_numeric(&a, &b, &tolerance).iter().map(|op| opcode_tuple(op, false)).collect())
+}
+
+/// Opcodes turning `a` into `b` with `a[i]` and `b[j]` equal when
+/// `eq(i, a[i], j, b[j])` is true, see `core::get_opcodes_with`. Warns with
+/// a `RuntimeWarning` when there are enough pairs of lines to be slow. The
+/// first exception raised by `eq` is re-raised once matching ends.
+#[pyfunction]
+fn get_opcodes_where(py: Python, a: Vec<String>, b: Vec<String>, eq: &PyAny) -> PyResult<Vec<OpCodeTuple>> {
+    if a.len().saturating_mul(b.len()) > core::PREDICATE_WARN_PAIRS {
+        let message = format!(
+            "get_opcodes_where compares all {} x {} pairs of lines, which may be slow",
+            a.len(),
+            b.len()
+        );
+        PyErr::warn(py, py.get_type::<PyRuntimeWarning>(), &message, 1)?;
+    }
+    let mut error = None;
+    let opcodes = core::get_opcodes_with(&a, &b, |i, a_line, j, b_line| {
+        if error.is_some() {
+            return false;
+        }
+        match eq.call1((i, a_line, j, b_line)).and_then(PyAny::is_true) {
+            Ok(equal) => equal,
+            Err(err) => {
+                error = Some(err);
+                false
+            }
+        }
+    });
+    match error {
+        Some(err) => Err(err),
+        None => Ok(opcodes.iter().map(|op| opcode_tuple(op, false)).collect()),
+    }
+}
+
+/// Hunks of opcodes with up to `n` lines of context, like
+/// `SequenceMatcher(isjunk, a, b).get_grouped_opcodes(n)`, with the same
+/// `one_based` option as `get_opcodes`
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (a, b, n=3, isjunk=None, autojunk=true, one_based=false, max_occurrences_per_element=None, progress=None, unique_anchors=false, gap_penalty=None, neighbor_tiebreak=false, strip_common_affixes=false))]
+fn get_grouped_opcodes(
+    py: Python,
+    a: Lines,
+    b: Lines,
+    n: usize,
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+    one_based: bool,
+    max_occurrences_per_element: Option<usize>,
+    progress: Option<&PyAny>,
+    unique_anchors: bool,
+    gap_penalty: Option<f64>,
+    neighbor_tiebreak: bool,
+    strip_common_affixes: bool,
+) -> PyResult<Vec<Vec<OpCodeTuple>>> {
+    let (a, b) = (a.0, b.0);
+    let matcher = matcher_config(isjunk, &b, autojunk, false, max_occurrences_per_element, progress)?
+        .unique_anchors(unique_anchors)
+        .anchor_scoring(anchor_scoring(gap_penalty)?)
+        .neighbor_tiebreak(neighbor_tiebreak)
+        .strip_common_affixes(strip_common_affixes)
+        .build(&a, &b);
+    let matcher = match_without_gil(py, matcher);
+    Ok(matcher
+        .get_grouped_opcodes(n)
+        .iter()
+        .map(|group| group.iter().map(|op| opcode_tuple(op, one_based)).collect())
+        .collect())
+}
+
+/// `blocks`, which end in the sentinel, without it unless
+/// `include_sentinel`
+fn matching_blocks_list(blocks: &[(usize, usize, usize)], include_sentinel: bool) -> Vec<(usize, usize, usize)> {
+    let end = blocks.len() - usize::from(!include_sentinel && !blocks.is_empty());
+    blocks[..end].to_vec()
+}
+
+/// Matching blocks of `a` and `b`, like
+/// `SequenceMatcher(isjunk, a, b).get_matching_blocks()`, without the
+/// final sentinel unless `include_sentinel`
+#[pyfunction]
+#[pyo3(signature = (a, b, isjunk=None, autojunk=true, include_sentinel=true))]
+fn get_matching_blocks(
+    py: Python,
+    a: Lines,
+    b: Lines,
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+    include_sentinel: bool,
+) -> PyResult<Vec<(usize, usize, usize)>> {
+    let (a, b) = (a.0, b.0);
+    let matcher = matcher_config(isjunk, &b, autojunk, false, None, None)?.build(&a, &b);
+    let matcher = match_without_gil(py, matcher);
+    Ok(matching_blocks_list(matcher.get_matching_blocks(), include_sentinel))
+}
+
+/// Lines of each matching block of `a` and `b`, without the sentinel, see
+/// `SequenceMatcher::matching_slices`
+#[pyfunction]
+#[pyo3(signature = (a, b, isjunk=None, autojunk=true))]
+fn matching_slices(a: Vec<String>, b: Vec<String>, isjunk: Option<&PyAny>, autojunk: bool) -> PyResult<Vec<Vec<String>>> {
+    let matcher = matcher_config(isjunk, &b, autojunk, false, None, None)?.build(&a, &b);
+    Ok(matcher.matching_slices().into_iter().map(<[String]>::to_vec).collect())
+}
+
+/// Python-facing `core::AnnotatedOpCode`, `(tag, i1, i2, j1, j2, indent_only)`
+type AnnotatedOpCodeTuple = (String, usize, usize, usize, usize, bool);
+
+impl From<&core::AnnotatedOpCode> for AnnotatedOpCodeTuple {
+    fn from(annotated: &core::AnnotatedOpCode) -> Self {
+        let (tag, i1, i2, j1, j2) = OpCodeTuple::from(&annotated.op);
+        (tag, i1, i2, j1, j2, annotated.indent_only)
+    }
+}
+
+/// Hunks like `get_grouped_opcodes` with an `indent_only` flag appended to
+/// each opcode tuple, and an `eol_only` one after it with `line_endings`,
+/// see `core::annotate_opcodes`
+#[pyfunction]
+#[pyo3(signature = (a, b, n=3, line_endings=false))]
+fn annotated_hunks(py: Python, a: Vec<String>, b: Vec<String>, n: usize, line_endings: bool) -> Vec<Vec<PyObject>> {
+    let tuple = |annotated: &core::AnnotatedOpCode| {
+        let (tag, i1, i2, j1, j2, indent_only) = AnnotatedOpCodeTuple::from(annotated);
+        if line_endings {
+            (tag, i1, i2, j1, j2, indent_only, annotated.eol_only).into_py(py)
+        } else {
+            (tag, i1, i2, j1, j2, indent_only).into_py(py)
+        }
+    };
+    core::annotated_hunks(&a, &b, n, line_endings)
+        .iter()
+        .map(|group| group.iter().map(tuple).collect())
+        .collect()
+}
+
+/// Line break of each line, `"lf"`, `"crlf"`, `"cr"`, `"other"` or
+/// `"none"`, see `core::line_ending_report`
+#[pyfunction]
+fn line_ending_report(lines: Vec<String>) -> Vec<&'static str> {
+    core::line_ending_report(&lines).into_iter().map(core::LineEnding::as_str).collect()
+}
+
+/// Python-facing wrapper of `invert_opcodes`
+#[pyfunction]
+fn invert_opcodes(opcodes: Vec<OpCodeTuple>) -> PyResult<Vec<OpCodeTuple>> {
+    let opcodes = opcodes
+        .into_iter()
+        .map(OpCode::try_from)
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(core::invert_opcodes(&opcodes).iter().map(OpCodeTuple::from).collect())
+}
+
+/// Matching blocks of `opcodes`, sentinel included, see
+/// `core::opcodes_to_matching_blocks`
+#[pyfunction]
+fn opcodes_to_matching_blocks(opcodes: Vec<OpCodeTuple>) -> PyResult<Vec<(usize, usize, usize)>> {
+    let opcodes = opcodes
+        .into_iter()
+        .map(OpCode::try_from)
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(core::opcodes_to_matching_blocks(&opcodes))
+}
+
+/// RFC 6902 JSON Patch turning the line array `a` into `b`, see
+/// `core::json_patch`
+#[pyfunction]
+fn json_patch(a: Vec<String>, b: Vec<String>) -> String {
+    core::json_patch(&a, &b)
+}
+
+/// `values` as consecutive little-endian `u64`s
+fn le_u64_bytes(py: Python, values: &[u64]) -> PyObject {
+    let bytes: Vec<u8> = values.iter().flat_map(|value| value.to_le_bytes()).collect();
+    PyBytes::new(py, &bytes).into()
+}
+
+/// Opcodes of `a` -> `b` as five `bytes` columns, see `core::opcode_arrays`:
+/// the tags as one byte each, then `i1`, `i2`, `j1` and `j2` as
+/// little-endian `u64`s
+#[pyfunction]
+#[pyo3(signature = (a, b, isjunk=None, autojunk=true))]
+fn get_opcodes_arrays(
+    py: Python,
+    a: Lines,
+    b: Lines,
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+) -> PyResult<(PyObject, PyObject, PyObject, PyObject, PyObject)> {
+    let (a, b) = (a.0, b.0);
+    let matcher = matcher_config(isjunk, &b, autojunk, false, None, None)?.build(&a, &b);
+    let matcher = match_without_gil(py, matcher);
+    let arrays = core::opcode_arrays(matcher.get_opcodes());
+    Ok((
+        PyBytes::new(py, &arrays.tags).into(),
+        le_u64_bytes(py, &arrays.i1),
+        le_u64_bytes(py, &arrays.i2),
+        le_u64_bytes(py, &arrays.j1),
+        le_u64_bytes(py, &arrays.j2),
+    ))
+}
+
+/// Opcodes encoded as compact `bytes`, see `core::serialize_opcodes`
+#[pyfunction]
+fn serialize_opcodes(py: Python, opcodes: Vec<OpCodeTuple>) -> PyResult<PyObject> {
+    let opcodes = opcodes
+        .into_iter()
+        .map(OpCode::try_from)
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyBytes::new(py, &core::serialize_opcodes(&opcodes)).into())
+}
+
+/// Opcodes decoded from `serialize_opcodes` output
+#[pyfunction]
+fn deserialize_opcodes(data: &[u8]) -> PyResult<Vec<OpCodeTuple>> {
+    Ok(core::deserialize_opcodes(data)?.iter().map(OpCodeTuple::from).collect())
+}
+
+/// Reverse a unified diff so it turns `b` back into `a`, like `patch -R`
+#[pyfunction]
+fn invert_unified_diff(patch: Vec<String>) -> PyResult<Vec<String>> {
+    Ok(core::invert_unified_diff(patch)?)
+}
+
+/// Line edits turning `a` into `b`, see `core::line_edits`
+#[pyfunction]
+fn line_edits(a: Vec<String>, b: Vec<String>) -> Vec<core::LineEdit> {
+    core::line_edits(&a, &b)
+}
+
+/// Python-facing `core::HunkPlacement`, `(offset, fuzz)`
+type HunkPlacementTuple = (isize, usize);
+
+/// `a` with `patch` applied, and the `(offset, fuzz)` each hunk needed,
+/// see `core::apply_unified_diff`
+#[pyfunction]
+#[pyo3(signature = (a, patch, fuzz=0, max_offset=None))]
+fn apply_unified_diff(
+    a: Vec<String>,
+    patch: Vec<String>,
+    fuzz: usize,
+    max_offset: Option<usize>,
+) -> PyResult<(Vec<String>, Vec<HunkPlacementTuple>)> {
+    let (lines, placements) = core::apply_unified_diff(&a, &patch, fuzz, max_offset)?;
+    Ok((lines, placements.iter().map(|placement| (placement.offset, placement.fuzz)).collect()))
+}
+
+/// Whether the unified diff of `a` and `b` applies back to `a` to give
+/// exactly `b`, see `core::verify_diff`. Takes the options of
+/// `unified_diff` beyond its labels.
+#[pyfunction]
+#[pyo3(signature = (a, b, n=3, **options))]
+fn verify_diff(a: Lines, b: Lines, n: usize, options: Option<&PyDict>) -> PyResult<bool> {
+    let (a, b) = (a.0, b.0);
+    let options = DiffOptions::extract("verify_diff", options)?;
+    let junk_lines = junk_lines(&b, options.normalizer.as_ref(), options.isjunk);
+    Ok(core::verify_diff(&a, &b, &options.unified(n, &junk_lines)?)?)
+}
+
+/// `a` with `patch` applied, merged with the base embedded in `patch`
+/// when a hunk fails, and the number of conflicts, see
+/// `core::apply_unified_diff_3way`
+#[pyfunction]
+#[pyo3(signature = (a, patch, fuzz=0, max_offset=None))]
+fn apply_unified_diff_3way(
+    a: Vec<String>,
+    patch: Vec<String>,
+    fuzz: usize,
+    max_offset: Option<usize>,
+) -> PyResult<(Vec<String>, usize)> {
+    Ok(core::apply_unified_diff_3way(&a, &patch, fuzz, max_offset)?)
+}
+
+/// `(start, end)` lines of a base, end exclusive
+type LineRange = (usize, usize);
+
+/// `base` with both patches applied and the `(start, end)` range of
+/// `base` each conflict replaces, see `core::merge_patches`
+#[pyfunction]
+fn merge_patches(
+    base: Vec<String>,
+    patch1: Vec<String>,
+    patch2: Vec<String>,
+) -> PyResult<(Vec<String>, Vec<LineRange>)> {
+    let (merged, conflicts) = core::merge_patches(&base, &patch1, &patch2)?;
+    Ok((merged, conflicts.into_iter().map(|range| (range.start, range.end)).collect()))
+}
+
+/// Where `a` and `b` first differ, see `core::first_difference`. The
+/// iterables are read in step and compared line by line, so only their
+/// common prefix is read and converted, not the whole of either.
+#[pyfunction]
+fn first_difference(a: &PyAny, b: &PyAny) -> PyResult<Option<(usize, usize)>> {
+    let (mut a, mut b) = (lines_iter(a)?, lines_iter(b)?);
+    let mut index = 0;
+    loop {
+        match (a.next().transpose()?, b.next().transpose()?) {
+            (None, None) => return Ok(None),
+            (Some(x), Some(y)) if x.extract::<&str>()? == y.extract::<&str>()? => index += 1,
+            _ => return Ok(Some((index, index))),
+        }
+    }
+}
+
+/// Whether `a` -> `b` changes at most `max_diff_lines` lines, see
+/// `core::differs_by_at_most`
+#[pyfunction]
+fn differs_by_at_most(a: Vec<String>, b: Vec<String>, max_diff_lines: usize) -> bool {
+    core::differs_by_at_most(&a, &b, max_diff_lines)
+}
+
+/// Local similarity of each window of `window` lines of `b`, see
+/// `core::SequenceMatcher::similarity_profile`
+#[pyfunction]
+#[pyo3(signature = (a, b, window, isjunk=None, autojunk=true))]
+fn similarity_profile(
+    py: Python,
+    a: Lines,
+    b: Lines,
+    window: usize,
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+) -> PyResult<Vec<f64>> {
+    let (a, b) = (a.0, b.0);
+    let matcher = matcher_config(isjunk, &b, autojunk, false, None, None)?.build(&a, &b);
+    let matcher = match_without_gil(py, matcher);
+    Ok(matcher.similarity_profile(window)?)
+}
+
+/// Similarity of `a[a_range[0]:a_range[1]]` and `b[b_range[0]:b_range[1]]`,
+/// see `core::SequenceMatcher::ratio_range`
+#[pyfunction]
+#[pyo3(signature = (a, b, a_range, b_range, isjunk=None, autojunk=true))]
+fn ratio_range(
+    a: Vec<String>,
+    b: Vec<String>,
+    a_range: (usize, usize),
+    b_range: (usize, usize),
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+) -> PyResult<f64> {
+    let matcher = matcher_config(isjunk, &b, autojunk, false, None, None)?.build(&a, &b);
+    Ok(matcher.ratio_range(a_range.0..a_range.1, b_range.0..b_range.1)?)
+}
+
+/// The similarity ratio of `a` and `b` if it is at least `cutoff`, else
+/// `None`, see `core::SequenceMatcher::ratio_streaming`
+#[pyfunction]
+#[pyo3(signature = (a, b, cutoff, isjunk=None, autojunk=true))]
+fn ratio_streaming(
+    a: Vec<String>,
+    b: Vec<String>,
+    cutoff: f64,
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+) -> PyResult<Option<f64>> {
+    if !(0.0..=1.0).contains(&cutoff) {
+        return Err(PyValueError::new_err(format!("cutoff must be in [0.0, 1.0]: {}", cutoff)));
+    }
+    let matcher = matcher_config(isjunk, &b, autojunk, false, None, None)?.build(&a, &b);
+    Ok(matcher.ratio_streaming(cutoff))
+}
+
+/// Rough cost of diffing `a` -> `b`, see `core::Index::estimate_cost`
+#[pyfunction]
+#[pyo3(signature = (a, b, isjunk=None, autojunk=true))]
+fn estimate_cost(a: Vec<String>, b: Vec<String>, isjunk: Option<&PyAny>, autojunk: bool) -> PyResult<u64> {
+    let index = matcher_config(isjunk, &b, autojunk, false, None, None)?.build_index(&b);
+    Ok(index.estimate_cost(&a))
+}
+
+/// `b`'s element-to-positions index after junk and popularity pruning, see
+/// `core::Index::get_b2j`
+#[pyfunction]
+#[pyo3(signature = (b, isjunk=None, autojunk=true))]
+fn get_b2j(b: Vec<String>, isjunk: Option<&PyAny>, autojunk: bool) -> PyResult<HashMap<String, Vec<usize>>> {
+    Ok(matcher_config(isjunk, &b, autojunk, false, None, None)?.build_index(&b).get_b2j())
+}
+
+/// Only the hunk headers of the unified diff, see `core::hunk_headers`
+#[pyfunction]
+#[pyo3(signature = (a, b, n=3))]
+fn hunk_headers(a: Vec<String>, b: Vec<String>, n: usize) -> Vec<String> {
+    core::hunk_headers(&a, &b, n)
+}
+
+/// One git-style diffstat line, e.g. `src/lib.rs | 12 +++++++-----`
+#[pyfunction]
+#[pyo3(signature = (a, b, filename, max_width=50))]
+fn diffstat_line(a: Vec<String>, b: Vec<String>, filename: &str, max_width: usize) -> String {
+    core::diffstat_line(&a, &b, filename, max_width)
+}
+
+/// `((old_start, old_end), (new_start, new_end))` spanning every change,
+/// `None` when `a == b`, see `core::changed_bounds`
+#[pyfunction]
+fn changed_bounds(a: Vec<String>, b: Vec<String>) -> Option<((usize, usize), (usize, usize))> {
+    core::changed_bounds(&a, &b)
+}
+
+/// Whether each line of `a` was deleted or replaced, see
+/// `core::change_mask_old`
+#[pyfunction]
+fn change_mask_old(a: Vec<String>, b: Vec<String>) -> Vec<bool> {
+    core::change_mask_old(&a, &b)
+}
+
+/// Whether each line of `b` was inserted or replaced, see
+/// `core::change_mask_new`
+#[pyfunction]
+fn change_mask_new(a: Vec<String>, b: Vec<String>) -> Vec<bool> {
+    core::change_mask_new(&a, &b)
+}
+
+/// Inserted and replaced lines of `b` without markers, see
+/// `core::added_lines`
+#[pyfunction]
+fn added_lines(a: Vec<String>, b: Vec<String>) -> Vec<String> {
+    core::added_lines(&a, &b)
+}
+
+/// Deleted and replaced lines of `a` without markers, see
+/// `core::removed_lines`
+#[pyfunction]
+fn removed_lines(a: Vec<String>, b: Vec<String>) -> Vec<String> {
+    core::removed_lines(&a, &b)
+}
+
+/// Stable hash of just the changes between `a` and `b`, see
+/// `core::diff_fingerprint`
+#[pyfunction]
+#[pyo3(signature = (a, b, ignore_position=false))]
+fn diff_fingerprint(a: Vec<String>, b: Vec<String>, ignore_position: bool) -> u64 {
+    core::diff_fingerprint(&a, &b, ignore_position)
+}
+
+/// 1-based inclusive ranges of `a` unchanged in `b`, see
+/// `core::unchanged_ranges_old`
+#[pyfunction]
+fn unchanged_ranges_old(a: Vec<String>, b: Vec<String>) -> Vec<(usize, usize)> {
+    core::unchanged_ranges_old(&a, &b)
+}
+
+/// 1-based inclusive ranges of `b` already in `a`, see
+/// `core::unchanged_ranges_new`
+#[pyfunction]
+fn unchanged_ranges_new(a: Vec<String>, b: Vec<String>) -> Vec<(usize, usize)> {
+    core::unchanged_ranges_new(&a, &b)
+}
+
+/// Changed hunks per line of input, see `core::hunk_density`
+#[pyfunction]
+#[pyo3(signature = (a, b, n=3))]
+fn hunk_density(a: Vec<String>, b: Vec<String>, n: usize) -> f64 {
+    core::hunk_density(&a, &b, n)
+}
+
+/// Tab-separated `op, old_lineno, new_lineno, content` rows, see
+/// `core::tsv_diff`
+#[pyfunction]
+fn tsv_diff(a: Vec<String>, b: Vec<String>) -> Vec<String> {
+    core::tsv_diff(&a, &b)
+}
+
+/// Character-level diff of two lines with changed runs wrapped in markers,
+/// e.g. `foo[-bar-]{+baz+}qux`
+#[pyfunction]
+#[pyo3(signature = (a, b, delete=("[-", "-]"), insert=("{+", "+}"), isolate_rtl=false))]
+fn inline_char_diff(a: &str, b: &str, delete: (&str, &str), insert: (&str, &str), isolate_rtl: bool) -> String {
+    core::inline_char_diff_with(a, b, &InlineMarkers { delete, insert, isolate_rtl })
+}
+
+/// Word-level diff of two texts in hunks with up to `n` words of context,
+/// see `core::word_unified_diff`
+#[pyfunction]
+#[pyo3(signature = (a, b, n=3, isolate_rtl=false))]
+fn word_unified_diff(a: &str, b: &str, n: usize, isolate_rtl: bool) -> Vec<String> {
+    let markers = InlineMarkers { isolate_rtl, ..InlineMarkers::default() };
+    core::word_unified_diff_with(a, b, n, &markers)
+}
+
+/// Character-level opcodes of `a` -> `b` indexed in UTF-16 code units, see
+/// `core::char_opcodes_utf16`
+#[pyfunction]
+fn char_opcodes_utf16(a: &str, b: &str) -> Vec<OpCodeTuple> {
+    core::char_opcodes_utf16(a, b).iter().map(OpCodeTuple::from).collect()
+}
+
+/// Character similarity favoring matches near the start, see
+/// `core::prefix_weighted_ratio`
+#[pyfunction]
+#[pyo3(signature = (a, b, decay=core::DEFAULT_PREFIX_DECAY))]
+fn prefix_weighted_ratio(a: &str, b: &str, decay: f64) -> PyResult<f64> {
+    Ok(core::prefix_weighted_ratio(a, b, decay)?)
+}
+
+/// Length of `text` in UTF-16 code units
+#[pyfunction]
+fn utf16_len(text: &str) -> usize {
+    core::utf16_len(text)
+}
+
+/// Call `callback(tag, i1, i2, j1, j2)` for each opcode of `a` -> `b` as it
+/// is produced, without building the opcode list. Returning `False` (or any
+/// falsy value other than `None`) stops the walk. Returns whether every
+/// opcode was visited.
+#[pyfunction]
+#[pyo3(signature = (a, b, callback, isjunk=None, autojunk=true))]
+fn walk_opcodes(a: Lines, b: Lines, callback: &PyAny, isjunk: Option<&PyAny>, autojunk: bool) -> PyResult<bool> {
+    let (a, b) = (a.0, b.0);
+    let matcher = matcher_config(isjunk, &b, autojunk, false, None, None)?.build(&a, &b);
+    for op in core::iter_opcodes(matcher.get_matching_blocks()) {
+        let keep_going = callback.call1((op.tag.as_str(), op.i1, op.i2, op.j1, op.j2))?;
+        if !keep_going.is_none() && !keep_going.is_true()? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// `(key, value)` records from a dict or a list of `key=value` lines
+fn records_from(obj: &PyAny) -> PyResult<Vec<(String, String)>> {
+    match obj.downcast::<PyDict>() {
+        Ok(dict) => dict.iter().map(|(key, value)| Ok((key.extract()?, value.extract()?))).collect(),
+        Err(_) => Ok(core::parse_records(&obj.extract::<Vec<String>>()?)),
+    }
+}
+
+/// Added, removed and changed keys between two dicts or lists of
+/// `key=value` lines, see `core::diff_records`
+#[pyfunction]
+fn diff_records<'py>(py: Python<'py>, a: &PyAny, b: &PyAny) -> PyResult<&'py PyDict> {
+    let diff = core::diff_records(records_from(a)?, records_from(b)?);
+    let result = PyDict::new(py);
+    result.set_item("added", diff.added)?;
+    result.set_item("removed", diff.removed)?;
+    result.set_item("changed", diff.changed)?;
+    Ok(result)
+}
+
+/// `(tag, key, old, new, diff)` of a `core::MapChange`, with `None` for the
+/// parts a tag doesn't have
+type MapChangeTuple = (&'static str, String, Option<String>, Option<String>, Option<String>);
+
+/// `(key, value)` pairs from a dict or a list of pairs, in order
+fn pairs_from(obj: &PyAny) -> PyResult<Vec<(String, String)>> {
+    match obj.downcast::<PyDict>() {
+        Ok(dict) => dict.iter().map(|(key, value)| Ok((key.extract()?, value.extract()?))).collect(),
+        Err(_) => obj.extract(),
+    }
+}
+
+/// Per-key changes between two ordered maps, see `core::map_diff`
+#[pyfunction]
+#[pyo3(signature = (a, b, value_context=None))]
+fn map_diff(a: &PyAny, b: &PyAny, value_context: Option<usize>) -> PyResult<Vec<MapChangeTuple>> {
+    let changes = core::map_diff(pairs_from(a)?, pairs_from(b)?, value_context);
+    Ok(changes
+        .into_iter()
+        .map(|change| match change {
+            core::MapChange::Added { key, value } => ("added", key, None, Some(value), None),
+            core::MapChange::Removed { key, value } => ("removed", key, Some(value), None, None),
+            core::MapChange::Changed { key, old, new, diff } => ("changed", key, Some(old), Some(new), diff),
+            core::MapChange::Unchanged { key, value } => ("unchanged", key, Some(value.clone()), Some(value), None),
+        })
+        .collect())
+}
+
+/// Added, removed and changed keys between two sets of `(key, text)`
+/// records, with inline diffs of the changed texts, see `core::keyed_diff`
+#[pyfunction]
+fn keyed_diff<'py>(py: Python<'py>, a: &PyAny, b: &PyAny) -> PyResult<&'py PyDict> {
+    let diff = core::keyed_diff(pairs_from(a)?, pairs_from(b)?);
+    let result = PyDict::new(py);
+    result.set_item("added", diff.added)?;
+    result.set_item("removed", diff.removed)?;
+    result.set_item("changed", diff.changed)?;
+    Ok(result)
+}
+
+/// Unified diffs of the sections that changed, keyed by
+/// `(title, occurrence)`, see `core::sectioned_diff`
+#[pyfunction]
+#[pyo3(signature = (a, b, section_pattern, n=3, lineterm="\n"))]
+fn sectioned_diff<'py>(
+    py: Python<'py>,
+    a: Lines,
+    b: Lines,
+    section_pattern: &str,
+    n: usize,
+    lineterm: &str,
+) -> PyResult<&'py PyDict> {
+    let (a, b) = (a.0, b.0);
+    let options = UnifiedDiffOptions {
+        n,
+        ..UnifiedDiffOptions::default()
+    };
+    let result = PyDict::new(py);
+    for (title, occurrence, diff) in core::sectioned_diff(&a, &b, section_pattern, lineterm, &options)? {
+        result.set_item((title, occurrence), diff)?;
+    }
+    Ok(result)
+}
+
+/// `(index, ratio)` of the candidate most similar to `target`, see
+/// `core::best_base`
+#[pyfunction]
+fn best_base(target: Vec<String>, candidates: Vec<Vec<String>>) -> PyResult<(usize, f64)> {
+    core::best_base(&target, &candidates).ok_or_else(|| PyValueError::new_err("candidates must not be empty"))
+}
+
+/// The `possibilities` most similar to `query` as token multisets, see
+/// `core::get_close_matches_set`, validated like `get_close_matches`
+#[pyfunction]
+#[pyo3(signature = (query, possibilities, n=3, cutoff=0.6))]
+fn get_close_matches_set(
+    query: Vec<String>,
+    possibilities: Vec<Vec<String>>,
+    n: usize,
+    cutoff: f64,
+) -> PyResult<Vec<Vec<String>>> {
+    if n == 0 {
+        return Err(PyValueError::new_err(format!("n must be > 0: {}", n)));
+    }
+    if !(0.0..=1.0).contains(&cutoff) {
+        return Err(PyValueError::new_err(format!("cutoff must be in [0.0, 1.0]: {}", cutoff)));
+    }
+    let best = core::get_close_matches_set(&query, &possibilities, n, cutoff);
+    Ok(best.into_iter().map(|(index, _)| possibilities[index].clone()).collect())
+}
+
+/// Documents indexed for repeated fuzzy queries, see `core::CorpusIndex`
+#[pyclass(name = "CorpusIndex")]
+struct PyCorpusIndex {
+    inner: core::CorpusIndex,
+}
+
+#[pymethods]
+impl PyCorpusIndex {
+    #[new]
+    fn new(docs: Vec<Vec<String>>) -> Self {
+        Self {
+            inner: core::CorpusIndex::build(docs),
+        }
+    }
+
+    /// Up to `k` `(index, ratio)` pairs of documents at least `cutoff`
+    /// similar to `query`, best first, validated like `get_close_matches`
+    #[pyo3(signature = (query, k=3, cutoff=0.6))]
+    fn query(&self, query: Vec<String>, k: usize, cutoff: f64) -> PyResult<Vec<(usize, f64)>> {
+        if k == 0 {
+            return Err(PyValueError::new_err(format!("k must be > 0: {}", k)));
+        }
+        if !(0.0..=1.0).contains(&cutoff) {
+            return Err(PyValueError::new_err(format!("cutoff must be in [0.0, 1.0]: {}", cutoff)));
+        }
+        Ok(self.inner.query(&query, k, cutoff))
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+/// A matcher configuration that keeps the allocations of its searches
+/// between pairs, see `core::MatchBuffers`. Usable as a context manager,
+/// which frees them on exit.
+#[pyclass(name = "SequenceMatcher")]
+struct PySequenceMatcher {
+    isjunk: Option<PyObject>,
+    autojunk: bool,
+    buffers: core::MatchBuffers,
+}
+
+impl PySequenceMatcher {
+    /// `f` of a matcher of `a` and `b` whose matching blocks were searched
+    /// with the pooled buffers
+    fn with_matcher<R>(
+        &mut self,
+        py: Python<'_>,
+        a: &[String],
+        b: &[String],
+        f: impl FnOnce(&core::SequenceMatcher) -> R,
+    ) -> PyResult<R> {
+        let isjunk = self.isjunk.as_ref().map(|isjunk| isjunk.as_ref(py));
+        let matcher = matcher_config(isjunk, b, self.autojunk, false, None, None)?.build(a, b);
+        matcher.get_matching_blocks_with(&mut self.buffers);
+        Ok(f(&matcher))
+    }
+}
+
+#[pymethods]
+impl PySequenceMatcher {
+    #[new]
+    #[pyo3(signature = (isjunk=None, autojunk=true))]
+    fn new(isjunk: Option<PyObject>, autojunk: bool) -> Self {
+        Self {
+            isjunk,
+            autojunk,
+            buffers: core::MatchBuffers::default(),
+        }
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Free the pooled buffers; exceptions propagate
+    fn __exit__(&mut self, _exc_type: &PyAny, _exc_value: &PyAny, _traceback: &PyAny) -> bool {
+        self.buffers = core::MatchBuffers::default();
+        false
+    }
+
+    /// Opcodes turning `a` into `b`, like `get_opcodes`
+    fn compare(&mut self, py: Python<'_>, a: Vec<String>, b: Vec<String>) -> PyResult<Vec<OpCodeTuple>> {
+        self.with_matcher(py, &a, &b, |matcher| matcher.get_opcodes().iter().map(Into::into).collect())
+    }
+
+    /// Similarity of `a` and `b`, like `SequenceMatcher.ratio()`
+    fn ratio(&mut self, py: Python<'_>, a: Vec<String>, b: Vec<String>) -> PyResult<f64> {
+        self.with_matcher(py, &a, &b, |matcher| matcher.ratio())
+    }
+
+    /// Matching blocks of `a` and `b`, like `get_matching_blocks`
+    #[pyo3(signature = (a, b, include_sentinel=true))]
+    fn get_matching_blocks(
+        &mut self,
+        py: Python<'_>,
+        a: Vec<String>,
+        b: Vec<String>,
+        include_sentinel: bool,
+    ) -> PyResult<Vec<(usize, usize, usize)>> {
+        self.with_matcher(py, &a, &b, |matcher| {
+            matching_blocks_list(matcher.get_matching_blocks(), include_sentinel)
+        })
+    }
+}
+
+/// A diff that follows edits to `b`, see `core::IncrementalMatcher`
+#[pyclass(name = "IncrementalMatcher")]
+struct PyIncrementalMatcher {
+    inner: core::IncrementalMatcher,
+}
+
+#[pymethods]
+impl PyIncrementalMatcher {
+    #[new]
+    #[pyo3(signature = (a, b, opcodes=None))]
+    fn new(a: Vec<String>, b: Vec<String>, opcodes: Option<Vec<OpCodeTuple>>) -> PyResult<Self> {
+        let inner = match opcodes {
+            Some(opcodes) => {
+                let opcodes = opcodes.into_iter().map(OpCode::try_from).collect::<PyResult<Vec<_>>>()?;
+                core::IncrementalMatcher::from_opcodes(a, b, &opcodes)?
+            }
+            None => core::IncrementalMatcher::new(a, b),
+        };
+        Ok(Self { inner })
+    }
+
+    #[getter]
+    fn a(&self) -> Vec<String> {
+        self.inner.a().to_vec()
+    }
+
+    #[getter]
+    fn b(&self) -> Vec<String> {
+        self.inner.b().to_vec()
+    }
+
+    /// Replace `b[start:end]` with `lines` and re-diff around the edit
+    fn edit(&mut self, start: usize, end: usize, lines: Vec<String>) -> PyResult<()> {
+        Ok(self.inner.edit(start..end, lines)?)
+    }
+
+    /// Matching blocks of the current diff, with the final sentinel unless
+    /// not `include_sentinel`
+    #[pyo3(signature = (include_sentinel=true))]
+    fn get_matching_blocks(&self, include_sentinel: bool) -> Vec<(usize, usize, usize)> {
+        matching_blocks_list(&self.inner.get_matching_blocks(), include_sentinel)
+    }
+
+    /// Opcodes of the current diff
+    fn get_opcodes(&self) -> Vec<OpCodeTuple> {
+        self.inner.get_opcodes().iter().map(Into::into).collect()
+    }
+}
+
+/// Outcome of a background diff, set once by its thread
+type DiffSlot = (Mutex<Option<Result<Vec<OpCode>, core::Error>>>, Condvar);
+
+import_exception!(concurrent.futures, CancelledError);
+
+/// A diff running on its own thread, see `diff_in_background`
+#[pyclass(name = "DiffHandle")]
+struct PyDiffHandle {
+    cancel: Arc<AtomicBool>,
+    slot: Arc<DiffSlot>,
+}
+
+#[pymethods]
+impl PyDiffHandle {
+    /// Ask the diff to stop; true unless it had already finished
+    fn cancel(&self) -> bool {
+        self.cancel.store(true, Ordering::Relaxed);
+        !self.done()
+    }
+
+    /// Whether the diff stopped early because of `cancel`
+    fn cancelled(&self) -> bool {
+        matches!(*self.slot.0.lock().unwrap(), Some(Err(core::Error::Cancelled)))
+    }
+
+    /// Whether the diff finished or stopped, so `result` won't block
+    fn done(&self) -> bool {
+        self.slot.0.lock().unwrap().is_some()
+    }
+
+    /// Opcodes of the diff, waiting up to `timeout` seconds (forever if
+    /// `None`) with the GIL released. Raises `TimeoutError` if it isn't
+    /// done by then and `concurrent.futures.CancelledError` if it was
+    /// cancelled before finishing.
+    #[pyo3(signature = (timeout=None))]
+    fn result(&self, py: Python, timeout: Option<f64>) -> PyResult<Vec<OpCodeTuple>> {
+        let timeout = match timeout {
+            Some(timeout) if !(timeout >= 0.0 && timeout.is_finite()) => {
+                return Err(PyValueError::new_err(format!("timeout must be finite and not negative: {}", timeout)));
+            }
+            timeout => timeout.map(Duration::from_secs_f64),
+        };
+        let outcome = py.allow_threads(|| {
+            let (lock, done) = &*self.slot;
+            let guard = lock.lock().unwrap();
+            let guard = match timeout {
+                Some(timeout) => done.wait_timeout_while(guard, timeout, |outcome| outcome.is_none()).unwrap().0,
+                None => done.wait_while(guard, |outcome| outcome.is_none()).unwrap(),
+            };
+            guard.clone()
+        });
+        match outcome {
+            None => Err(PyTimeoutError::new_err("diff not done before the timeout")),
+            Some(Err(core::Error::Cancelled)) => Err(CancelledError::new_err("diff cancelled")),
+            Some(outcome) => Ok(outcome?.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+/// Start diffing `a` and `b` on a new thread and return a `DiffHandle` to
+/// wait for or cancel it. `isjunk` is called on this thread, up front;
+/// the matching itself runs without the GIL.
+#[pyfunction]
+#[pyo3(signature = (a, b, isjunk=None, autojunk=true, unique_anchors=false))]
+fn diff_in_background(
+    a: Lines,
+    b: Lines,
+    isjunk: Option<&PyAny>,
+    autojunk: bool,
+    unique_anchors: bool,
+) -> PyResult<PyDiffHandle> {
+    let (a, b) = (a.0, b.0);
+    let junk: Vec<String> = junk_from_callable(isjunk, &b)?.into_iter().map(str::to_string).collect();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let slot: Arc<DiffSlot> = Arc::new((Mutex::new(None), Condvar::new()));
+    let (thread_cancel, thread_slot) = (cancel.clone(), slot.clone());
+    std::thread::spawn(move || {
+        let outcome = SequenceMatcherBuilder::new()
+            .extend_junk(junk.iter().map(String::as_str))
+            .autojunk(autojunk)
+            .unique_anchors(unique_anchors)
+            .cancel(thread_cancel)
+            .build(&a, &b)
+            .try_get_opcodes()
+            .map(<[OpCode]>::to_vec);
+        let (lock, done) = &*thread_slot;
+        *lock.lock().unwrap() = Some(outcome);
+        done.notify_all();
+    });
+    Ok(PyDiffHandle { cancel, slot })
+}
+
+/// Optional cargo features and whether this build has them
+const FEATURES: &[(&str, bool)] = &[
+    ("python", cfg!(feature = "python")),
+    ("simd", cfg!(feature = "simd")),
+    ("bench", cfg!(feature = "bench")),
+];
+
+/// Names of the optional features compiled into this build
+#[pyfunction]
+fn features() -> Vec<&'static str> {
+    FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// `(rust_micros, python_micros, outputs_match)` of `unified_diff` against
+/// Python's `difflib.unified_diff` with default arguments on the same
+/// input. Converting the inputs is left out of both timings.
+#[cfg(feature = "bench")]
+#[pyfunction]
+fn benchmark_against_difflib(py: Python, a: Lines, b: Lines) -> PyResult<(u64, u64, bool)> {
+    use std::time::Instant;
+
+    let (a, b) = (a.0, b.0);
+    let (a_list, b_list) = (PyList::new(py, &a), PyList::new(py, &b));
+    let python_diff = py.import("difflib")?.getattr("unified_diff")?;
+
+    let start = Instant::now();
+    let rust_lines = core::unified_diff(&a, &b, &DiffLabels::default(), &UnifiedDiffOptions::default())?;
+    let rust_micros = start.elapsed().as_micros() as u64;
+
+    let start = Instant::now();
+    let python_lines: Vec<&PyAny> = python_diff.call1((a_list, b_list))?.iter()?.collect::<PyResult<_>>()?;
+    let python_micros = start.elapsed().as_micros() as u64;
+
+    let python_lines = python_lines.into_iter().map(PyAny::extract).collect::<PyResult<Vec<String>>>()?;
+    let outputs_match = python_lines == rust_lines;
+    Ok((rust_micros, python_micros, outputs_match))
+}
+
+#[pymodule]
+fn difflib_rs(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(unified_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_diff_str, m)?)?;
+    m.add_function(wrap_pyfunction!(split_lines, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_str, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_diff_with_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_diff_dual, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_diff_readers, m)?)?;
+    m.add_function(wrap_pyfunction!(context_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_diff_range, m)?)?;
+    m.add_function(wrap_pyfunction!(get_opcodes, m)?)?;
+    m.add_function(wrap_pyfunction!(get_grouped_opcodes, m)?)?;
+    m.add_function(wrap_pyfunction!(get_matching_blocks, m)?)?;
+    m.add_function(wrap_pyfunction!(matching_slices, m)?)?;
+    m.add_function(wrap_pyfunction!(annotated_hunks, m)?)?;
+    m.add_function(wrap_pyfunction!(line_ending_report, m)?)?;
+    m.add_function(wrap_pyfunction!(invert_opcodes, m)?)?;
+    m.add_function(wrap_pyfunction!(opcodes_to_matching_blocks, m)?)?;
+    m.add_function(wrap_pyfunction!(json_patch, m)?)?;
+    m.add_function(wrap_pyfunction!(get_opcodes_arrays, m)?)?;
+    m.add_function(wrap_pyfunction!(serialize_opcodes, m)?)?;
+    m.add_function(wrap_pyfunction!(deserialize_opcodes, m)?)?;
+    m.add_function(wrap_pyfunction!(invert_unified_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(apply_unified_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(line_edits, m)?)?;
+    m.add_function(wrap_pyfunction!(get_opcodes_numeric, m)?)?;
+    m.add_function(wrap_pyfunction!(get_opcodes_where, m)?)?;
+    m.add_function(wrap_pyfunction!(get_opcodes_for_render, m)?)?;
+    m.add_function(wrap_pyfunction!(word_unified_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(hunk_headers, m)?)?;
+    m.add_function(wrap_pyfunction!(differs_by_at_most, m)?)?;
+    m.add_function(wrap_pyfunction!(first_difference, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_diff_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(ratio_range, m)?)?;
+    m.add_function(wrap_pyfunction!(similarity_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(apply_unified_diff_3way, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_patches, m)?)?;
+    m.add_function(wrap_pyfunction!(ratio_streaming, m)?)?;
+    m.add_function(wrap_pyfunction!(estimate_cost, m)?)?;
+    m.add_function(wrap_pyfunction!(get_b2j, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_in_background, m)?)?;
+    m.add_function(wrap_pyfunction!(diffstat_line, m)?)?;
+    m.add_function(wrap_pyfunction!(walk_opcodes, m)?)?;
+    m.add_function(wrap_pyfunction!(inline_char_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(char_opcodes_utf16, m)?)?;
+    m.add_function(wrap_pyfunction!(utf16_len, m)?)?;
+    m.add_function(wrap_pyfunction!(prefix_weighted_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(hunk_density, m)?)?;
+    m.add_function(wrap_pyfunction!(changed_bounds, m)?)?;
+    m.add_function(wrap_pyfunction!(change_mask_old, m)?)?;
+    m.add_function(wrap_pyfunction!(change_mask_new, m)?)?;
+    m.add_function(wrap_pyfunction!(added_lines, m)?)?;
+    m.add_function(wrap_pyfunction!(removed_lines, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_fingerprint, m)?)?;
+    m.add_function(wrap_pyfunction!(unchanged_ranges_old, m)?)?;
+    m.add_function(wrap_pyfunction!(unchanged_ranges_new, m)?)?;
+    m.add_function(wrap_pyfunction!(tsv_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_records, m)?)?;
+    m.add_function(wrap_pyfunction!(map_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(keyed_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(sectioned_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(best_base, m)?)?;
+    m.add_function(wrap_pyfunction!(get_close_matches_set, m)?)?;
+    m.add_function(wrap_pyfunction!(features, m)?)?;
+    #[cfg(feature = "bench")]
+    m.add_function(wrap_pyfunction!(benchmark_against_difflib, m)?)?;
+    m.add_class::<PyCorpusIndex>()?;
+    m.add_class::<PySequenceMatcher>()?;
+    m.add_class::<PyIncrementalMatcher>()?;
+    m.add_class::<PyDiffHandle>()?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+"""Test the adaptive_context option of unified_diff."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_str, unified_diff_with_stats
+
+
+def hunk_headers(lines):
+    return [line for line in lines if line.startswith('@@')]
+
+
+def test_isolated_change_gets_max_context():
+    """Test that a lone change shows max lines of context."""
+    a = [f"line {i}" for i in range(40)]
+    b = a[:20] + ['changed'] + a[21:]
+    result = unified_diff(a, b, lineterm='', adaptive_context=(1, 5))
+    assert result == list(difflib.unified_diff(a, b, lineterm='', n=5))
+
+
+def test_dense_cluster_gets_less_context():
+    """Test that a cluster of changes shrinks its context towards min."""
+    a = [f"line {i}" for i in range(60)]
+    b = list(a)
+    for i in (20, 22, 24, 26):
+        b[i] = f"changed {i}"
+    result = unified_diff(a, b, lineterm='', adaptive_context=(1, 5))
+    # 4 changes: 1 + (5 - 1) // 4 = 2 lines of context
+    assert result == list(difflib.unified_diff(a, b, lineterm='', n=2))
+
+
+def test_clusters_sized_independently():
+    """Test that separate clusters get their own context."""
+    a = [f"line {i}" for i in range(100)]
+    b = list(a)
+    b[10] = 'lone'
+    for i in (60, 61, 63, 65, 67):
+        b[i] = f"busy {i}"
+    result = unified_diff(a, b, lineterm='', adaptive_context=(0, 6))
+    # The lone change gets 6 lines; the busy lines form 4 changes (60 and 61
+    # are one replace), which get 6 // 4 = 1 line
+    assert hunk_headers(result) == ['@@ -5,13 +5,13 @@', '@@ -60,10 +60,10 @@']
+
+
+def test_min_equals_max_matches_fixed_context():
+    """Test that a (n, n) range behaves like plain n."""
+    random.seed(3)
+    a = [f"line{random.randint(0, 20)}" for _ in range(150)]
+    b = [f"line{random.randint(0, 20)}" for _ in range(150)]
+    for n in (0, 1, 3):
+        assert unified_diff(a, b, adaptive_context=(n, n)) == unified_diff(a, b, n=n)
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_adaptive_context_covers_every_change(seed):
+    """Test that the hunks still contain every change and apply cleanly."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 40)}" for _ in range(200)]
+    b = list(a)
+    for _ in range(random.randint(1, 15)):
+        b[random.randrange(len(b))] = f"edit{random.randint(0, 999)}"
+    result, (added, removed, _) = unified_diff_with_stats(
+        a, b, lineterm='', adaptive_context=(1, 6), validate=True)
+    expected = list(difflib.unified_diff(a, b, lineterm='', n=0))
+    assert [l for l in result if l[:1] in '+-' and l[:3] not in ('---', '+++')] == \
+        [l for l in expected if l[:1] in '+-' and l[:3] not in ('---', '+++')]
+    assert added == sum(1 for l in expected if l.startswith('+') and not l.startswith('+++'))
+    assert removed == sum(1 for l in expected if l.startswith('-') and not l.startswith('---'))
+
+
+def test_adaptive_context_str():
+    """Test that unified_diff_str passes adaptive_context through."""
+    a = "\n".join(f"line {i}" for i in range(30))
+    b = a.replace("line 15", "changed")
+    result = unified_diff_str(a, b, adaptive_context=(2, 4))
+    assert hunk_headers(result) == ['@@ -12,9 +12,9 @@\n']
+
+
+def test_adaptive_context_identical():
+    """Test that identical inputs still give an empty diff."""
+    a = ['same'] * 10
+    assert unified_diff(a, a, adaptive_context=(1, 5)) == []
+
+
+def test_adaptive_context_min_above_max():
+    """Test that an inverted range raises ValueError."""
+    with pytest.raises(ValueError):
+        unified_diff(['a'], ['b'], adaptive_context=(5, 1))
@@ -0,0 +1,40 @@
+"""Test the added_lines and removed_lines functions."""
+
+import difflib
+import random
+
+from difflib_rs import added_lines, removed_lines
+
+
+def test_added_removed_basic():
+    """Test that replaced lines count on both sides."""
+    a = ['a', 'gone', 'b', 'old', 'c']
+    b = ['a', 'b', 'new', 'c', 'added']
+    assert added_lines(a, b) == ['new', 'added']
+    assert removed_lines(a, b) == ['gone', 'old']
+
+
+def test_added_removed_keep_line_endings():
+    """Test that lines come back exactly as given."""
+    a = ['x\n', 'y\r\n']
+    b = ['x\n', 'z\r\n']
+    assert added_lines(a, b) == ['z\r\n']
+    assert removed_lines(a, b) == ['y\r\n']
+
+
+def test_added_removed_identical():
+    """Test that identical inputs have no changed lines."""
+    assert added_lines(['a'], ['a']) == []
+    assert removed_lines([], []) == []
+
+
+def test_added_removed_match_difflib():
+    """Test against the opcodes of difflib on random inputs."""
+    rng = random.Random(7)
+    for _ in range(50):
+        a = [rng.choice('abcde') for _ in range(rng.randrange(15))]
+        b = [rng.choice('abcde') for _ in range(rng.randrange(15))]
+        ops = difflib.SequenceMatcher(None, a, b).get_opcodes()
+        changes = [op for op in ops if op[0] != 'equal']
+        assert added_lines(a, b) == [line for _, _, _, j1, j2 in changes for line in b[j1:j2]]
+        assert removed_lines(a, b) == [line for _, i1, i2, _, _ in changes for line in a[i1:i2]]
@@ -0,0 +1,72 @@
+"""Test the annotated_hunks function."""
+
+import random
+
+import pytest
+from difflib_rs import annotated_hunks, get_grouped_opcodes
+
+
+def test_reindented_block_is_indent_only():
+    """Test that re-indented lines are flagged apart from a logic change."""
+    a = ['def f():', 'if x:', 'return 1', 'return 2']
+    b = ['def f():', '    if x:', '        return 1', 'return 3']
+    assert annotated_hunks(a, b) == [[
+        ('equal', 0, 1, 0, 1, False),
+        ('replace', 1, 3, 1, 3, True),
+        ('replace', 3, 4, 3, 4, False),
+    ]]
+
+
+def test_leftover_lines_join_changed_run():
+    """Test that unpaired lines of a replace are never indent-only."""
+    a = ['x = 1', 'y', 'z']
+    b = ['x = 2', '  y', 'extra', 'more', 'z']
+    assert annotated_hunks(a, b) == [[
+        ('replace', 0, 1, 0, 1, False),
+        ('replace', 1, 2, 1, 2, True),
+        ('insert', 2, 2, 2, 4, False),
+        ('equal', 2, 3, 4, 5, False),
+    ]]
+
+
+def test_trailing_whitespace_is_not_indent():
+    """Test that only leading whitespace counts."""
+    assert annotated_hunks(['a'], ['a  ']) == [[('replace', 0, 1, 0, 1, False)]]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_annotated_hunks_cover_grouped_opcodes(seed):
+    """Test that annotation only splits opcodes, never moves lines."""
+    random.seed(seed)
+    a = [' ' * random.randint(0, 2) + f"l{random.randint(0, 6)}" for _ in range(40)]
+    b = [' ' * random.randint(0, 2) + f"l{random.randint(0, 6)}" for _ in range(40)]
+    for hunk, group in zip(annotated_hunks(a, b), get_grouped_opcodes(a, b), strict=True):
+        assert (hunk[0][1], hunk[0][3]) == (group[0][1], group[0][3])
+        assert (hunk[-1][2], hunk[-1][4]) == (group[-1][2], group[-1][4])
+        for prev, op in zip(hunk, hunk[1:]):
+            assert (prev[2], prev[4]) == (op[1], op[3])
+        for tag, i1, i2, j1, j2, indent_only in hunk:
+            if indent_only:
+                assert [line.lstrip() for line in a[i1:i2]] == [line.lstrip() for line in b[j1:j2]]
+
+
+def test_line_ending_only_runs():
+    """Test that line_endings flags replaces that only change line breaks."""
+    a = ['a\n', 'b\n', 'c\n', 'd\n']
+    b = ['a\n', 'b\r\n', 'C\n', 'd\n']
+    assert annotated_hunks(a, b) == [[
+        ('equal', 0, 1, 0, 1, False),
+        ('replace', 1, 3, 1, 3, False),
+        ('equal', 3, 4, 3, 4, False),
+    ]]
+    assert annotated_hunks(a, b, line_endings=True) == [[
+        ('equal', 0, 1, 0, 1, False, False),
+        ('replace', 1, 2, 1, 2, False, True),
+        ('replace', 2, 3, 2, 3, False, False),
+        ('equal', 3, 4, 3, 4, False, False),
+    ]]
+
+
+def test_missing_final_newline_is_eol_only():
+    """Test that dropping the last line break counts as line-ending-only."""
+    assert annotated_hunks(['x\n'], ['x'], line_endings=True) == [[('replace', 0, 1, 0, 1, False, True)]]
@@ -0,0 +1,116 @@
+"""Test the apply_unified_diff function."""
+
+import random
+
+import pytest
+from difflib_rs import apply_unified_diff, unified_diff
+
+
+@pytest.mark.parametrize("seed", range(20))
+def test_apply_roundtrip(seed):
+    """Test that a diff of a -> b applied to a gives b, with no offsets."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 30)}" for _ in range(random.randint(0, 100))]
+    b = [f"line{random.randint(0, 30)}" for _ in range(random.randint(0, 100))]
+    for n in (0, 1, 3):
+        patch = unified_diff(a, b, 'a', 'b', n=n)
+        result, placements = apply_unified_diff(a, patch)
+        assert result == b
+        assert placements == [(0, 0)] * sum(line.startswith('@@') for line in patch)
+
+
+def test_apply_keepends_lines():
+    """Test patches of lines that keep their endings."""
+    a = ['one\n', 'two\n', 'three\n']
+    b = ['one\n', '2\n', 'three\n', 'four\n']
+    patch = unified_diff(a, b, 'a', 'b')
+    assert apply_unified_diff(a, patch) == (b, [(0, 0)])
+
+
+def test_apply_with_offset():
+    """Test that a hunk is found when lines were added above it."""
+    a = [f"line {i}" for i in range(40)]
+    b = a[:20] + ['changed'] + a[21:]
+    patch = unified_diff(a, b, lineterm='')
+    drifted = ['extra 1', 'extra 2', 'extra 3'] + a
+    result, placements = apply_unified_diff(drifted, patch)
+    assert result == ['extra 1', 'extra 2', 'extra 3'] + b
+    assert placements == [(3, 0)]
+
+
+def test_apply_offset_carries_to_later_hunks():
+    """Test that each hunk reports its own offset from its header."""
+    a = [f"line {i}" for i in range(60)]
+    b = list(a)
+    b[10] = 'first'
+    b[50] = 'second'
+    patch = unified_diff(a, b, lineterm='')
+    drifted = a[:30] + ['inserted'] * 5 + a[30:]
+    result, placements = apply_unified_diff(drifted, patch)
+    assert result == b[:30] + ['inserted'] * 5 + b[30:]
+    assert placements == [(0, 0), (5, 0)]
+
+
+def test_apply_max_offset():
+    """Test that max_offset bounds the search."""
+    a = [f"line {i}" for i in range(40)]
+    b = a[:20] + ['changed'] + a[21:]
+    patch = unified_diff(a, b, lineterm='')
+    drifted = ['extra'] * 5 + a
+    assert apply_unified_diff(drifted, patch, max_offset=5)[1] == [(5, 0)]
+    with pytest.raises(ValueError, match="hunk #1 does not apply"):
+        apply_unified_diff(drifted, patch, max_offset=4)
+
+
+def test_apply_with_fuzz():
+    """Test that fuzz ignores mismatched context at the ends of a hunk."""
+    a = [f"line {i}" for i in range(20)]
+    b = a[:10] + ['changed'] + a[11:]
+    patch = unified_diff(a, b, lineterm='')
+    edited = list(a)
+    edited[7] = 'edited context'
+    with pytest.raises(ValueError):
+        apply_unified_diff(edited, patch)
+    result, placements = apply_unified_diff(edited, patch, fuzz=1)
+    assert result == edited[:10] + ['changed'] + edited[11:]
+    assert placements == [(0, 1)]
+
+
+def test_apply_fuzz_keeps_target_lines():
+    """Test that ignored context lines keep the target's version."""
+    a = [f"line {i}" for i in range(20)]
+    b = a[:10] + ['changed'] + a[11:]
+    patch = unified_diff(a, b, lineterm='')
+    edited = list(a)
+    edited[12] = 'edited'
+    edited[13] = 'edited too'
+    result, placements = apply_unified_diff(edited, patch, fuzz=2)
+    assert result == edited[:10] + ['changed'] + edited[11:]
+    assert placements == [(0, 2)]
+
+
+def test_apply_mismatched_change_fails():
+    """Test that fuzz never ignores the changed lines themselves."""
+    a = [f"line {i}" for i in range(20)]
+    b = a[:10] + ['changed'] + a[11:]
+    patch = unified_diff(a, b, lineterm='')
+    edited = list(a)
+    edited[10] = 'not line 10'
+    with pytest.raises(ValueError, match="hunk #1 does not apply"):
+        apply_unified_diff(edited, patch, fuzz=3)
+
+
+def test_apply_stripped_blank_context():
+    """Test that blank context lines without their leading space still count."""
+    a = ['a', '', 'b', 'c']
+    b = ['a', '', 'B', 'c']
+    patch = [line if line != ' ' else '' for line in unified_diff(a, b, lineterm='')]
+    assert '' in patch
+    assert apply_unified_diff(a, patch)[0] == b
+
+
+def test_apply_truncated_hunk():
+    """Test that a hunk shorter than its header raises ValueError."""
+    patch = ['--- a', '+++ b', '@@ -1,3 +1,3 @@', ' a', '-b']
+    with pytest.raises(ValueError, match="malformed hunk header"):
+        apply_unified_diff(['a', 'b', 'c'], patch)
@@ -0,0 +1,71 @@
+"""Test the auto_context option."""
+
+import random
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_range, unified_diff_str, unified_diff_with_stats
+
+A = [f"line {i}\n" for i in range(200)]
+B = list(A)
+for i in (20, 90, 160):
+    B[i] = f"changed {i}\n"
+
+
+def largest_fitting_n(a, b, target):
+    """The n auto_context should pick, found by trying every one."""
+    fitting = [n for n in range(max(len(a), len(b)) + 1) if len(unified_diff(a, b, n=n)) <= target]
+    return max(fitting, default=0)
+
+
+@pytest.mark.parametrize("target", [0, 5, 15, 20, 21, 40, 100, 200, 1000])
+def test_picks_largest_fitting_context(target):
+    """Test that the diff is the one with the largest n within the target."""
+    n = largest_fitting_n(A, B, target)
+    assert unified_diff(A, B, auto_context=target) == unified_diff(A, B, n=n)
+
+
+def test_small_diff_gets_full_context():
+    """Test that a generous target expands context to the whole file."""
+    diff = unified_diff(A, B, auto_context=1000)
+    assert diff == unified_diff(A, B, n=200)
+    assert len(diff) == 2 + 1 + 203
+
+
+def test_huge_diff_falls_back_to_no_context():
+    """Test that a target too small for any context gives n=0."""
+    assert unified_diff(A, B, auto_context=3) == unified_diff(A, B, n=0)
+
+
+def test_ignores_n():
+    """Test that n has no effect once auto_context is set."""
+    assert unified_diff(A, B, n=0, auto_context=40) == unified_diff(A, B, n=50, auto_context=40)
+
+
+def test_identical_inputs():
+    """Test that identical inputs still give an empty diff."""
+    assert unified_diff(A, A, auto_context=10) == []
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_random_inputs(seed):
+    """Test the pick against trying every n on random edits."""
+    rng = random.Random(seed)
+    a = [f"l{rng.randint(0, 30)}\n" for _ in range(80)]
+    b = [line if rng.random() < 0.9 else "x\n" for line in a]
+    target = rng.randint(0, 120)
+    n = largest_fitting_n(a, b, target)
+    assert unified_diff(a, b, auto_context=target) == unified_diff(a, b, n=n)
+
+
+def test_other_entry_points():
+    """Test that the string, range and stats variants take the option."""
+    expected = unified_diff(A, B, auto_context=30)
+    assert unified_diff_str(''.join(A), ''.join(B), auto_context=30, keepends=True) == expected
+    assert unified_diff_range(A, B, (0, 200), (0, 200), auto_context=30) == expected
+    assert unified_diff_with_stats(A, B, auto_context=30)[0] == expected
+
+
+def test_conflicts_with_adaptive_context():
+    """Test that auto_context and adaptive_context can't be combined."""
+    with pytest.raises(ValueError):
+        unified_diff(A, B, auto_context=30, adaptive_context=(1, 5))
@@ -2,6 +2,7 @@ import pytest
 import difflib
 import random
 import string
+import difflib_rs
 from difflib_rs import unified_diff as rust_unified_diff
 from utils import Timer
 
@@ -217,6 +218,58 @@ class TestBenchmark:
             # Verify results are similar
             assert abs(len(python_result) - len(rust_result)) <= 50, "Results should be similar length"
     
+    def test_unchanged_prefix_extension(self):
+        """Test a 10k-line unchanged prefix that matches are extended over."""
+        # Prefix lines are all popular, so autojunk keeps them from anchoring
+        # a match and the match found in the tail is extended back over them
+        vocabulary = ['', '    }', '}', '    return x', '  pass']
+        prefix = [random.choice(vocabulary) for _ in range(10000)]
+        tail = generate_large_text(50)
+        original = prefix + tail
+        modified = prefix + tail[:25] + ['CHANGED'] + tail[26:]
+        
+        with Timer() as python_timer:
+            python_result = list(difflib.unified_diff(original, modified, 'original', 'modified'))
+        python_time = python_timer.elapsed
+        
+        with Timer() as rust_timer:
+            for _ in range(100):
+                rust_result = rust_unified_diff(original, modified, 'original', 'modified')
+        rust_time = rust_timer.elapsed / 100
+        
+        speedup = python_time / rust_time if rust_time > 0 else float('inf')
+        features = "simd" if "simd" in difflib_rs.features() else "default"
+        
+        print(f"\n--- Unchanged Prefix Benchmark (10,000-line prefix, {features} features) ---")
+        print(f"Python time: {python_time:.1f}μs")
+        print(f"Rust time:   {rust_time:.1f}μs")
+        print(f"Speedup:     {speedup:.2f}x")
+        
+        assert rust_result == python_result
+    
+    def test_strip_common_affixes_one_line_change(self):
+        """Test a one-line change in the middle of 50k lines with the affix fast path."""
+        original = generate_large_text(50000)
+        modified = original.copy()
+        modified[25000] = "CHANGED"
+        
+        with Timer() as default_timer:
+            default_result = rust_unified_diff(original, modified, 'original', 'modified')
+        default_time = default_timer.elapsed
+        
+        with Timer() as stripped_timer:
+            stripped_result = rust_unified_diff(original, modified, 'original', 'modified', strip_common_affixes=True)
+        stripped_time = stripped_timer.elapsed
+        
+        speedup = default_time / stripped_time if stripped_time > 0 else float('inf')
+        
+        print("\n--- Common Affixes Benchmark (50,000 lines, 1 change) ---")
+        print(f"Full matcher time:     {default_time:.1f}μs")
+        print(f"Stripped affixes time: {stripped_time:.1f}μs")
+        print(f"Speedup:               {speedup:.2f}x")
+        
+        assert stripped_result == default_result
+    
     def test_memory_usage_large_diff(self):
         """Test with very large diffs to check memory efficiency."""
         # Generate large sequences
@@ -0,0 +1,23 @@
+"""Test benchmark_against_difflib, available with the bench feature."""
+
+import pytest
+import difflib_rs
+
+if "bench" not in difflib_rs.features():
+    pytest.skip("built without the bench feature", allow_module_level=True)
+
+
+def test_outputs_match_and_timings():
+    """Test that both diffs agree and both timings are reported."""
+    a = [f"line {i}\n" for i in range(2000)]
+    b = a[:500] + ["changed\n"] + a[501:1500] + a[1510:] + ["new\n"]
+    rust_micros, python_micros, outputs_match = difflib_rs.benchmark_against_difflib(a, b)
+    assert outputs_match
+    assert rust_micros >= 0 and python_micros > 0
+
+
+def test_identical_and_empty_inputs():
+    """Test inputs whose diffs are empty."""
+    assert difflib_rs.benchmark_against_difflib([], [])[2]
+    assert difflib_rs.benchmark_against_difflib(['a\n'], ['a\n'])[2]
+    assert difflib_rs.benchmark_against_difflib(['a'], ['b'])[2]
@@ -0,0 +1,41 @@
+"""Test the best_base function."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import best_base
+
+
+def test_best_base_picks_closest_version():
+    """Test that the version the target was derived from wins."""
+    v1 = [f"line {i}" for i in range(30)]
+    v2 = v1[:10] + ["inserted"] + v1[10:]
+    v3 = [line.upper() for line in v1]
+    target = v2[:20] + ["edited"] + v2[21:]
+    index, ratio = best_base(target, [v1, v3, v2])
+    assert index == 2
+    assert ratio == difflib.SequenceMatcher(None, v2, target).ratio()
+
+
+def test_best_base_ties_keep_first():
+    """Test that the first of equally good candidates wins."""
+    assert best_base(['a'], [['b'], ['a'], ['a']]) == (1, 1.0)
+    assert best_base(['a'], [['b'], ['c']]) == (0, 0.0)
+
+
+def test_best_base_requires_candidates():
+    """Test that an empty candidate list is rejected."""
+    with pytest.raises(ValueError):
+        best_base(['a'], [])
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_best_base_matches_python(seed):
+    """Test against the best ratio computed with Python's SequenceMatcher."""
+    random.seed(seed)
+    target = [f"line{random.randint(0, 8)}" for _ in range(30)]
+    candidates = [[f"line{random.randint(0, 8)}" for _ in range(random.randint(0, 40))] for _ in range(8)]
+    ratios = [difflib.SequenceMatcher(None, c, target).ratio() for c in candidates]
+    best = max(ratios)
+    assert best_base(target, candidates) == (ratios.index(best), best)
@@ -0,0 +1,39 @@
+"""Test the change_mask_old and change_mask_new functions."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import change_mask_new, change_mask_old
+
+
+def test_change_masks_basic():
+    """Test deleted, replaced and inserted lines."""
+    a = ['a', 'gone', 'b', 'old', 'c']
+    b = ['a', 'b', 'new', 'c', 'added']
+    assert change_mask_old(a, b) == [False, True, False, True, False]
+    assert change_mask_new(a, b) == [False, False, True, False, True]
+
+
+def test_change_masks_lengths():
+    """Test that masks have one flag per line even without changes."""
+    a = ['x', 'y']
+    assert change_mask_old(a, a) == [False, False]
+    assert change_mask_new(a, a) == [False, False]
+    assert change_mask_old([], ['x']) == []
+    assert change_mask_new([], ['x']) == [True]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_change_masks_match_python(seed):
+    """Test against masks built from Python's opcodes."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 10)}" for _ in range(random.randint(0, 50))]
+    b = [f"line{random.randint(0, 10)}" for _ in range(random.randint(0, 50))]
+    old, new = [False] * len(a), [False] * len(b)
+    for tag, i1, i2, j1, j2 in difflib.SequenceMatcher(None, a, b).get_opcodes():
+        if tag != 'equal':
+            old[i1:i2] = [True] * (i2 - i1)
+            new[j1:j2] = [True] * (j2 - j1)
+    assert change_mask_old(a, b) == old
+    assert change_mask_new(a, b) == new
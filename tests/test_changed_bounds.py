@@ -0,0 +1,48 @@
+"""Test the changed_bounds function."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import changed_bounds
+
+
+def python_changed_bounds(a, b):
+    """Reference implementation on top of Python's get_opcodes."""
+    changes = [op for op in difflib.SequenceMatcher(None, a, b).get_opcodes() if op[0] != 'equal']
+    if not changes:
+        return None
+    return ((changes[0][1], changes[-1][2]), (changes[0][3], changes[-1][4]))
+
+
+def test_changed_bounds_identical():
+    """Test that unchanged inputs have no bounds."""
+    a = [f"line {i}" for i in range(10)]
+    assert changed_bounds(a, a) is None
+    assert changed_bounds([], []) is None
+
+
+def test_changed_bounds_spans_all_changes():
+    """Test that the bounds run from the first change to the last."""
+    a = [f"line {i}" for i in range(30)]
+    b = list(a)
+    b[5] = 'first'
+    b[20:22] = ['last']
+    assert changed_bounds(a, b) == ((5, 22), (5, 21))
+
+
+def test_changed_bounds_pure_insert():
+    """Test that an insertion gives an empty range on the old side."""
+    a = ['a', 'b', 'c']
+    b = ['a', 'new', 'b', 'c']
+    assert changed_bounds(a, b) == ((1, 1), (1, 2))
+    assert changed_bounds([], ['x']) == ((0, 0), (0, 1))
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_changed_bounds_matches_python(seed):
+    """Test against a reference built on Python's opcodes."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 15)}" for _ in range(random.randint(0, 60))]
+    b = [f"line{random.randint(0, 15)}" for _ in range(random.randint(0, 60))]
+    assert changed_bounds(a, b) == python_changed_bounds(a, b)
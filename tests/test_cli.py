@@ -0,0 +1,108 @@
+"""Test the difflib-rs command line tool end to end."""
+
+import functools
+import os
+import shutil
+import subprocess
+import tempfile
+
+import pytest
+
+ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+@functools.lru_cache(maxsize=None)
+def binary():
+    """Path of the CLI, built with the cli feature on first use."""
+    if shutil.which("cargo") is None:
+        pytest.skip("cargo is needed to build the CLI")
+    subprocess.run(
+        ["cargo", "build", "--quiet", "--no-default-features", "--features", "cli"],
+        cwd=ROOT,
+        check=True,
+    )
+    return os.path.join(ROOT, "target", "debug", "difflib-rs")
+
+
+def run(*args):
+    return subprocess.run([binary(), *args], capture_output=True, text=True)
+
+
+TMP = tempfile.mkdtemp()
+
+
+def write(name, text):
+    path = os.path.join(TMP, name)
+    with open(path, "w", newline="") as f:
+        f.write(text)
+    return path
+
+
+FILES = (
+    write("a.txt", "one\ntwo\nthree\nfour\nfive\n"),
+    write("b.txt", "one\n2\nthree\nfour\nfive\n"),
+)
+
+
+def test_identical_files_exit_0():
+    """Test that identical files print nothing and exit with 0."""
+    result = run(FILES[0], FILES[0])
+    assert (result.returncode, result.stdout) == (0, "")
+
+
+def test_different_files_exit_1():
+    """Test that differing files print the unified diff and exit with 1."""
+    a, b = FILES
+    result = run(a, b)
+    assert result.returncode == 1
+    assert result.stdout.splitlines() == [
+        f"--- {a}",
+        f"+++ {b}",
+        "@@ -1,5 +1,5 @@",
+        " one",
+        "-two",
+        "+2",
+        " three",
+        " four",
+        " five",
+    ]
+
+
+@pytest.mark.parametrize("option", [["--context", "0"], ["-U", "0"], ["--unified=0"], ["-u", "--context=0"]])
+def test_context_option(option):
+    """Test that the number of context lines can be set."""
+    result = run(*option, *FILES)
+    assert result.stdout.splitlines()[2:] == ["@@ -2 +2 @@", "-two", "+2"]
+
+
+def test_color():
+    """Test that --color wraps headers and changed lines in ANSI codes."""
+    lines = run("--color", *FILES).stdout.splitlines()
+    assert lines[2] == "\x1b[36m@@ -1,5 +1,5 @@\x1b[0m"
+    assert "\x1b[31m-two\x1b[0m" in lines
+    assert "\x1b[32m+2\x1b[0m" in lines
+    assert " one" in lines
+
+
+def test_missing_final_newline():
+    """Test that a last line without a newline is marked like GNU diff."""
+    a = write("c.txt", "x\ny\n")
+    b = write("d.txt", "x\ny")
+    assert run(a, b).stdout.splitlines()[3:] == [
+        " x",
+        "-y",
+        "+y",
+        "\\ No newline at end of file",
+    ]
+
+
+@pytest.mark.parametrize(
+    "args",
+    [[], ["only-one"], ["--context", "many", "a", "b"], ["--bogus", "a", "b"], ["missing-a", "missing-b"]],
+)
+def test_errors_exit_2(args):
+    """Test that bad arguments and unreadable files exit with 2."""
+    result = run(*args)
+    assert result.returncode == 2
+    assert result.stdout == ""
+    assert result.stderr.startswith("difflib-rs: ")
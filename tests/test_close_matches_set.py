@@ -0,0 +1,48 @@
+"""Test get_close_matches_set."""
+
+import pytest
+from difflib_rs import get_close_matches_set
+
+
+def test_order_does_not_matter():
+    """Test that a permutation of the query is a perfect match."""
+    possibilities = [['red', 'green'], ['blue', 'green', 'red'], ['yellow']]
+    assert get_close_matches_set(['red', 'green', 'blue'], possibilities) == [
+        ['blue', 'green', 'red'],
+        ['red', 'green'],
+    ]
+
+
+def test_jaccard_scores_against_cutoff():
+    """Test that 2 shared tokens out of 4 distinct pass 0.5 but not 0.6."""
+    possibilities = [['a', 'b', 'c'], ['b', 'c', 'd']]
+    assert get_close_matches_set(['a', 'b', 'c'], possibilities, cutoff=0.5) == possibilities
+    assert get_close_matches_set(['a', 'b', 'c'], possibilities, cutoff=0.6) == [['a', 'b', 'c']]
+
+
+def test_repeated_tokens_count():
+    """Test that repeats make a multiset: 1 shared out of 2."""
+    assert get_close_matches_set(['x', 'x'], [['x']], cutoff=0.5) == [['x']]
+    assert get_close_matches_set(['x', 'x'], [['x']], cutoff=0.51) == []
+
+
+def test_limit_and_ties():
+    """Test that n limits results and ties keep the earlier possibility."""
+    possibilities = [['a', 'z'], ['a', 'y'], ['a']]
+    assert get_close_matches_set(['a'], possibilities, n=2, cutoff=0.0) == [['a'], ['a', 'z']]
+
+
+def test_empty_sets():
+    """Test that two empty token lists are identical."""
+    assert get_close_matches_set([], [[], ['a']], cutoff=0.0) == [[], ['a']]
+
+
+@pytest.mark.parametrize("n,cutoff,message", [
+    (0, 0.6, "n must be > 0"),
+    (3, 1.5, "cutoff must be in"),
+    (3, -0.1, "cutoff must be in"),
+])
+def test_invalid_arguments(n, cutoff, message):
+    """Test validation like get_close_matches."""
+    with pytest.raises(ValueError, match=message):
+        get_close_matches_set(['a'], [['a']], n=n, cutoff=cutoff)
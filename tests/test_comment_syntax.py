@@ -0,0 +1,74 @@
+"""Test diffing with a comment syntax."""
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_str, unified_diff_with_stats
+
+A = [
+    "# Compute the total",
+    "def total(xs):",
+    "    // sum them up",
+    "    return sum(xs)",
+    "/* trailing",
+    "   block */",
+]
+
+
+def test_comment_only_changes_give_empty_diff():
+    """Test that changing only comments gives no diff with the option on."""
+    b = [
+        "# Compute the sum",
+        "def total(xs):",
+        "    // add everything",
+        "    return sum(xs)",
+        "/* a different",
+        "",
+        "   block */",
+    ]
+    options = dict(comment_prefixes=["#", "//"], block_comment=("/*", "*/"))
+    assert unified_diff(A, b, ignore_comment_changes=True, **options) == []
+    assert unified_diff(A, b) != []
+
+
+def test_code_changes_keep_their_hunk():
+    """Test that hunks changing code still show, comments included."""
+    b = list(A)
+    b[0] = "# Compute the sum"
+    b[3] = "    return sum(xs) + 1"
+    diff = unified_diff(A, b, comment_prefixes=["#", "//"], ignore_comment_changes=True)
+    assert diff == unified_diff(A, b)
+
+
+def test_ignored_hunks_are_not_counted():
+    """Test that stats only count the hunks that are kept."""
+    a = ["# old"] + [f"line {i}" for i in range(20)]
+    b = ["# new"] + [f"line {i}" for i in range(20)]
+    b[15] = "changed"
+    lines, stats = unified_diff_with_stats(a, b, comment_prefixes=["#"], ignore_comment_changes=True)
+    assert stats == (1, 1, 1)
+    assert "-# old\n" not in lines
+
+
+def test_comment_block_mixed_with_code():
+    """Test that code on a line with a block comment isn't comment-only."""
+    a = "x = 1 /* one */\n/* two */ // three\ny = 2\n"
+    b = "x = 2 /* one */\n/* TWO */ // three\ny = 2\n"
+    diff = unified_diff_str(a, b, block_comment=("/*", "*/"), comment_prefixes=["//"], ignore_comment_changes=True)
+    assert diff == [
+        "--- \n", "+++ \n", "@@ -1,3 +1,3 @@\n",
+        "-x = 1 /* one */", "-/* two */ // three", "+x = 2 /* one */", "+/* TWO */ // three", " y = 2",
+    ]
+
+
+def test_comments_do_not_anchor_matches():
+    """Test that a shared comment line doesn't anchor the match."""
+    a = ["a", "# note", "b"]
+    b = ["x", "# note", "y"]
+    assert unified_diff(a, b, comment_prefixes=["#"]) == unified_diff(a, b, isjunk=lambda line: line == "# note")
+
+
+def test_empty_comment_prefix_rejected():
+    """Test that an empty prefix, which would match every line, is rejected."""
+    with pytest.raises(ValueError, match="comment prefixes"):
+        unified_diff(["a"], ["b"], comment_prefixes=[""])
+    with pytest.raises(ValueError, match="block comment"):
+        unified_diff(["a"], ["b"], block_comment=("/*", ""))
@@ -0,0 +1,45 @@
+"""Test the context_diff function."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import context_diff
+
+
+@pytest.mark.parametrize("seed", range(10))
+@pytest.mark.parametrize("n", [0, 1, 3])
+def test_context_diff_matches_python(seed, n):
+    """Test that the default markers give Python's output."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 10)}\n" for _ in range(random.randint(0, 40))]
+    b = [f"line{random.randint(0, 10)}\n" for _ in range(random.randint(0, 40))]
+    expected = list(difflib.context_diff(a, b, 'a.txt', 'b.txt', '2024-01-01', '', n=n))
+    assert context_diff(a, b, 'a.txt', 'b.txt', '2024-01-01', '', n=n) == expected
+
+
+def test_context_diff_identical():
+    """Test that identical inputs give no diff."""
+    a = ['same\n']
+    assert context_diff(a, a) == []
+    assert context_diff([], []) == []
+
+
+def test_context_diff_custom_markers():
+    """Test that each marker can be replaced."""
+    a = ['keep\n', 'old\n', 'gone\n', 'keep\n']
+    b = ['keep\n', 'new\n', 'keep\n', 'added\n']
+    markers = dict(change_marker='~', delete_marker='<', insert_marker='>', context_marker='.')
+    expected = [
+        line.replace('! ', '~ ', 1).replace('- ', '< ', 1).replace('+ ', '> ', 1).replace('  ', '. ', 1)
+        if line[:2] in ('! ', '- ', '+ ', '  ') else line
+        for line in difflib.context_diff(a, b)
+    ]
+    assert context_diff(a, b, **markers) == expected
+
+
+@pytest.mark.parametrize("marker", ['', '!!', '\t', '\x00'])
+def test_context_diff_rejects_bad_markers(marker):
+    """Test that markers must be one printable character."""
+    with pytest.raises(ValueError):
+        context_diff(['a'], ['b'], change_marker=marker)
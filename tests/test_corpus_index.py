@@ -0,0 +1,68 @@
+"""Test the CorpusIndex class."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import CorpusIndex
+
+
+def python_close_matches(docs, query, k, cutoff):
+    """Brute-force reference with the ratio used by get_close_matches."""
+    scored = []
+    for i, doc in enumerate(docs):
+        s = difflib.SequenceMatcher(None, doc, query)
+        if s.ratio() >= cutoff:
+            scored.append((i, s.ratio()))
+    scored.sort(key=lambda item: (-item[1], item[0]))
+    return scored[:k]
+
+
+def make_corpus(seed, size=200):
+    random.seed(seed)
+    base = [f"line {i}" for i in range(40)]
+    docs = []
+    for _ in range(size):
+        doc = base[random.randint(0, 10):random.randint(15, 40)]
+        for _ in range(random.randint(0, 8)):
+            doc.insert(random.randint(0, len(doc)), f"extra {random.randint(0, 30)}")
+        docs.append(doc)
+    return docs
+
+
+@pytest.mark.parametrize("seed", range(5))
+@pytest.mark.parametrize("k,cutoff", [(1, 0.6), (3, 0.6), (10, 0.3), (5, 0.0), (3, 1.0)])
+def test_corpus_index_matches_brute_force(seed, k, cutoff):
+    """Test that pruning never changes the top-k result."""
+    docs = make_corpus(seed)
+    index = CorpusIndex(docs)
+    random.seed(seed + 100)
+    for _ in range(5):
+        query = random.choice(docs)[:]
+        if query and random.random() < 0.5:
+            query[random.randrange(len(query))] = "changed"
+        assert index.query(query, k, cutoff) == python_close_matches(docs, query, k, cutoff)
+
+
+def test_corpus_index_exact_match_first():
+    """Test that an identical document wins with a ratio of 1.0."""
+    docs = [['a', 'b', 'c'], ['x', 'y'], ['a', 'b', 'd']]
+    index = CorpusIndex(docs)
+    assert len(index) == 3
+    assert index.query(['a', 'b', 'c'], k=2) == [(0, 1.0), (2, 2 / 3)]
+
+
+def test_corpus_index_empty_documents():
+    """Test empty queries and documents, whose ratio is 1.0 like difflib."""
+    index = CorpusIndex([[], ['a']])
+    assert index.query([], k=2, cutoff=0.0) == [(0, 1.0), (1, 0.0)]
+    assert CorpusIndex([]).query(['a']) == []
+
+
+def test_corpus_index_rejects_bad_arguments():
+    """Test the same argument checks as get_close_matches."""
+    index = CorpusIndex([['a']])
+    with pytest.raises(ValueError):
+        index.query(['a'], k=0)
+    with pytest.raises(ValueError):
+        index.query(['a'], cutoff=1.5)
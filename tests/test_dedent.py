@@ -0,0 +1,92 @@
+"""Test the dedent option of the unified diff functions."""
+
+import random
+import textwrap
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_range, unified_diff_str, unified_diff_with_stats
+
+SOURCE = [
+    'def area(r):\n',
+    '    """Area of a circle."""\n',
+    '\n',
+    '    return 3.14159 * r * r\n',
+]
+# The same function copied into indented documentation
+SNIPPET = ['        ' + line if line.strip() else '  \n' for line in SOURCE]
+
+
+def hunk_headers(diff):
+    return [line for line in diff if line.startswith('@@')]
+
+
+def test_indented_copy_matches():
+    """Test that a copy indented as a whole has no diff."""
+    assert len(hunk_headers(unified_diff(SOURCE, SNIPPET))) == 1
+    assert unified_diff(SOURCE, SNIPPET, dedent=True) == []
+
+
+def test_diff_shows_original_lines():
+    """Test that a real change is shown with the original indentation."""
+    snippet = list(SNIPPET)
+    snippet[3] = '            return 3.14 * r * r\n'
+    assert unified_diff(SOURCE, snippet, dedent=True, n=0) == [
+        '--- \n', '+++ \n', '@@ -4 +4 @@\n',
+        '-    return 3.14159 * r * r\n', '+            return 3.14 * r * r\n',
+    ]
+
+
+def test_relative_indentation_still_counts():
+    """Test that indentation beyond the common margin is compared."""
+    a = ['if x:\n', '    y()\n']
+    b = ['  if x:\n', '  y()\n']
+    assert unified_diff(a, b, dedent=True, n=0)[2:] == ['@@ -2 +2 @@\n', '-    y()\n', '+  y()\n']
+    assert unified_diff(a, b, normalize=['trim']) == []
+
+
+def test_tabs_and_spaces_differ():
+    """Test that a tab margin doesn't cancel a space margin, as in textwrap.dedent."""
+    a = ['\tx\n', '\t y\n']
+    b = ['    x\n', '     y\n']
+    assert unified_diff(a, b, dedent=True) == []
+    assert unified_diff(['\tx\n', '    y\n'], ['x\n', 'y\n'], dedent=True) != []
+
+
+@pytest.mark.parametrize("seed", range(20))
+def test_matches_like_textwrap_dedent(seed):
+    """Test that hunks are those of diffing the textwrap.dedent-ed inputs."""
+    rng = random.Random(seed)
+    pool = ['x = 1', 'if x:', '    y()', 'return x', '', '  ', '\tz']
+    a = [rng.choice(pool) + '\n' for _ in range(rng.randint(0, 30))]
+    b = [line for line in a if rng.random() > 0.2] + [rng.choice(pool) + '\n' for _ in range(rng.randint(0, 3))]
+    a = [' ' * 4 + line if line.strip() else line for line in a]
+    b = [' ' * rng.randint(0, 8) + line if line.strip() and rng.random() < 0.1 else line for line in b]
+    dedented_a = textwrap.dedent(''.join(a)).splitlines(keepends=True)
+    dedented_b = textwrap.dedent(''.join(b)).splitlines(keepends=True)
+    assert hunk_headers(unified_diff(a, b, dedent=True)) == hunk_headers(unified_diff(dedented_a, dedented_b))
+
+
+def test_combines_with_normalize():
+    """Test that dedent runs before the normalize transforms."""
+    a = ['    Hello\n', '    World\n']
+    b = ['HELLO\n', 'WORLD\n']
+    assert unified_diff(a, b, dedent=True, normalize=['lowercase']) == []
+    assert unified_diff(a, b, normalize=['dedent', 'lowercase']) == []
+    assert unified_diff(a, b, normalize=['lowercase']) != []
+
+
+def test_validate():
+    """Test that validate checks the dedented lines the diff matched on."""
+    a = ['  x\n', '  y\n']
+    b = ['x\n', 'z\n']
+    assert unified_diff(a, b, dedent=True, validate=True) == [
+        '--- \n', '+++ \n', '@@ -1,2 +1,2 @@\n', '   x\n', '-  y\n', '+z\n',
+    ]
+    assert unified_diff(SOURCE, SNIPPET, dedent=True, validate=True) == []
+
+
+def test_other_entry_points():
+    """Test the option through the str, range and stats variants."""
+    assert unified_diff_str(''.join(SOURCE), ''.join(SNIPPET), dedent=True) == []
+    assert unified_diff_range(SOURCE, SNIPPET, (0, 4), (0, 4), dedent=True) == []
+    assert unified_diff_with_stats(SOURCE, SNIPPET, dedent=True) == ([], (0, 0, 0))
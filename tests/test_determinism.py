@@ -0,0 +1,91 @@
+"""Test that diffs are reproducible byte for byte."""
+
+import difflib
+import os
+import random
+import subprocess
+import sys
+
+import pytest
+from difflib_rs import SequenceMatcher, get_matching_blocks, unified_diff
+
+OPTIONS = [
+    {},
+    {"autojunk": False},
+    {"symmetric_junk": True},
+    {"window_size": 64},
+    {"isjunk": lambda line: line.strip() == ""},
+    {"compact": True, "brace_scope_context": 5},
+]
+
+
+def popular_inputs(seed):
+    """Inputs over 200 lines with many popular lines, so autojunk prunes."""
+    random.seed(seed)
+    vocabulary = ["", "}", "{", "return x;"] + [f"line {i}" for i in range(40)]
+    a = [random.choice(vocabulary) for _ in range(600)]
+    b = list(a)
+    for _ in range(60):
+        b[random.randrange(len(b))] = random.choice(vocabulary)
+    return a, b
+
+
+@pytest.mark.parametrize("seed", range(5))
+@pytest.mark.parametrize("options", OPTIONS)
+def test_repeated_diffs_are_identical(seed, options):
+    """Test that the same diff gives the same output every time."""
+    a, b = popular_inputs(seed)
+    first = unified_diff(a, b, **options)
+    for _ in range(20):
+        assert unified_diff(a, b, **options) == first
+
+
+SCRIPT = """
+import random, sys
+sys.path.insert(0, {tests!r})
+from test_determinism import popular_inputs
+from difflib_rs import unified_diff
+for seed in range(5):
+    a, b = popular_inputs(seed)
+    sys.stdout.write("".join(unified_diff(a, b)))
+    sys.stdout.write("".join(unified_diff(a, b, symmetric_junk=True)))
+"""
+
+
+def test_diffs_are_identical_across_processes():
+    """Test that fresh interpreters with different hash seeds agree."""
+    script = SCRIPT.format(tests=os.path.dirname(os.path.abspath(__file__)))
+    outputs = set()
+    for hash_seed in ["0", "1", "12345"]:
+        env = dict(os.environ, PYTHONHASHSEED=hash_seed)
+        result = subprocess.run([sys.executable, "-c", script], env=env, capture_output=True, text=True, check=True)
+        outputs.add(result.stdout)
+    assert len(outputs) == 1
+
+
+def tie_heavy_inputs(seed):
+    """Short lines from a tiny vocabulary, where many matches tie."""
+    rng = random.Random(seed)
+    a = [rng.choice("abc") for _ in range(rng.randint(0, 150))]
+    b = [rng.choice("abc") for _ in range(rng.randint(0, 150))]
+    return a, b
+
+
+@pytest.mark.parametrize("seed", range(30))
+def test_ties_resolved_like_cpython(seed):
+    """Test that tied longest matches go to the earliest, as in CPython."""
+    a, b = tie_heavy_inputs(seed)
+    expected = difflib.SequenceMatcher(None, a, b, autojunk=False).get_matching_blocks()
+    assert get_matching_blocks(a, b, autojunk=False) == [tuple(block) for block in expected]
+
+
+def test_reused_buffers_give_identical_blocks():
+    """Test that search maps grown by earlier diffs don't change results."""
+    pairs = [tie_heavy_inputs(seed) for seed in range(20)]
+    fresh = [get_matching_blocks(a, b) for a, b in pairs]
+    large_a, large_b = popular_inputs(0)
+    with SequenceMatcher() as matcher:
+        matcher.get_matching_blocks(large_a * 4, large_b * 4)
+        pooled = [matcher.get_matching_blocks(a, b) for a, b in pairs]
+        assert pooled == fresh
+        assert [matcher.get_matching_blocks(a, b) for a, b in reversed(pairs)] == fresh[::-1]
@@ -0,0 +1,43 @@
+"""Test the diff_fingerprint function."""
+
+from difflib_rs import diff_fingerprint
+
+A = [f"line {i}\n" for i in range(20)]
+B = A[:5] + ['changed\n'] + A[6:]
+
+
+def test_same_changes_same_fingerprint():
+    """Test that only the changes matter, not the unchanged lines."""
+    fingerprint = diff_fingerprint(A, B)
+    assert fingerprint == diff_fingerprint(A, B)
+    assert 0 <= fingerprint < 2 ** 64
+    # Different unchanged lines after the change
+    assert diff_fingerprint(A[:10], B[:10]) == fingerprint
+    assert diff_fingerprint(A, A) == diff_fingerprint([], []) == diff_fingerprint(B, B)
+    assert diff_fingerprint(A, A) != fingerprint
+
+
+def test_different_changes_differ():
+    """Test that tags and content are part of the fingerprint."""
+    fingerprint = diff_fingerprint(A, B)
+    assert diff_fingerprint(A, A[:5] + ['other\n'] + A[6:]) != fingerprint
+    assert diff_fingerprint(B, A) != fingerprint
+    assert diff_fingerprint(A, A[:5] + A[6:]) != fingerprint
+    assert diff_fingerprint(['ab'], ['c']) != diff_fingerprint(['a'], ['bc'])
+
+
+def test_ignore_position():
+    """Test that moved edits match only with ignore_position."""
+    shifted_a = ['extra\n'] * 3 + A
+    shifted_b = ['extra\n'] * 3 + B
+    assert diff_fingerprint(shifted_a, shifted_b) != diff_fingerprint(A, B)
+    assert diff_fingerprint(shifted_a, shifted_b, ignore_position=True) == diff_fingerprint(
+        A, B, ignore_position=True)
+    assert diff_fingerprint(A, B, ignore_position=True) != diff_fingerprint(A, B)
+
+
+def test_stable_value():
+    """Test that the fingerprint doesn't change between releases."""
+    assert diff_fingerprint(['a\n'], ['b\n']) == 0x27b739ae6075efec
+    assert diff_fingerprint(['a\n'], ['b\n'], ignore_position=True) == 0x8fed72a13e9a5f2c
+    assert diff_fingerprint([], []) == 0xcbf29ce484222325
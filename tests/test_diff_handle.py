@@ -0,0 +1,98 @@
+"""Test diffing off the GIL and on background threads."""
+
+import random
+import threading
+from concurrent.futures import CancelledError, ThreadPoolExecutor
+
+import pytest
+
+from difflib_rs import diff_in_background, get_grouped_opcodes, get_opcodes
+
+
+def slow_pair():
+    """Pair of few distinct lines that takes a while to diff without autojunk."""
+    random.seed(0)
+    a = [f"line{random.randint(0, 5)}" for _ in range(3000)]
+    b = [f"line{random.randint(0, 5)}" for _ in range(3000)]
+    return a, b
+
+
+def test_result_matches_get_opcodes():
+    """Test that the handle's result is the opcodes of the same diff."""
+    a = ['a', 'b', 'c', 'd']
+    b = ['a', 'x', 'c', 'd', 'e']
+    handle = diff_in_background(a, b)
+    assert handle.result() == get_opcodes(a, b)
+    assert handle.done()
+    assert not handle.cancelled()
+    assert not handle.cancel()
+    assert handle.result() == get_opcodes(a, b)
+
+
+def test_options_are_passed_on():
+    """Test that isjunk, autojunk and unique_anchors apply to the background diff."""
+    a, b = slow_pair()
+    isjunk = lambda line: line == "line0"
+    handle = diff_in_background(a, b, isjunk=isjunk, autojunk=False, unique_anchors=True)
+    assert handle.result() == get_opcodes(a, b, isjunk=isjunk, autojunk=False, unique_anchors=True)
+
+
+def test_cancel_stops_the_diff():
+    """Test that a cancelled diff raises CancelledError from result."""
+    a, b = slow_pair()
+    handle = diff_in_background(a * 4, b * 4, autojunk=False)
+    assert handle.cancel()
+    with pytest.raises(CancelledError):
+        handle.result()
+    assert handle.cancelled()
+    assert handle.done()
+
+
+def test_result_timeout():
+    """Test that result raises TimeoutError when the diff isn't done in time."""
+    a, b = slow_pair()
+    handle = diff_in_background(a * 4, b * 4, autojunk=False)
+    try:
+        with pytest.raises(TimeoutError):
+            handle.result(timeout=0)
+    finally:
+        handle.cancel()
+    with pytest.raises(ValueError):
+        handle.result(timeout=-1)
+
+
+def test_thread_pool_matches_serial():
+    """Test that diffs from a thread pool equal the same diffs run one by one."""
+    random.seed(1)
+    pairs = [
+        ([f"l{random.randint(0, 20)}" for _ in range(300)], [f"l{random.randint(0, 20)}" for _ in range(300)])
+        for _ in range(16)
+    ]
+    with ThreadPoolExecutor(max_workers=4) as pool:
+        opcodes = list(pool.map(lambda pair: get_opcodes(*pair), pairs))
+        grouped = list(pool.map(lambda pair: get_grouped_opcodes(*pair), pairs))
+    assert opcodes == [get_opcodes(a, b) for a, b in pairs]
+    assert grouped == [get_grouped_opcodes(a, b) for a, b in pairs]
+
+
+def test_gil_released_during_matching():
+    """Test that another Python thread runs while get_opcodes is matching."""
+    a, b = slow_pair()
+    ticks = []
+    stop = threading.Event()
+
+    def tick():
+        while not stop.is_set():
+            ticks.append(1)
+            stop.wait(0.001)
+
+    thread = threading.Thread(target=tick)
+    thread.start()
+    try:
+        before = len(ticks)
+        get_opcodes(a, b, autojunk=False)
+        during = len(ticks) - before
+    finally:
+        stop.set()
+        thread.join()
+    assert during > 0
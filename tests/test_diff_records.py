@@ -0,0 +1,67 @@
+"""Test the diff_records function."""
+
+import random
+
+from difflib_rs import diff_records
+
+
+def test_diff_records_lines():
+    """Test added, removed and changed keys from key=value lines."""
+    a = ['host=localhost', 'port=8080', 'debug=false', 'name=app']
+    b = ['name=app', 'port=9090', 'host=localhost', 'workers=4']
+    assert diff_records(a, b) == {
+        'added': [('workers', '4')],
+        'removed': [('debug', 'false')],
+        'changed': [('port', '8080', '9090')],
+    }
+
+
+def test_diff_records_dicts():
+    """Test that dicts are diffed the same as lines."""
+    a = {'b': '2', 'a': '1', 'c': '3'}
+    b = {'a': '1', 'c': 'three', 'd': '4'}
+    assert diff_records(a, b) == {
+        'added': [('d', '4')],
+        'removed': [('b', '2')],
+        'changed': [('c', '3', 'three')],
+    }
+    assert diff_records(a, b) == diff_records([f"{k}={v}" for k, v in a.items()],
+                                              [f"{k}={v}" for k, v in b.items()])
+
+
+def test_diff_records_parsing():
+    """Test whitespace trimming, blank lines, bare keys and '=' in values."""
+    a = ['  key = value  ', '', 'flag', 'url=http://x?a=b']
+    b = ['key=value', '   ', 'flag=', 'url=http://x?a=c']
+    assert diff_records(a, b) == {
+        'added': [],
+        'removed': [],
+        'changed': [('url', 'http://x?a=b', 'http://x?a=c')],
+    }
+
+
+def test_diff_records_last_value_wins():
+    """Test that a repeated key keeps its last value, like a dict."""
+    assert diff_records(['k=1', 'k=2'], ['k=2'])['changed'] == []
+    assert diff_records(['k=1', 'k=2'], ['k=1'])['changed'] == [('k', '2', '1')]
+
+
+def test_diff_records_identical_and_empty():
+    """Test identical records and empty inputs."""
+    empty = {'added': [], 'removed': [], 'changed': []}
+    assert diff_records(['a=1', 'b=2'], ['b=2', 'a=1']) == empty
+    assert diff_records([], {}) == empty
+    assert diff_records({}, {'a': '1'}) == {'added': [('a', '1')], 'removed': [], 'changed': []}
+
+
+def test_diff_records_matches_dict_comparison():
+    """Test against a straightforward dict comparison."""
+    random.seed(1)
+    for _ in range(50):
+        a = {f"key{random.randint(0, 300)}": str(random.randint(0, 3)) for _ in range(random.randint(0, 250))}
+        b = {f"key{random.randint(0, 300)}": str(random.randint(0, 3)) for _ in range(random.randint(0, 250))}
+        assert diff_records(a, b) == {
+            'added': sorted((k, v) for k, v in b.items() if k not in a),
+            'removed': sorted((k, v) for k, v in a.items() if k not in b),
+            'changed': sorted((k, a[k], b[k]) for k in a.keys() & b.keys() if a[k] != b[k]),
+        }
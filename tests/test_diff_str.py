@@ -0,0 +1,37 @@
+"""Test the diff_str function."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import diff_str
+
+
+def test_diff_str_equal_texts():
+    """Test that equal texts give an empty string."""
+    assert diff_str("", "") == ""
+    assert diff_str("a\nb\n", "a\nb\n") == ""
+
+
+def test_diff_str_every_line_terminated():
+    """Test that lines without a final newline still end in one."""
+    out = diff_str("one\ntwo", "one\nthree", "a.txt", "b.txt")
+    assert out == "--- a.txt\n+++ b.txt\n@@ -1,2 +1,2 @@\n one\n-two\n+three\n"
+
+
+def test_diff_str_keeps_crlf():
+    """Test that existing line breaks aren't doubled."""
+    out = diff_str("x\r\ny\r\n", "x\r\nz\r\n")
+    assert out.endswith("-y\r\n+z\r\n")
+    assert "\r\n\n" not in out
+
+
+@pytest.mark.parametrize("seed", range(10))
+@pytest.mark.parametrize("n", [0, 1, 3])
+def test_diff_str_matches_python(seed, n):
+    """Test against Python's unified_diff on lines that keep their ends."""
+    random.seed(seed)
+    a = "".join(f"line{random.randint(0, 9)}\n" for _ in range(random.randint(0, 30)))
+    b = "".join(f"line{random.randint(0, 9)}\n" for _ in range(random.randint(0, 30)))
+    expected = "".join(difflib.unified_diff(a.splitlines(True), b.splitlines(True), 'a', 'b', n=n))
+    assert diff_str(a, b, 'a', 'b', n) == expected
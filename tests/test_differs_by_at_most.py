@@ -0,0 +1,59 @@
+"""Test gating on a budget of differing lines."""
+
+import random
+
+import pytest
+from difflib_rs import differs_by_at_most, unified_diff_with_stats
+
+A = [f"line {i}" for i in range(50)]
+
+
+def changed_lines(a, b):
+    _, (added, removed, _) = unified_diff_with_stats(a, b)
+    return added + removed
+
+
+def test_identical_within_any_budget():
+    """Test that equal inputs fit a zero budget."""
+    assert differs_by_at_most(A, A, 0)
+    assert differs_by_at_most([], [], 0)
+
+
+def test_replace_counts_both_sides():
+    """Test that a replaced line counts as one removed and one added."""
+    b = A[:10] + ["changed"] + A[11:]
+    assert not differs_by_at_most(A, b, 1)
+    assert differs_by_at_most(A, b, 2)
+
+
+def test_length_difference_bails_out():
+    """Test that a length difference over budget is rejected."""
+    assert not differs_by_at_most(A, A[:40], 9)
+    assert differs_by_at_most(A, A[:40], 10)
+
+
+def test_unmatched_lines_bail_out():
+    """Test that lines with no counterpart count before any matching."""
+    b = [line + "!" for line in A]
+    assert not differs_by_at_most(A, b, 99)
+    assert differs_by_at_most(A, b, 100)
+
+
+def test_moved_lines_count_as_changes():
+    """Test that reordering counts the opcodes' changes, not just the multiset."""
+    b = A[25:] + A[:25]
+    assert not differs_by_at_most(A, b, 0)
+    assert differs_by_at_most(A, b, changed_lines(A, b))
+
+
+@pytest.mark.parametrize("seed", range(20))
+def test_agrees_with_diff_stats(seed):
+    """Test that the answer matches counting the full diff."""
+    random.seed(seed)
+    b = [line for line in A if random.random() > 0.1]
+    for _ in range(random.randrange(5)):
+        b.insert(random.randrange(len(b) + 1), f"new {random.random()}")
+    changed = changed_lines(A, b)
+    assert differs_by_at_most(A, b, changed)
+    if changed:
+        assert not differs_by_at_most(A, b, changed - 1)
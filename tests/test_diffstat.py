@@ -0,0 +1,51 @@
+"""Test the diffstat_line function."""
+
+import pytest
+from difflib_rs import diffstat_line
+
+
+def test_diffstat_line_basic():
+    """Test counts and bar for a small change."""
+    a = ['one', 'two', 'three', 'four']
+    b = ['zero', 'one', 'tree', 'four']
+    assert diffstat_line(a, b, 'file.txt') == 'file.txt | 4 ++--'
+
+
+def test_diffstat_line_identical():
+    """Test that identical inputs report zero changes and no bar."""
+    a = ['same', 'lines']
+    assert diffstat_line(a, a, 'file.txt') == 'file.txt | 0'
+
+
+def test_diffstat_line_only_additions():
+    """Test a pure insertion."""
+    assert diffstat_line([], ['a', 'b', 'c'], 'new.txt') == 'new.txt | 3 +++'
+
+
+@pytest.mark.parametrize("max_width", [1, 5, 10, 40])
+def test_diffstat_line_scales_bar(max_width):
+    """Test that the bar never exceeds max_width and keeps both signs."""
+    a = [f"old_{i}" for i in range(7)] + ['keep']
+    b = [f"new_{i}" for i in range(300)] + ['keep']
+    line = diffstat_line(a, b, 'big.txt', max_width=max_width)
+
+    name, rest = line.split(' | ')
+    total, bar = rest.split(' ')
+    assert name == 'big.txt'
+    assert total == '307'
+    assert len(bar) == max_width
+    assert set(bar) <= {'+', '-'}
+    if max_width > 1:
+        assert '+' in bar and '-' in bar
+        assert bar.count('+') > bar.count('-')
+
+
+def test_diffstat_line_narrow_widths():
+    """Test that widths of 0 and 1 give an empty and a one-mark bar."""
+    a = ['one', 'two', 'three', 'four']
+    b = ['zero', 'one', 'tree', 'four']
+    assert diffstat_line(a, b, 'f', max_width=0) == 'f | 4'
+    assert diffstat_line([], ['a', 'b', 'c'], 'f', max_width=0) == 'f | 3'
+    assert diffstat_line(a, b, 'f', max_width=1) == 'f | 4 -'
+    assert diffstat_line([], ['a', 'b', 'c'], 'f', max_width=1) == 'f | 3 +'
+    assert diffstat_line(['a', 'b', 'c'], [], 'f', max_width=1) == 'f | 3 -'
@@ -0,0 +1,59 @@
+"""Test the elide_equal_over option."""
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_str, unified_diff_with_stats
+
+A = [f"line {i}\n" for i in range(40)]
+B = list(A)
+B[5] = 'changed 5\n'
+B[20] = 'changed 20\n'
+
+
+def test_internal_run_elided():
+    """Test that a long run between changes becomes a marker line."""
+    full = unified_diff(A, B, n=10)
+    diff = unified_diff(A, B, n=10, elide_equal_over=8)
+    assert diff[2] == full[2] == '@@ -1,31 +1,31 @@\n'
+    marker = diff.index('… 10 unchanged lines …\n')
+    assert diff[marker - 3:marker + 5] == [
+        '+changed 5\n', ' line 6\n', ' line 7\n',
+        '… 10 unchanged lines …\n',
+        ' line 18\n', ' line 19\n', '-line 20\n', '+changed 20\n',
+    ]
+    assert diff[:marker - 2] == full[:marker - 2]
+    assert diff[marker + 1:] == full[marker + 10:]
+
+
+def test_leading_and_trailing_context_kept():
+    """Test that only runs between two changes are elided."""
+    diff = unified_diff(A, B, n=10, elide_equal_over=3)
+    assert diff[3:8] == [' line 0\n', ' line 1\n', ' line 2\n', ' line 3\n', ' line 4\n']
+    assert diff[-1] == ' line 30\n'
+    assert sum(line.startswith('…') for line in diff) == 1
+
+
+@pytest.mark.parametrize("over", [14, 100])
+def test_short_runs_untouched(over):
+    """Test that runs of at most elide_equal_over lines are shown whole."""
+    assert unified_diff(A, B, n=10, elide_equal_over=over) == unified_diff(A, B, n=10)
+
+
+def test_tiny_runs_never_elided():
+    """Test that a marker never hides fewer than two lines."""
+    b = list(A)
+    b[5], b[10] = 'x\n', 'y\n'
+    assert unified_diff(A, b, elide_equal_over=0) == unified_diff(A, b)
+    b[10], b[12] = A[10], 'z\n'
+    assert unified_diff(A, b, elide_equal_over=0)[10] == '… 2 unchanged lines …\n'
+
+
+def test_with_line_numbers_and_other_entry_points():
+    """Test numbering around the marker and the other diff functions."""
+    diff = unified_diff(A, B, n=10, elide_equal_over=8, show_line_numbers=True, lineterm='')
+    marker = diff.index('… 10 unchanged lines …')
+    assert diff[marker - 1].startswith(' 8  8 ')
+    assert diff[marker + 1].startswith('19 19 ')
+    text = unified_diff_str(''.join(A), ''.join(B), n=10, elide_equal_over=8)
+    assert '… 10 unchanged lines …\n' in text
+    elided, stats = unified_diff_with_stats(A, B, n=10, elide_equal_over=8)
+    assert stats == unified_diff_with_stats(A, B, n=10)[1]
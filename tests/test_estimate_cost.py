@@ -0,0 +1,26 @@
+"""Test the estimate_cost function."""
+
+from difflib_rs import estimate_cost
+
+
+def test_cost_counts_bucket_sizes():
+    """Test that each line of a costs the occurrences of it in b."""
+    assert estimate_cost(['x', 'y', 'x'], ['x', 'x', 'z']) == 4
+    assert estimate_cost(['q'], ['x', 'y']) == 0
+    assert estimate_cost([], ['x']) == 0
+
+
+def test_cost_grows_with_repetition():
+    """Test that repeated lines cost more than distinct ones."""
+    distinct = [f"line {i}" for i in range(100)]
+    repeated = ["}"] * 100
+    assert estimate_cost(distinct, distinct) == 100
+    assert estimate_cost(repeated, repeated) == 100 * 100
+
+
+def test_autojunk_and_isjunk_drop_cost():
+    """Test that junk and popular lines cost nothing."""
+    b = ["}"] * 300
+    assert estimate_cost(["}"], b) == 0
+    assert estimate_cost(["}"], b, autojunk=False) == 300
+    assert estimate_cost(["}"], ["}"], isjunk=lambda line: line == "}") == 0
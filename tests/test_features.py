@@ -0,0 +1,18 @@
+"""Test version and feature introspection."""
+
+import re
+
+import difflib_rs
+
+
+def test_version_is_semver():
+    """Test that __version__ is a plain x.y.z version string."""
+    assert re.fullmatch(r'\d+\.\d+\.\d+', difflib_rs.__version__)
+
+
+def test_features_lists_python():
+    """Test that features() returns names of compiled-in features."""
+    features = difflib_rs.features()
+    assert isinstance(features, list)
+    assert 'python' in features
+    assert len(features) == len(set(features))
@@ -0,0 +1,37 @@
+"""Test the first_difference function."""
+
+import itertools
+
+import pytest
+from difflib_rs import first_difference
+
+
+def test_first_difference_positions():
+    """Test the index of the first differing line."""
+    assert first_difference(['a', 'b', 'c'], ['a', 'x', 'c']) == (1, 1)
+    assert first_difference(['a'], ['b']) == (0, 0)
+    assert first_difference(['a', 'b'], ['a', 'b', 'c']) == (2, 2)
+    assert first_difference(['a', 'b', 'c'], ['a']) == (1, 1)
+    assert first_difference([], ['a']) == (0, 0)
+
+
+def test_first_difference_equal():
+    """Test None for equal sequences."""
+    assert first_difference(['a', 'b'], ('a', 'b')) is None
+    assert first_difference([], []) is None
+
+
+def test_first_difference_stops_at_difference():
+    """Test that nothing after the first difference is read."""
+    a = itertools.chain(['same\n', 'a\n'], itertools.count())
+    b = itertools.chain(['same\n', 'b\n'], itertools.count())
+    assert first_difference(a, b) == (1, 1)
+    assert first_difference(['x', 'x'], itertools.repeat('x')) == (2, 2)
+
+
+def test_first_difference_rejects_str():
+    """Test that a str isn't taken as lines."""
+    with pytest.raises(TypeError):
+        first_difference('abc', ['a'])
+    with pytest.raises(TypeError):
+        first_difference([1], [1])
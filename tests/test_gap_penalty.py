@@ -0,0 +1,82 @@
+"""Test the gap_penalty anchor scoring, and its diff quality against the
+default on hand-picked cases."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import get_grouped_opcodes, get_opcodes, unified_diff_with_stats, verify_diff
+
+
+def _changed_lines(a, b, **kwargs):
+    added, removed, _ = unified_diff_with_stats(a, b, **kwargs)[1]
+    return added + removed
+
+
+# A long block moved across shorter ones: the longest match drags every
+# other line into the diff, where anchoring near the diagonal keeps them.
+MOVED_BLOCK = (
+    ['p1', 'p2', 'p3', 'm', 'q1', 'q2', 'q3', 'L1', 'L2', 'L3', 'L4'],
+    ['L1', 'L2', 'L3', 'L4', 'p1', 'p2', 'p3', 'n', 'q1', 'q2', 'q3'],
+)
+
+# A function moved to the top of a file whose other functions were edited
+F1 = ['def f1():', '    a = 1', '    return a']
+F2 = ['def f2():', '    b = 2', '    return b']
+G = ['def g():', '    x = 1', '    y = 2', '    z = 3', '    return x + y + z']
+MOVED_FUNCTION = (F1 + ['# old 1'] + F2 + ['# old 2'] + G, G + F1 + ['# new 1'] + F2 + ['# new 2'])
+
+
+@pytest.mark.parametrize("a,b,penalty,default,scored", [
+    (*MOVED_BLOCK, 0.5, 14, 10),
+    (*MOVED_FUNCTION, 1.0, 16, 14),
+])
+def test_quality_against_default(a, b, penalty, default, scored):
+    """Test that a gap penalty gives a smaller diff on hand-picked cases."""
+    assert _changed_lines(a, b) == default
+    assert _changed_lines(a, b, gap_penalty=penalty) == scored
+
+
+def test_moved_block_opcodes():
+    """Test that the shorter runs near the diagonal are the ones matched."""
+    a, b = MOVED_BLOCK
+    assert get_opcodes(a, b, gap_penalty=0.5) == [
+        ('insert', 0, 0, 0, 4),
+        ('equal', 0, 3, 4, 7),
+        ('replace', 3, 4, 7, 8),
+        ('equal', 4, 7, 8, 11),
+        ('delete', 7, 11, 11, 11),
+    ]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_default_unchanged(seed):
+    """Test that without a penalty, or with 0, the opcodes are CPython's."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 10)}" for _ in range(random.randint(0, 60))]
+    b = [f"line{random.randint(0, 10)}" for _ in range(random.randint(0, 60))]
+    expected = [tuple(op) for op in difflib.SequenceMatcher(None, a, b).get_opcodes()]
+    assert get_opcodes(a, b) == expected
+    assert get_opcodes(a, b, gap_penalty=0.0) == expected
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_scored_diff_is_valid(seed):
+    """Test that diffs anchored with a penalty still turn a into b."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 10)}" for _ in range(random.randint(0, 60))]
+    b = [f"line{random.randint(0, 10)}" for _ in range(random.randint(0, 60))]
+    rebuilt = []
+    for tag, i1, i2, j1, j2 in get_opcodes(a, b, gap_penalty=1.0):
+        assert (a[i1:i2] == b[j1:j2]) == (tag == 'equal')
+        rebuilt.extend(b[j1:j2])
+    assert rebuilt == b
+    assert get_grouped_opcodes(a, b, gap_penalty=1.0) is not None
+    assert verify_diff(a, b, gap_penalty=1.0)
+
+
+@pytest.mark.parametrize("penalty", [-1.0, float('inf'), float('nan')])
+def test_invalid_penalty(penalty):
+    """Test that negative and non-finite penalties are rejected."""
+    with pytest.raises(ValueError, match="gap penalty"):
+        get_opcodes(['a'], ['b'], gap_penalty=penalty)
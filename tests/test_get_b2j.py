@@ -0,0 +1,35 @@
+"""Test the get_b2j function."""
+
+import difflib
+
+from difflib_rs import get_b2j
+
+
+def test_get_b2j_positions():
+    """Test that each line maps to its positions in order."""
+    assert get_b2j(['a', 'b', 'a', 'c']) == {'a': [0, 2], 'b': [1], 'c': [3]}
+    assert get_b2j([]) == {}
+
+
+def test_get_b2j_junk():
+    """Test that junk lines are left out."""
+    b = ['x', '', 'y', '', 'x']
+    assert get_b2j(b, isjunk=lambda line: not line) == {'x': [0, 4], 'y': [2]}
+
+
+def test_get_b2j_autojunk_matches_cpython():
+    """Test the post-pruning index against CPython's b2j."""
+    b = ['common'] * 5 + [f"line {i}" for i in range(250)]
+    pruned = get_b2j(b)
+    assert 'common' not in pruned
+    assert pruned == difflib.SequenceMatcher(None, [], b).b2j
+    assert get_b2j(b, autojunk=False)['common'] == [0, 1, 2, 3, 4]
+
+
+def test_get_b2j_is_a_snapshot():
+    """Test that changing the returned dict doesn't change later results."""
+    b = ['a', 'b']
+    index = get_b2j(b)
+    index['a'].append(9)
+    del index['b']
+    assert get_b2j(b) == {'a': [0], 'b': [1]}
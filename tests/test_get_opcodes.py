@@ -0,0 +1,144 @@
+"""Test the get_opcodes and get_grouped_opcodes functions."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import get_grouped_opcodes, get_opcodes, unified_diff
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_get_opcodes_matches_python(seed):
+    """Test that the default 0-based opcodes match SequenceMatcher's."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 20)}" for _ in range(random.randint(0, 80))]
+    b = [f"line{random.randint(0, 20)}" for _ in range(random.randint(0, 80))]
+    sm = difflib.SequenceMatcher(None, a, b)
+    assert get_opcodes(a, b) == [tuple(op) for op in sm.get_opcodes()]
+    for n in (0, 1, 3):
+        # get_grouped_opcodes trims the matcher's cached opcodes in place
+        sm = difflib.SequenceMatcher(None, a, b)
+        expected = [[tuple(op) for op in group] for group in sm.get_grouped_opcodes(n)]
+        assert get_grouped_opcodes(a, b, n=n) == expected
+
+
+def test_grouped_zero_context_keeps_empty_equal():
+    """Test that n=0 hunks keep the empty equal opcodes Python pads them with."""
+    a = ['a', 'b', 'c', 'd', 'e']
+    b = ['a', 'X', 'c', 'd', 'Y']
+    assert get_grouped_opcodes(a, b, n=0) == [
+        [('equal', 1, 1, 1, 1), ('replace', 1, 2, 1, 2), ('equal', 2, 2, 2, 2)],
+        [('equal', 4, 4, 4, 4), ('replace', 4, 5, 4, 5)],
+    ]
+
+
+def test_one_based_ranges():
+    """Test the 1-based inclusive conversion, including empty sides."""
+    a = ['a', 'b', 'c', 'd']
+    b = ['a', 'x', 'c', 'd', 'e']
+    assert get_opcodes(a, b, one_based=True) == [
+        ('equal', 1, 1, 1, 1),
+        ('replace', 2, 2, 2, 2),
+        ('equal', 3, 4, 3, 4),
+        ('insert', 5, 4, 5, 5),
+    ]
+
+
+def test_one_based_matches_patch_lines():
+    """Test that 1-based ranges index the lines directly, as patch tools do."""
+    a = [f"line {i}" for i in range(1, 21)]
+    b = a[:9] + ['new 10', 'new 11'] + a[12:]
+    for tag, i1, i2, j1, j2 in get_opcodes(a, b, one_based=True):
+        if tag == 'replace':
+            assert [a[k - 1] for k in range(i1, i2 + 1)] == ['line 10', 'line 11', 'line 12']
+            assert [b[k - 1] for k in range(j1, j2 + 1)] == ['new 10', 'new 11']
+
+
+def test_grouped_one_based():
+    """Test that grouped opcodes convert every opcode."""
+    a = [f"line {i}" for i in range(30)]
+    b = a[:15] + a[16:]
+    zero = get_grouped_opcodes(a, b)
+    one = get_grouped_opcodes(a, b, one_based=True)
+    assert one == [[(tag, i1 + 1, i2, j1 + 1, j2) for tag, i1, i2, j1, j2 in group] for group in zero]
+    assert ('delete', 16, 16, 16, 15) in one[0]
+
+
+def test_get_opcodes_isjunk():
+    """Test that isjunk and autojunk are passed to the matcher."""
+    a = ['x', ' ', 'y', ' ', 'z']
+    b = [' ', 'y', 'x', ' ', 'z']
+    sm = difflib.SequenceMatcher(lambda line: line == ' ', a, b)
+    assert get_opcodes(a, b, isjunk=lambda line: line == ' ') == [tuple(op) for op in sm.get_opcodes()]
+    a = ['common'] * 300 + ['a']
+    b = ['common'] * 300 + ['b']
+    sm = difflib.SequenceMatcher(None, a, b, autojunk=False)
+    assert get_opcodes(a, b, autojunk=False) == [tuple(op) for op in sm.get_opcodes()]
+
+
+def test_autojunk_popular_line_extends_matches():
+    """Test a 300-line input whose popular '}' line is pruned by autojunk.
+
+    '}' is too popular to anchor a match, but it is not junk, so matches
+    found on unique lines still extend over it, exactly like CPython.
+    """
+    b = []
+    for i in range(60):
+        b += [f"def f{i}():", f"    return {i}", "}", "}", "}"]
+    a = b[:]
+    a[10:15] = ["}", "}", "}", "}", "}"]
+    a[101] = "    return changed"
+    del a[200:202]
+    assert len(b) == 300
+
+    # The match anchored at 'def f3():' extends back over a[12:15]
+    assert get_opcodes(a, b) == [
+        ('equal', 0, 10, 0, 10), ('replace', 10, 12, 10, 12), ('equal', 12, 101, 12, 101),
+        ('replace', 101, 102, 101, 102), ('equal', 102, 200, 102, 200), ('insert', 200, 200, 200, 202),
+        ('equal', 200, 298, 202, 300),
+    ]
+    assert get_opcodes(a, b) == [tuple(op) for op in difflib.SequenceMatcher(None, a, b).get_opcodes()]
+    assert unified_diff(a, b, lineterm='') == list(difflib.unified_diff(a, b, lineterm=''))
+
+
+@pytest.mark.parametrize("seed", range(5))
+def test_max_occurrences_above_counts_is_exact(seed):
+    """Test that a cap no element reaches changes nothing."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 20)}" for _ in range(150)]
+    b = [f"line{random.randint(0, 20)}" for _ in range(150)]
+    assert get_opcodes(a, b, max_occurrences_per_element=150) == get_opcodes(a, b)
+    assert unified_diff(a, b, max_occurrences_per_element=150) == unified_diff(a, b)
+
+
+@pytest.mark.parametrize("seed", range(5))
+def test_max_occurrences_gives_valid_opcodes(seed):
+    """Test that capped opcodes still turn a into b."""
+    random.seed(seed)
+    a = [random.choice('abc') for _ in range(400)]
+    b = [random.choice('abc') for _ in range(400)]
+    for cap in (1, 2, 5):
+        rebuilt = []
+        for tag, i1, i2, j1, j2 in get_opcodes(a, b, autojunk=False, max_occurrences_per_element=cap):
+            if tag == 'equal':
+                assert a[i1:i2] == b[j1:j2]
+            rebuilt.extend(b[j1:j2])
+        assert rebuilt == b
+
+
+def test_max_occurrences_keeps_popular_anchors():
+    """Test that capped popular lines still match, unlike with autojunk."""
+    a = ['a'] + ['x'] * 300
+    b = ['b'] + ['x'] * 300
+    expected = [('replace', 0, 1, 0, 1), ('equal', 1, 301, 1, 301)]
+    assert get_opcodes(a, b, autojunk=False, max_occurrences_per_element=1) == expected
+    assert get_opcodes(a, b) == [tuple(op) for op in difflib.SequenceMatcher(None, a, b).get_opcodes()]
+    assert get_opcodes(a, b) == [('replace', 0, 301, 0, 301)]
+
+
+def test_max_occurrences_must_be_positive():
+    """Test that a cap of 0 raises ValueError."""
+    with pytest.raises(ValueError, match="max_occurrences_per_element"):
+        get_opcodes(['a'], ['a'], max_occurrences_per_element=0)
+    with pytest.raises(ValueError, match="max_occurrences_per_element"):
+        unified_diff(['a'], ['b'], max_occurrences_per_element=0)
@@ -0,0 +1,76 @@
+"""Test get_opcodes_where with a position-dependent equality."""
+
+import warnings
+
+import pytest
+from difflib_rs import get_opcodes, get_opcodes_where
+
+
+def same(i, a_line, j, b_line):
+    return a_line == b_line
+
+
+@pytest.mark.parametrize("a,b", [
+    (["a", "b", "c"], ["a", "b", "c"]),
+    (["a", "b", "c", "d"], ["a", "x", "c", "y", "d"]),
+    (["one", "two", "three"], ["zero", "one", "three", "four"]),
+    ([], ["a"]),
+    (["a"], []),
+    ([], []),
+])
+def test_plain_equality_matches_get_opcodes(a, b):
+    """Test that ordinary equality gives the same opcodes as get_opcodes."""
+    assert get_opcodes_where(a, b, same) == get_opcodes(a, b)
+
+
+def test_header_row_forced_to_match():
+    """Test that a predicate can always match the first rows."""
+    a = ["id,name", "1,ann", "2,bob"]
+    b = ["ID,Name", "1,ann", "2,bob"]
+
+    def eq(i, a_line, j, b_line):
+        return (i == 0 and j == 0) or a_line == b_line
+
+    assert get_opcodes(a, b)[0][0] == "replace"
+    assert get_opcodes_where(a, b, eq) == [("equal", 0, 3, 0, 3)]
+
+
+def test_loose_data_rows():
+    """Test that rows can be compared on one column only."""
+    a = ["1,ann,10", "2,bob,20", "3,cy,30"]
+    b = ["1,ann,11", "3,cy,31"]
+
+    def eq(i, a_line, j, b_line):
+        return a_line.split(",")[0] == b_line.split(",")[0]
+
+    assert get_opcodes_where(a, b, eq) == [
+        ("equal", 0, 1, 0, 1),
+        ("delete", 1, 2, 1, 1),
+        ("equal", 2, 3, 1, 2),
+    ]
+
+
+def test_predicate_exception_is_raised():
+    """Test that an exception from the predicate propagates."""
+    def eq(i, a_line, j, b_line):
+        raise KeyError("boom")
+
+    with pytest.raises(KeyError):
+        get_opcodes_where(["a"], ["a"], eq)
+
+
+def test_large_input_warns():
+    """Test that a quadratic comparison of large inputs warns."""
+    def eq(i, a_line, j, b_line):
+        raise StopIteration
+
+    a = ["x"] * 4000
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always")
+        with pytest.raises(StopIteration):
+            get_opcodes_where(a, a, eq)
+    assert [w.category for w in caught] == [RuntimeWarning]
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always")
+        get_opcodes_where(a[:10], a[:10], same)
+    assert caught == []
@@ -0,0 +1,66 @@
+"""Test grapheme-cluster segmentation and bidi isolation with RTL fixtures."""
+
+from difflib_rs import char_opcodes_utf16, inline_char_diff, utf16_len, word_unified_diff
+
+FSI, PDI = '⁨', '⁩'
+
+# Hebrew "shalom" with vowel points, and the same letters with one point changed
+HEBREW_A = 'שָׁלוֹם'
+HEBREW_B = 'שָׁלוּם'
+# Arabic "kataba" with harakat, and "kutiba"
+ARABIC_A = 'كَتَبَ'
+ARABIC_B = 'كُتِبَ'
+
+
+def test_combining_marks_stay_with_their_letter():
+    """Test that a changed point marks the whole letter, not the mark alone."""
+    diff = inline_char_diff(HEBREW_A, HEBREW_B)
+    assert diff == 'שָׁל[-וֹ-]{+וּ+}ם'
+
+
+def test_arabic_harakat():
+    """Test that letters with changed harakat are replaced whole."""
+    assert inline_char_diff(ARABIC_A, ARABIC_B) == '[-كَتَ-]{+كُتِ+}بَ'
+
+
+def test_decomposed_accent():
+    """Test that a decomposed accent isn't split from its base letter."""
+    assert inline_char_diff('café', 'cafe') == 'caf[-é-]{+e+}'
+
+
+def test_emoji_zwj_sequence():
+    """Test that a ZWJ sequence is one character."""
+    family = '\U0001F468‍\U0001F469‍\U0001F467'
+    assert inline_char_diff(f'a{family}b', 'ab') == f'a[-{family}-]b'
+
+
+def test_utf16_offsets_cover_clusters():
+    """Test that UTF-16 opcodes span the whole cluster, marks included."""
+    ops = char_opcodes_utf16(HEBREW_A, HEBREW_B)
+    assert ops[-1][2] == utf16_len(HEBREW_A)
+    assert ops[-1][4] == utf16_len(HEBREW_B)
+    assert [op[0] for op in ops] == ['equal', 'replace', 'equal']
+    assert ops[1][1:3] == (utf16_len('שָׁל'), utf16_len('שָׁלוֹ'))
+
+
+def test_isolate_rtl_runs():
+    """Test that RTL runs are isolated inside their markers."""
+    diff = inline_char_diff('abc שלום', 'abc עולם', isolate_rtl=True)
+    assert FSI + 'ש' + PDI in diff
+    assert diff.count(FSI) == diff.count(PDI) > 0
+    assert inline_char_diff('abc', 'abd', isolate_rtl=True) == 'ab[-c-]{+d+}'
+    assert FSI not in inline_char_diff('abc שלום', 'abc עולם')
+
+
+def test_word_diff_rtl():
+    """Test word diffs of Hebrew text, with and without isolates."""
+    a = 'שלום עולם יפה'
+    b = 'שלום עולם נאה'
+    assert word_unified_diff(a, b) == ['@@ -1,3 +1,3 @@', 'שלום עולם [-יפה-]{+נאה+}']
+    assert word_unified_diff(a, b, isolate_rtl=True)[1] == f'שלום עולם [-{FSI}יפה{PDI}-]{{+{FSI}נאה{PDI}+}}'
+
+
+def test_word_after_combining_mark_on_space():
+    """Test that a mark after a space doesn't start a word of its own."""
+    assert word_unified_diff('a ́b', 'a ́b') == []
+    assert word_unified_diff('a b', 'a  ́b') == []
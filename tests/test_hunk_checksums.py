@@ -0,0 +1,64 @@
+"""Test hunk checksums in unified diff headers."""
+
+import zlib
+
+import pytest
+from difflib_rs import apply_unified_diff, invert_unified_diff, unified_diff, unified_diff_str
+
+A = [f"line {i}" for i in range(20)]
+B = A[:3] + ["changed"] + A[4:15] + A[16:] + ["added"]
+
+
+def expected_checksum(lines):
+    return f"crc32={zlib.crc32(''.join(line + chr(10) for line in lines).encode()):08x}"
+
+
+def test_headers_carry_crc32_of_old_lines():
+    """Test that each @@ header ends with the CRC-32 of its old-side lines."""
+    diff = unified_diff(A, B, lineterm="", hunk_checksums=True)
+    headers = [line for line in diff if line.startswith("@@")]
+    assert headers == [
+        f"@@ -1,7 +1,7 @@ {expected_checksum(A[0:7])}",
+        f"@@ -13,8 +13,8 @@ {expected_checksum(A[12:20])}",
+    ]
+
+
+def test_off_by_default():
+    """Test that diffs have no checksums without the option."""
+    assert unified_diff(A, B) == unified_diff(A, B, hunk_checksums=False)
+    assert not any("crc32" in line for line in unified_diff(A, B))
+
+
+def test_lineterm_stays_last():
+    """Test that the checksum goes before the line terminator."""
+    diff = unified_diff_str("a\nb\n", "a\nc\n", hunk_checksums=True)
+    assert diff[2].startswith("@@ -1,2 +1,2 @@ crc32=")
+    assert diff[2].endswith("\n")
+
+
+def test_apply_verifies_checksums():
+    """Test that a checksummed diff applies and a corrupted hunk is caught."""
+    diff = unified_diff(A, B, lineterm="", hunk_checksums=True)
+    assert apply_unified_diff(A, diff)[0] == B
+
+    corrupted = list(diff)
+    index = corrupted.index(" line 13")
+    corrupted[index] = " line 13 (edited)"
+    with pytest.raises(ValueError, match="hunk #2 does not match its checksum"):
+        apply_unified_diff(A, corrupted)
+
+
+def test_checksum_checked_before_fuzz():
+    """Test that a checksum mismatch is reported even when fuzz would apply it."""
+    diff = unified_diff(A, B, lineterm="", hunk_checksums=True)
+    corrupted = [" line 0 (edited)" if line == " line 0" else line for line in diff]
+    with pytest.raises(ValueError, match="hunk #1 does not match its checksum"):
+        apply_unified_diff(A, corrupted, fuzz=3)
+
+
+def test_invert_drops_checksums():
+    """Test that inverting a diff removes checksums of the other side."""
+    diff = unified_diff(A, B, lineterm="", hunk_checksums=True)
+    inverted = invert_unified_diff(diff)
+    assert not any("crc32" in line for line in inverted)
+    assert apply_unified_diff(B, inverted)[0] == A
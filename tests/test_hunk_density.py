@@ -0,0 +1,49 @@
+"""Test the hunk_density function."""
+
+import difflib
+
+import pytest
+from difflib_rs import hunk_density
+
+
+def python_hunk_density(a, b, n=3):
+    """Reference implementation on top of Python's get_grouped_opcodes."""
+    if not a and not b:
+        return 0.0
+    groups = difflib.SequenceMatcher(None, a, b).get_grouped_opcodes(n)
+    hunks = sum(1 for group in groups if any(tag != 'equal' for tag, *_ in group))
+    return hunks / (len(a) + len(b))
+
+
+def test_hunk_density_identical_and_empty():
+    """Test that unchanged inputs have zero density."""
+    a = [f"line {i}" for i in range(20)]
+    assert hunk_density(a, a) == 0.0
+    assert hunk_density([], []) == 0.0
+
+
+def test_hunk_density_single_change():
+    """Test one hunk over 20 lines of input."""
+    a = [f"line {i}" for i in range(10)]
+    b = a[:5] + ['changed'] + a[6:]
+    assert hunk_density(a, b) == 1 / 20
+
+
+def test_hunk_density_more_context_merges_hunks():
+    """Test that larger n groups scattered changes into fewer hunks."""
+    a = [f"line {i}" for i in range(100)]
+    b = [f"edit {i}" if i % 10 == 0 else line for i, line in enumerate(a)]
+    # Nine unchanged lines between changes: kept apart up to n=4
+    assert hunk_density(a, b, n=3) == 10 / 200
+    assert hunk_density(a, b, n=5) == 1 / 200
+
+
+@pytest.mark.parametrize("n", [0, 1, 3, 5])
+def test_hunk_density_matches_python(n):
+    """Test against hunks counted from Python's difflib."""
+    a = [f"line {i}" for i in range(200)]
+    b = a[:]
+    for i in (3, 4, 40, 47, 120, 199):
+        b[i] = f"changed {i}"
+    del b[80:85]
+    assert hunk_density(a, b, n) == python_hunk_density(a, b, n)
@@ -0,0 +1,31 @@
+"""Test listing only the hunk headers of a diff."""
+
+import pytest
+from difflib_rs import hunk_headers, unified_diff
+
+A = [f"line {i}" for i in range(30)]
+B = A[:2] + ["new"] + A[3:20] + A[21:] + ["end"]
+
+
+@pytest.mark.parametrize("n", [0, 1, 3, 10])
+def test_matches_unified_diff_headers(n):
+    """Test that the headers are those of the full unified diff."""
+    full = [line for line in unified_diff(A, B, n=n, lineterm="") if line.startswith("@@")]
+    assert hunk_headers(A, B, n=n) == full
+
+
+def test_default_context():
+    """Test the default of three context lines."""
+    assert hunk_headers(A, B) == ["@@ -1,6 +1,6 @@", "@@ -18,7 +18,6 @@", "@@ -28,3 +27,4 @@"]
+
+
+def test_no_changes():
+    """Test that equal inputs have no hunks."""
+    assert hunk_headers(A, A) == []
+    assert hunk_headers([], []) == []
+
+
+def test_empty_side():
+    """Test headers when one side is empty."""
+    assert hunk_headers([], ["a", "b"]) == ["@@ -0,0 +1,2 @@"]
+    assert hunk_headers(["a"], []) == ["@@ -1 +0,0 @@"]
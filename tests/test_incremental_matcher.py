@@ -0,0 +1,99 @@
+"""Test IncrementalMatcher."""
+
+import random
+
+import pytest
+from difflib_rs import IncrementalMatcher, get_opcodes
+
+
+def apply_opcodes(a, b, opcodes):
+    """Rebuild b from a and the opcodes, checking equal ranges."""
+    out = []
+    for tag, i1, i2, j1, j2 in opcodes:
+        if tag == 'equal':
+            assert a[i1:i2] == b[j1:j2]
+            out.extend(a[i1:i2])
+        else:
+            out.extend(b[j1:j2])
+    return out
+
+
+def test_initial_diff_matches_get_opcodes():
+    """Test that without edits the opcodes are those of get_opcodes."""
+    a = ['a', 'b', 'c', 'd']
+    b = ['a', 'x', 'c', 'd', 'e']
+    m = IncrementalMatcher(a, b)
+    assert m.get_opcodes() == get_opcodes(a, b)
+    assert m.get_matching_blocks()[-1] == (4, 5, 0)
+
+
+def test_edit_inside_equal_block():
+    """Test that typing inside an unchanged stretch splits it."""
+    a = [f"line {i}" for i in range(10)]
+    m = IncrementalMatcher(a, list(a))
+    m.edit(4, 5, ['typed'])
+    assert m.b == a[:4] + ['typed'] + a[5:]
+    assert m.get_opcodes() == [
+        ('equal', 0, 4, 0, 4),
+        ('replace', 4, 5, 4, 5),
+        ('equal', 5, 10, 5, 10),
+    ]
+
+
+def test_undo_restores_match():
+    """Test that reverting an edit makes the line equal again."""
+    a = [f"line {i}" for i in range(10)]
+    m = IncrementalMatcher(a, list(a))
+    m.edit(3, 3, ['new'])
+    m.edit(3, 4, [])
+    assert m.get_opcodes() == [('equal', 0, 10, 0, 10)]
+
+
+def test_from_previous_opcodes():
+    """Test picking up a diff from its opcodes."""
+    a = ['a', 'b', 'c']
+    b = ['a', 'c']
+    m = IncrementalMatcher(a, b, opcodes=get_opcodes(a, b))
+    assert m.get_opcodes() == get_opcodes(a, b)
+    m.edit(2, 2, ['d'])
+    assert m.get_opcodes() == [('equal', 0, 1, 0, 1), ('delete', 1, 2, 1, 1), ('equal', 2, 3, 1, 2), ('insert', 3, 3, 2, 3)]
+
+
+@pytest.mark.parametrize("opcodes", [
+    [('equal', 0, 1, 0, 1)],
+    [('equal', 0, 2, 0, 2)],
+    [('replace', 0, 1, 0, 1), ('equal', 2, 2, 1, 2)],
+])
+def test_invalid_opcodes(opcodes):
+    """Test that opcodes not matching a and b are rejected."""
+    with pytest.raises(ValueError, match="opcodes don't describe a diff"):
+        IncrementalMatcher(['a', 'b'], ['a', 'c'], opcodes=opcodes)
+
+
+def test_invalid_edit_range():
+    """Test that an edit outside b is rejected."""
+    m = IncrementalMatcher(['a'], ['a'])
+    with pytest.raises(ValueError):
+        m.edit(0, 2, [])
+    with pytest.raises(ValueError):
+        m.edit(1, 0, [])
+
+
+def test_random_edits_stay_valid():
+    """Test that random edit sequences always give a valid diff."""
+    rng = random.Random(3)
+    for _ in range(30):
+        a = [rng.choice('abcdef') for _ in range(rng.randrange(30))]
+        m = IncrementalMatcher(a, list(a))
+        b = list(a)
+        for _ in range(10):
+            start = rng.randrange(len(b) + 1)
+            end = rng.randrange(start, min(len(b), start + 3) + 1)
+            lines = [rng.choice('abcdefg') for _ in range(rng.randrange(3))]
+            m.edit(start, end, lines)
+            b[start:end] = lines
+            assert m.b == b
+            ops = m.get_opcodes()
+            assert apply_opcodes(a, b, ops) == b
+            if ops:
+                assert (ops[-1][2], ops[-1][4]) == (len(a), len(b))
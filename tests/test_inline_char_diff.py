@@ -0,0 +1,58 @@
+"""Test the inline_char_diff function."""
+
+import difflib
+
+import pytest
+from difflib_rs import inline_char_diff
+
+
+def python_inline_char_diff(a, b, delete=("[-", "-]"), insert=("{+", "+}")):
+    """Reference implementation on top of Python's SequenceMatcher."""
+    out = []
+    for tag, i1, i2, j1, j2 in difflib.SequenceMatcher(None, a, b).get_opcodes():
+        if tag == 'equal':
+            out.append(a[i1:i2])
+        if tag in ('delete', 'replace'):
+            out.append(delete[0] + a[i1:i2] + delete[1])
+        if tag in ('insert', 'replace'):
+            out.append(insert[0] + b[j1:j2] + insert[1])
+    return ''.join(out)
+
+
+def test_inline_char_diff_replace():
+    """Test that a replace emits the deletion before the insertion."""
+    assert inline_char_diff('foobarqux', 'foobazqux') == 'fooba[-r-]{+z+}qux'
+
+
+def test_inline_char_diff_identical():
+    """Test that equal lines come back unmarked."""
+    assert inline_char_diff('same line', 'same line') == 'same line'
+
+
+def test_inline_char_diff_pure_insert_and_delete():
+    """Test insertion-only and deletion-only changes."""
+    assert inline_char_diff('', 'new') == '{+new+}'
+    assert inline_char_diff('old', '') == '[-old-]'
+    assert inline_char_diff('ac', 'abc') == 'a{+b+}c'
+
+
+def test_inline_char_diff_custom_markers():
+    """Test configurable delimiters."""
+    result = inline_char_diff('cat', 'cut', delete=('<del>', '</del>'), insert=('<ins>', '</ins>'))
+    assert result == 'c<del>a</del><ins>u</ins>t'
+
+
+def test_inline_char_diff_unicode():
+    """Test that multi-byte characters are diffed as single characters."""
+    assert inline_char_diff('naïve café', 'naive cafe') == python_inline_char_diff('naïve café', 'naive cafe')
+
+
+@pytest.mark.parametrize("a,b", [
+    ("the quick brown fox", "the quack brown fax"),
+    ("def foo(x, y):", "def foo(x, y, z=None):"),
+    ("abcdefgh", "hgfedcba"),
+    ("x" * 250 + "y", "x" * 240 + "z"),
+])
+def test_inline_char_diff_matches_python(a, b):
+    """Test against Python's SequenceMatcher on the same characters."""
+    assert inline_char_diff(a, b) == python_inline_char_diff(a, b)
@@ -0,0 +1,87 @@
+"""Test invert_opcodes and invert_unified_diff."""
+
+import pytest
+import difflib
+import random
+from difflib_rs import unified_diff, invert_opcodes, invert_unified_diff
+
+
+def test_invert_opcodes_swaps_ranges_and_tags():
+    """Test that indices are swapped and insert/delete trade places."""
+    opcodes = [
+        ('equal', 0, 1, 0, 1),
+        ('delete', 1, 2, 1, 1),
+        ('insert', 2, 2, 1, 3),
+        ('replace', 2, 4, 3, 4),
+    ]
+    assert invert_opcodes(opcodes) == [
+        ('equal', 0, 1, 0, 1),
+        ('insert', 1, 1, 1, 2),
+        ('delete', 1, 3, 2, 2),
+        ('replace', 3, 4, 2, 4),
+    ]
+
+
+def test_invert_opcodes_describes_reverse_edit():
+    """Test that inverted opcodes rebuild a from b."""
+    random.seed(7)
+    for _ in range(20):
+        a = [random.choice('abcde') for _ in range(random.randint(0, 30))]
+        b = [random.choice('abcde') for _ in range(random.randint(0, 30))]
+        opcodes = difflib.SequenceMatcher(None, a, b).get_opcodes()
+
+        rebuilt = []
+        for tag, i1, i2, j1, j2 in invert_opcodes(opcodes):
+            if tag == 'equal':
+                assert b[i1:i2] == a[j1:j2]
+            rebuilt.extend(a[j1:j2])
+        assert rebuilt == a
+
+
+def test_invert_opcodes_unknown_tag():
+    """Test that an unknown tag raises ValueError."""
+    with pytest.raises(ValueError):
+        invert_opcodes([('swap', 0, 1, 0, 1)])
+
+
+def test_invert_unified_diff_matches_reverse_diff():
+    """Test that inverting a patch gives the diff of b against a."""
+    a = ['one', 'two', 'three', 'four', 'five']
+    b = ['zero', 'one', 'tree', 'four', 'five', 'six']
+
+    patch = unified_diff(a, b, 'a.txt', 'b.txt', '2024-01-01', '2024-01-02')
+    expected = list(difflib.unified_diff(b, a, 'b.txt', 'a.txt', '2024-01-02', '2024-01-01'))
+    assert invert_unified_diff(patch) == expected
+
+
+@pytest.mark.parametrize("n", [0, 1, 3])
+def test_invert_unified_diff_round_trip(n):
+    """Test that inverting twice returns the original patch."""
+    random.seed(n)
+    for _ in range(20):
+        a = [f"line{random.randint(0, 15)}" for _ in range(40)]
+        b = [f"line{random.randint(0, 15)}" for _ in range(40)]
+        patch = unified_diff(a, b, 'a', 'b', n=n)
+        assert invert_unified_diff(invert_unified_diff(patch)) == patch
+
+
+def test_invert_unified_diff_lines_that_look_like_headers():
+    """Test that removed lines starting with '--' stay inside their hunk."""
+    a = ['-- comment', 'keep']
+    b = ['++ comment', 'keep']
+    patch = unified_diff(a, b, 'a', 'b', lineterm='')
+    assert patch[3] == '--- comment'
+
+    inverted = invert_unified_diff(patch)
+    assert inverted == list(difflib.unified_diff(b, a, 'b', 'a', lineterm=''))
+
+
+def test_invert_unified_diff_empty():
+    """Test that an empty patch inverts to an empty patch."""
+    assert invert_unified_diff([]) == []
+
+
+def test_invert_unified_diff_malformed_header():
+    """Test that a broken hunk header raises ValueError."""
+    with pytest.raises(ValueError):
+        invert_unified_diff(['--- a\n', '+++ b\n', '@@ bogus @@\n'])
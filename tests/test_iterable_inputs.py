@@ -0,0 +1,58 @@
+"""Test diffing iterables of lines other than lists."""
+
+import io
+
+import pytest
+from difflib_rs import get_grouped_opcodes, get_opcodes, unified_diff, unified_diff_with_stats, walk_opcodes
+
+A = ["one\n", "two\n", "three\n", "four\n"]
+B = ["one\n", "2\n", "three\n", "four\n", "five\n"]
+
+
+def test_generators_match_lists():
+    """Test that generators give the same diff as lists."""
+    expected = unified_diff(A, B)
+    assert unified_diff((line for line in A), (line for line in B)) == expected
+    assert unified_diff(iter(A), B) == expected
+    assert unified_diff(tuple(A), tuple(B)) == expected
+
+
+def test_file_objects():
+    """Test that open text files can be diffed directly."""
+    a, b = io.StringIO("".join(A)), io.StringIO("".join(B))
+    assert unified_diff(a, b, "a", "b") == unified_diff(A, B, "a", "b")
+
+
+def test_opcode_functions_accept_iterables():
+    """Test that the opcode functions take iterables too."""
+    assert get_opcodes(iter(A), iter(B)) == get_opcodes(A, B)
+    assert get_grouped_opcodes(iter(A), iter(B), n=1) == get_grouped_opcodes(A, B, n=1)
+    assert unified_diff_with_stats(iter(A), iter(B)) == unified_diff_with_stats(A, B)
+    seen = []
+    walk_opcodes(iter(A), iter(B), lambda *op: seen.append(op))
+    assert seen == get_opcodes(A, B)
+
+
+def test_generators_are_consumed_once():
+    """Test that each input is read exactly once."""
+    pulled = []
+
+    def lines(source):
+        for line in source:
+            pulled.append(line)
+            yield line
+
+    unified_diff(lines(A), lines(B))
+    assert pulled == A + B
+
+
+def test_str_is_rejected():
+    """Test that a str is not mistaken for an iterable of one-char lines."""
+    with pytest.raises(TypeError, match="not a str"):
+        unified_diff("abc", ["a"])
+
+
+def test_non_str_items_are_rejected():
+    """Test that items must be str."""
+    with pytest.raises(TypeError):
+        unified_diff(iter([1, 2]), ["a"])
@@ -0,0 +1,70 @@
+"""Test the json_patch function."""
+
+import json
+import random
+
+import pytest
+from difflib_rs import json_patch
+
+
+def apply_json_patch(doc, patch):
+    """Apply RFC 6902 operations with /N paths to a list."""
+    doc = list(doc)
+    for op in json.loads(patch):
+        index = int(op['path'][1:])
+        if op['op'] == 'add':
+            assert 0 <= index <= len(doc)
+            doc.insert(index, op['value'])
+        elif op['op'] == 'remove':
+            assert 0 <= index < len(doc)
+            del doc[index]
+        else:
+            assert op['op'] == 'replace' and 0 <= index < len(doc)
+            doc[index] = op['value']
+    return doc
+
+
+def test_json_patch_operations():
+    """Test the operations and paths of a small patch."""
+    a = ['a', 'b', 'c', 'd', 'e']
+    b = ['a', 'x', 'c', 'e', 'f', 'g']
+    assert json.loads(json_patch(a, b)) == [
+        {'op': 'add', 'path': '/5', 'value': 'f'},
+        {'op': 'add', 'path': '/6', 'value': 'g'},
+        {'op': 'remove', 'path': '/3'},
+        {'op': 'replace', 'path': '/1', 'value': 'x'},
+    ]
+    assert json_patch(a, a) == '[]'
+    assert json_patch([], []) == '[]'
+
+
+def test_json_patch_uneven_replace():
+    """Test removes in descending order after a shrinking replace."""
+    a = ['a', 'b', 'c', 'd', 'z']
+    b = ['x', 'z']
+    patch = json.loads(json_patch(a, b))
+    assert patch == [
+        {'op': 'replace', 'path': '/0', 'value': 'x'},
+        {'op': 'remove', 'path': '/3'},
+        {'op': 'remove', 'path': '/2'},
+        {'op': 'remove', 'path': '/1'},
+    ]
+    assert apply_json_patch(a, json_patch(a, b)) == b
+
+
+def test_json_patch_escaping():
+    """Test that values with quotes, backslashes and control characters round-trip."""
+    a = ['plain\n']
+    b = ['say "hi"\n', 'back\\slash\ttab\r\n', '\x00\x1f\x7f bell\x07', 'héllo ✓ 😀']
+    patch = json_patch(a, b)
+    assert apply_json_patch(a, patch) == b
+    assert '\n' not in patch
+
+
+@pytest.mark.parametrize("seed", range(30))
+def test_json_patch_applies(seed):
+    """Test that random patches turn a into b."""
+    rng = random.Random(seed)
+    a = [str(rng.randint(0, 9)) for _ in range(rng.randint(0, 40))]
+    b = [str(rng.randint(0, 9)) for _ in range(rng.randint(0, 40))]
+    assert apply_json_patch(a, json_patch(a, b)) == b
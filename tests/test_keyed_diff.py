@@ -0,0 +1,34 @@
+"""Test the keyed_diff function."""
+
+from difflib_rs import diff_records, inline_char_diff, keyed_diff
+
+
+def test_keyed_diff_translations():
+    """Test added, removed and changed keys with inline diffs."""
+    a = [('menu.save', 'Save file'), ('menu.open', 'Open'), ('menu.quit', 'Quit')]
+    b = [('menu.open', 'Open'), ('menu.save', 'Save all'), ('menu.help', 'Help')]
+    assert keyed_diff(a, b) == {
+        'added': [('menu.help', 'Help')],
+        'removed': [('menu.quit', 'Quit')],
+        'changed': [('menu.save', 'Save file', 'Save all', 'Save [-fi-]{+a+}l[-e-]{+l+}')],
+    }
+
+
+def test_keyed_diff_order_independent():
+    """Test that record order doesn't matter and dicts are accepted."""
+    a = {f"k{i}": f"text {i}" for i in range(30)}
+    b = {f"k{i}": f"text {i * 2}" for i in range(10, 40)}
+    shuffled = list(reversed(list(a.items())))
+    assert keyed_diff(shuffled, b) == keyed_diff(a, b)
+    records = diff_records(a, b)
+    result = keyed_diff(a, b)
+    assert result['added'] == records['added']
+    assert result['removed'] == records['removed']
+    assert [change[:3] for change in result['changed']] == records['changed']
+    assert all(inline == inline_char_diff(old, new) for _, old, new, inline in result['changed'])
+
+
+def test_keyed_diff_repeated_and_empty():
+    """Test that a repeated key keeps its last text."""
+    assert keyed_diff([('k', 'one'), ('k', 'two')], {'k': 'two'}) == {'added': [], 'removed': [], 'changed': []}
+    assert keyed_diff([], []) == {'added': [], 'removed': [], 'changed': []}
@@ -0,0 +1,51 @@
+"""Test line edits for reconstructing text."""
+
+import pytest
+from difflib_rs import get_opcodes, line_edits
+
+
+def apply(a, edits):
+    lines = list(a)
+    for start, end, replacement in reversed(edits):
+        lines[start:end] = replacement
+    return lines
+
+
+@pytest.mark.parametrize(
+    "a, b",
+    [
+        ([], []),
+        ([], ["x", "y"]),
+        (["x", "y"], []),
+        (["a", "b", "c"], ["a", "b", "c"]),
+        (["a", "b", "c"], ["a", "x", "c"]),
+        (["a", "b", "c", "d"], ["x", "b", "d", "e"]),
+        (["a", "b"], ["b", "a", "b", "a"]),
+        ([str(i) for i in range(20)], [str(i) for i in range(20) if i % 3] + ["end"]),
+    ],
+)
+def test_applying_in_reverse_gives_b(a, b):
+    """Test that applying the edits bottom-up reconstructs b."""
+    assert apply(a, line_edits(a, b)) == b
+
+
+def test_edit_kinds():
+    """Test that inserts, deletes and replaces map to ranges and lines."""
+    a = ["a", "b", "c", "d"]
+    b = ["new", "a", "c", "D"]
+    assert line_edits(a, b) == [(0, 0, ["new"]), (1, 2, []), (3, 4, ["D"])]
+
+
+def test_one_edit_per_changed_region():
+    """Test that edits match the non-equal opcodes and are sorted."""
+    a = ["1", "2", "3", "4", "5", "6"]
+    b = ["1", "x", "3", "y", "z", "6", "7"]
+    edits = line_edits(a, b)
+    changes = [op for op in get_opcodes(a, b) if op[0] != "equal"]
+    assert [(start, end) for start, end, _ in edits] == [(i1, i2) for _, i1, i2, _, _ in changes]
+    assert all(edits[k][1] < edits[k + 1][0] for k in range(len(edits) - 1))
+
+
+def test_no_edits_for_equal_sequences():
+    """Test that equal sequences need no edits."""
+    assert line_edits(["a", "b"], ["a", "b"]) == []
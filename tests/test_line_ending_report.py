@@ -0,0 +1,21 @@
+"""Test the line_ending_report function."""
+
+from difflib_rs import line_ending_report, split_lines
+
+
+def test_line_ending_kinds():
+    """Test each kind of line break."""
+    lines = ['lf\n', 'crlf\r\n', 'cr\r', 'ff\x0c', 'last']
+    assert line_ending_report(lines) == ['lf', 'crlf', 'cr', 'other', 'none']
+
+
+def test_line_ending_report_of_split_text():
+    """Test mixed endings in text split with keepends."""
+    text = "one\ntwo\r\nthree\rfour"
+    assert line_ending_report(split_lines(text, keepends=True)) == ['lf', 'crlf', 'cr', 'none']
+
+
+def test_line_ending_report_empty():
+    """Test that no lines give an empty report."""
+    assert line_ending_report([]) == []
+    assert line_ending_report(['']) == ['none']
@@ -0,0 +1,62 @@
+"""Test the show_line_numbers option of the unified diff functions."""
+
+from difflib_rs import unified_diff, unified_diff_str, unified_diff_with_stats
+
+
+def test_line_numbers_columns():
+    """Test that context shows both numbers, removals old and additions new."""
+    a = ['one', 'two', 'three']
+    b = ['one', '2', 'three', 'four']
+    diff = unified_diff(a, b, 'a', 'b', lineterm='', show_line_numbers=True)
+    assert diff == [
+        '--- a',
+        '+++ b',
+        '@@ -1,3 +1,4 @@',
+        '1 1  one',
+        '2   -two',
+        '  2 +2',
+        '3 3  three',
+        '  4 +four',
+    ]
+
+
+def test_line_numbers_follow_hunks():
+    """Test numbering restarts from each hunk header, with a wide column."""
+    a = [f"line {i}" for i in range(1, 121)]
+    b = list(a)
+    b[4] = 'changed'
+    b[109] = 'also changed'
+    diff = unified_diff(a, b, lineterm='', n=1, show_line_numbers=True)
+    assert diff[2:] == [
+        '@@ -4,3 +4,3 @@',
+        '  4   4  line 4',
+        '  5     -line 5',
+        '      5 +changed',
+        '  6   6  line 6',
+        '@@ -109,3 +109,3 @@',
+        '109 109  line 109',
+        '110     -line 110',
+        '    110 +also changed',
+        '111 111  line 111',
+    ]
+
+
+def test_line_numbers_keep_line_endings():
+    """Test that content keeps its line ending after the numbers."""
+    diff = unified_diff_str('x\ny\n', 'x\nz\n', keepends=True, show_line_numbers=True)
+    assert diff[2:] == ['@@ -1,2 +1,2 @@\n', '1 1  x\n', '2   -y\n', '  2 +z\n']
+
+
+def test_line_numbers_with_stats():
+    """Test that stats are unaffected by the numbering."""
+    a = ['a', 'b']
+    b = ['a', 'c', 'd']
+    lines, stats = unified_diff_with_stats(a, b, lineterm='', show_line_numbers=True)
+    assert stats == (2, 1, 1)
+    assert lines[3:] == ['1 1  a', '2   -b', '  2 +c', '  3 +d']
+
+
+def test_line_numbers_off_by_default():
+    """Test that the default output is unchanged."""
+    a, b = ['a', 'b'], ['a', 'c']
+    assert unified_diff(a, b, lineterm='')[3:] == [' a', '-b', '+c']
@@ -0,0 +1,64 @@
+"""Test the map_diff function."""
+
+from difflib_rs import diff_records, map_diff
+
+
+def test_map_diff_in_key_order():
+    """Test every key reported in the interleaved key order."""
+    a = {'name': 'app', 'host': 'localhost', 'port': '8080', 'debug': 'false'}
+    b = {'name': 'app', 'host': 'localhost', 'workers': '4', 'port': '9090'}
+    assert map_diff(a, b) == [
+        ('unchanged', 'name', 'app', 'app', None),
+        ('unchanged', 'host', 'localhost', 'localhost', None),
+        ('added', 'workers', None, '4', None),
+        ('changed', 'port', '8080', '9090', None),
+        ('removed', 'debug', 'false', None, None),
+    ]
+
+
+def test_map_diff_pairs_and_duplicates():
+    """Test lists of pairs, where a repeated key keeps its last value."""
+    a = [('x', '1'), ('y', '2'), ('x', '3')]
+    b = {'x': '3', 'y': '2'}
+    assert map_diff(a, b) == [
+        ('unchanged', 'x', '3', '3', None),
+        ('unchanged', 'y', '2', '2', None),
+    ]
+    assert map_diff([], []) == []
+
+
+def test_map_diff_moved_key_reported_once():
+    """Test that a reordered key appears once, where it comes first."""
+    a = {'a': '1', 'b': '2', 'c': '3'}
+    b = {'c': '30', 'a': '1', 'b': '2'}
+    changes = map_diff(a, b)
+    assert [change[1] for change in changes] == ['c', 'a', 'b']
+    assert changes[0] == ('changed', 'c', '3', '30', None)
+
+
+def test_map_diff_agrees_with_diff_records():
+    """Test that the keys' tags match diff_records on the same maps."""
+    a = {f"k{i}": str(i) for i in range(20)}
+    b = {f"k{i}": str(i * (i % 3)) for i in range(5, 25)}
+    changes = map_diff(a, b)
+    records = diff_records(a, b)
+    assert sorted(c[1:3] for c in changes if c[0] == 'removed') == records['removed']
+    assert sorted((c[1], c[3]) for c in changes if c[0] == 'added') == records['added']
+    assert sorted(c[1:4] for c in changes if c[0] == 'changed') == records['changed']
+    assert len(changes) == len(set(a) | set(b))
+
+
+def test_map_diff_value_diff():
+    """Test the nested diff of multiline values."""
+    a = {'script': 'set -e\nmake\nmake test\n', 'name': 'ci'}
+    b = {'script': 'set -e\nmake all\nmake test\n', 'name': 'build'}
+    assert map_diff(a, b)[0][4] is None
+    script, name = map_diff(a, b, value_context=0)
+    assert script[4] == (
+        "--- script\n"
+        "+++ script\n"
+        "@@ -2 +2 @@\n"
+        "-make\n"
+        "+make all\n"
+    )
+    assert name == ('changed', 'name', 'ci', 'build', None)
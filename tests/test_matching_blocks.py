@@ -0,0 +1,41 @@
+"""Test get_matching_blocks and its include_sentinel option."""
+
+import difflib
+import random
+
+from difflib_rs import IncrementalMatcher, SequenceMatcher, get_matching_blocks
+
+
+def test_matches_difflib_with_sentinel():
+    """Test that the default output equals difflib's, sentinel included."""
+    rng = random.Random(11)
+    for _ in range(50):
+        a = [rng.choice('abcd') for _ in range(rng.randrange(12))]
+        b = [rng.choice('abcd') for _ in range(rng.randrange(12))]
+        expected = [tuple(block) for block in difflib.SequenceMatcher(None, a, b).get_matching_blocks()]
+        assert get_matching_blocks(a, b) == expected
+        assert get_matching_blocks(a, b, include_sentinel=False) == expected[:-1]
+
+
+def test_without_sentinel_has_no_empty_blocks():
+    """Test that without the sentinel every block has a size."""
+    assert get_matching_blocks(['a', 'b'], ['a', 'c']) == [(0, 0, 1), (2, 2, 0)]
+    assert get_matching_blocks(['a', 'b'], ['a', 'c'], include_sentinel=False) == [(0, 0, 1)]
+    assert get_matching_blocks([], [], include_sentinel=False) == []
+    assert get_matching_blocks(['x'], ['y'], include_sentinel=False) == []
+
+
+def test_classes_take_include_sentinel():
+    """Test the option on SequenceMatcher and IncrementalMatcher."""
+    a, b = ['a', 'b', 'c'], ['a', 'c']
+    with SequenceMatcher() as m:
+        assert m.get_matching_blocks(a, b) == get_matching_blocks(a, b)
+        assert m.get_matching_blocks(a, b, include_sentinel=False) == [(0, 0, 1), (2, 1, 1)]
+    incremental = IncrementalMatcher(a, b)
+    assert incremental.get_matching_blocks() == [(0, 0, 1), (2, 1, 1), (3, 2, 0)]
+    assert incremental.get_matching_blocks(include_sentinel=False) == [(0, 0, 1), (2, 1, 1)]
+
+
+def test_empty_inputs_only_sentinel():
+    """Test that two empty sequences give only the sentinel, like difflib."""
+    assert get_matching_blocks([], []) == [(0, 0, 0)]
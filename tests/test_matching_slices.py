@@ -0,0 +1,31 @@
+"""Test the matching_slices function."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import matching_slices
+
+
+def test_matching_slices_basic():
+    """Test that each block's lines are returned."""
+    a = ['a', 'b', 'c', 'd', 'e']
+    b = ['a', 'b', 'x', 'd', 'e']
+    assert matching_slices(a, b) == [['a', 'b'], ['d', 'e']]
+
+
+def test_matching_slices_omits_sentinel():
+    """Test that no empty block is returned."""
+    assert matching_slices([], []) == []
+    assert matching_slices(['a'], ['b']) == []
+    assert matching_slices(['a'], ['a']) == [['a']]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_matching_slices_matches_python(seed):
+    """Test against the blocks of Python's SequenceMatcher."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 10)}" for _ in range(random.randint(0, 50))]
+    b = [f"line{random.randint(0, 10)}" for _ in range(random.randint(0, 50))]
+    blocks = difflib.SequenceMatcher(None, a, b).get_matching_blocks()
+    assert matching_slices(a, b) == [a[i:i + k] for i, _, k in blocks if k]
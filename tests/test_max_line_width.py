@@ -0,0 +1,75 @@
+"""Test truncating long lines in unified diff output."""
+
+from difflib_rs import unified_diff, unified_diff_str, unified_diff_with_stats
+
+
+def test_long_lines_are_truncated():
+    """Test that content past max_line_width is replaced by an ellipsis."""
+    a = ["short", "x" * 50, "same"]
+    b = ["short", "y" * 50, "same"]
+    diff = unified_diff(a, b, lineterm="", max_line_width=10)
+    assert diff == [
+        "--- ",
+        "+++ ",
+        "@@ -1,3 +1,3 @@",
+        " short",
+        "-" + "x" * 10 + "…",
+        "+" + "y" * 10 + "…",
+        " same",
+    ]
+
+
+def test_matching_uses_full_lines():
+    """Test that lines equal up to the width still show as changed."""
+    a = ["prefix" * 10 + "a"]
+    b = ["prefix" * 10 + "b"]
+    diff = unified_diff(a, b, lineterm="", max_line_width=6)
+    assert diff[3:] == ["-prefix…", "+prefix…"]
+
+
+def test_lines_within_width_are_unchanged():
+    """Test that a line exactly max_line_width long is not marked."""
+    a = ["abcd", "keep"]
+    b = ["abce", "keep"]
+    assert unified_diff(a, b, max_line_width=4) == unified_diff(a, b)
+
+
+def test_truncates_on_char_boundary():
+    """Test that multi-byte characters are never split."""
+    a = ["héllo wörld ☃☃☃"]
+    b = ["ünïcödé ☃"]
+    diff = unified_diff(a, b, lineterm="", max_line_width=3)
+    assert diff[3:] == ["-hél…", "+ünï…"]
+
+
+def test_line_endings_are_kept():
+    """Test that the line break stays after the ellipsis."""
+    diff = unified_diff_str("x" * 20 + "\nsame\n", "y" * 20 + "\nsame\n", keepends=True, max_line_width=5)
+    assert "-xxxxx…\n" in diff
+    assert "+yyyyy…\n" in diff
+    assert " same\n" in diff
+    crlf = unified_diff_str("x" * 20 + "\r\n", "y\r\n", keepends=True, max_line_width=5)
+    assert "-xxxxx…\r\n" in crlf
+    assert "+y\r\n" in crlf
+
+
+def test_headers_are_not_truncated():
+    """Test that file headers and hunk headers keep their full length."""
+    diff = unified_diff(["a"], ["b"], fromfile="a/" + "d" * 30, tofile="b/file", lineterm="", max_line_width=2)
+    assert diff[0] == "--- a/" + "d" * 30
+    assert diff[2] == "@@ -1 +1 @@"
+
+
+def test_zero_width():
+    """Test that a zero width keeps only the marker."""
+    diff = unified_diff(["a"], ["b"], lineterm="", max_line_width=0)
+    assert diff[3:] == ["-…", "+…"]
+
+
+def test_stats_count_full_diff():
+    """Test that truncation doesn't change the reported counts."""
+    a = ["x" * 100, "y" * 100]
+    b = ["z" * 100]
+    diff, stats = unified_diff_with_stats(a, b, lineterm="", max_line_width=8)
+    assert stats == (1, 2, 1)
+    assert all(len(line) <= 10 for line in diff[3:])
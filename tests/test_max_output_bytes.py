@@ -0,0 +1,56 @@
+"""Test the max_output_bytes option."""
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_str, unified_diff_with_stats
+
+A = [f"line {i}\n" for i in range(30)]
+B = A[:2] + ['first\n'] + A[3:15] + ['second\n'] + A[16:27] + ['third\n'] + A[28:]
+
+
+def size(lines):
+    return sum(len(line.encode('utf-8')) for line in lines)
+
+
+def test_unlimited_by_default_and_when_it_fits():
+    """Test that output within the budget is unchanged."""
+    full = unified_diff(A, B, 'a', 'b')
+    assert unified_diff(A, B, 'a', 'b', max_output_bytes=size(full)) == full
+    assert unified_diff(A, A, max_output_bytes=0) == []
+
+
+@pytest.mark.parametrize("budget", [0, 10, 50, 120, 200, 250])
+def test_truncated_within_budget(budget):
+    """Test that the kept lines and notice fit and are a prefix of the diff."""
+    full = unified_diff(A, B, 'a', 'b')
+    diff = unified_diff(A, B, 'a', 'b', max_output_bytes=budget)
+    *kept, notice = diff
+    assert kept == full[:len(kept)]
+    omitted = full[len(kept):]
+    hunks = sum(line.startswith('@@') for line in omitted)
+    assert notice == f"@@ output truncated: {len(omitted)} lines omitted ({hunks} hunks) @@\n"
+    if size([notice]) <= budget:
+        assert size(diff) <= budget
+    else:
+        assert kept == []
+
+
+def test_counts_bytes_not_characters():
+    """Test that multi-byte characters count by their UTF-8 length."""
+    a, b = ['x\n'], ['é' * 40 + '\n']
+    full = unified_diff(a, b, lineterm='')
+    budget = size(full) - 1
+    assert len(''.join(full)) <= budget
+    diff = unified_diff(a, b, lineterm='', max_output_bytes=budget)
+    assert diff[:-1] == full[:-1]
+    assert diff[-1] == "@@ output truncated: 1 lines omitted (0 hunks) @@"
+
+
+def test_str_and_stats():
+    """Test the option through unified_diff_str and unified_diff_with_stats."""
+    a, b = ''.join(A), ''.join(B)
+    diff = unified_diff_str(a, b, max_output_bytes=100)
+    assert size(diff) <= 100
+    assert diff[-1].startswith('@@ output truncated:')
+    capped, stats = unified_diff_with_stats(A, B, max_output_bytes=100)
+    assert capped == unified_diff(A, B, max_output_bytes=100)
+    assert stats == unified_diff_with_stats(A, B)[1]
@@ -0,0 +1,54 @@
+"""Test the merge_patches function."""
+
+import pytest
+from difflib_rs import merge_patches, unified_diff
+
+
+BASE = [f"line {i}\n" for i in range(30)]
+
+
+def _patch(changed):
+    return unified_diff(BASE, changed, 'a', 'b')
+
+
+def test_non_overlapping_patches():
+    """Test that hunks touching different lines are all applied."""
+    first = BASE[:3] + ['first\n'] + BASE[4:]
+    second = BASE[:20] + ['second\n', 'more\n'] + BASE[21:]
+    merged, conflicts = merge_patches(BASE, _patch(first), _patch(second))
+    assert conflicts == []
+    assert merged == BASE[:3] + ['first\n'] + BASE[4:20] + ['second\n', 'more\n'] + BASE[21:]
+
+
+def test_same_change_in_both():
+    """Test that an identical change from both patches isn't a conflict."""
+    changed = BASE[:10] + ['same\n'] + BASE[11:]
+    assert merge_patches(BASE, _patch(changed), _patch(changed)) == (changed, [])
+
+
+def test_conflicting_patches():
+    """Test conflict markers and the base range of the conflict."""
+    first = BASE[:10] + ['first\n'] + BASE[11:]
+    second = BASE[:10] + ['second\n'] + BASE[12:]
+    merged, conflicts = merge_patches(BASE, _patch(first), _patch(second))
+    assert conflicts == [(10, 12)]
+    assert merged == BASE[:10] + [
+        '<<<<<<< ours\n', 'first\n', 'line 11\n', '=======\n', 'second\n', '>>>>>>> theirs\n',
+    ] + BASE[12:]
+
+
+def test_conflicting_insertions():
+    """Test that different insertions at one place conflict over an empty range."""
+    first = BASE[:5] + ['one\n'] + BASE[5:]
+    second = BASE[:5] + ['two\n'] + BASE[5:]
+    merged, conflicts = merge_patches(BASE, _patch(first), _patch(second))
+    assert conflicts == [(5, 5)]
+    assert merged[5:10] == ['<<<<<<< ours\n', 'one\n', '=======\n', 'two\n', '>>>>>>> theirs\n']
+
+
+def test_patch_that_does_not_apply():
+    """Test that a patch of another base is reported."""
+    other = ['x\n', 'y\n']
+    patch = unified_diff(other, ['x\n', 'z\n'])
+    with pytest.raises(ValueError, match="hunk #1 does not apply"):
+        merge_patches(BASE, patch, _patch(BASE))
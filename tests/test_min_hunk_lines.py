@@ -0,0 +1,56 @@
+"""Test the min_hunk_lines option."""
+
+import random
+
+import pytest
+from difflib_rs import apply_unified_diff, unified_diff, unified_diff_str, unified_diff_with_stats
+
+A = [f"line {i}\n" for i in range(60)]
+B = list(A)
+for i in (5, 13, 21, 45, 46, 47, 48):
+    B[i] = f"changed {i}\n"
+
+
+def headers(diff):
+    return [line for line in diff if line.startswith('@@')]
+
+
+def test_small_hunks_merged():
+    """Test that small hunks merge, absorbing the lines between, until large."""
+    assert headers(unified_diff(A, B, n=1)) == [
+        '@@ -5,3 +5,3 @@\n', '@@ -13,3 +13,3 @@\n', '@@ -21,3 +21,3 @@\n', '@@ -45,6 +45,6 @@\n',
+    ]
+    diff = unified_diff(A, B, n=1, min_hunk_lines=6)
+    assert headers(diff) == ['@@ -5,19 +5,19 @@\n', '@@ -45,6 +45,6 @@\n']
+    assert [line for line in diff[3:16]] == [
+        ' line 4\n', '-line 5\n', '+changed 5\n', ' line 6\n', ' line 7\n', ' line 8\n',
+        ' line 9\n', ' line 10\n', ' line 11\n', ' line 12\n', '-line 13\n', '+changed 13\n', ' line 14\n',
+    ]
+
+
+def test_no_effect_when_hunks_are_large_enough():
+    """Test that the option leaves hunks of at least min_hunk_lines alone."""
+    assert unified_diff(A, B, n=1, min_hunk_lines=4) == unified_diff(A, B, n=1)
+    assert unified_diff(A, B, n=0, min_hunk_lines=2) == unified_diff(A, B, n=0)
+    assert unified_diff(A, A, min_hunk_lines=100) == []
+
+
+@pytest.mark.parametrize("seed", range(20))
+def test_merged_hunks_apply(seed):
+    """Test that merged hunks validate and still apply to the old lines."""
+    rng = random.Random(seed)
+    a = [f"{rng.randint(0, 30)}\n" for _ in range(rng.randint(0, 80))]
+    b = [line for line in a if rng.random() > 0.1]
+    for _ in range(rng.randint(0, 8)):
+        b.insert(rng.randint(0, len(b)), f"new {rng.random()}\n")
+    for min_hunk_lines in (0, 3, 8, 50):
+        diff = unified_diff(a, b, n=rng.randint(0, 3), min_hunk_lines=min_hunk_lines, validate=True)
+        assert apply_unified_diff(a, diff)[0] == b
+
+
+def test_str_and_stats():
+    """Test the option through unified_diff_str and unified_diff_with_stats."""
+    diff = unified_diff_str(''.join(A), ''.join(B), n=1, min_hunk_lines=6)
+    assert len(headers(diff)) == 2
+    merged, (added, removed, hunks) = unified_diff_with_stats(A, B, n=1, min_hunk_lines=6)
+    assert (added, removed, hunks) == (7, 7, 2)
@@ -0,0 +1,78 @@
+"""Test the neighbor_tiebreak option, and its diff quality against the
+default on repetitive code."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import get_grouped_opcodes, get_opcodes, unified_diff, verify_diff
+
+
+def _paired_similarity(a, b, opcodes):
+    """Mean character similarity of the line pairs of replace opcodes."""
+    ratios = [
+        difflib.SequenceMatcher(None, a[i], b[j]).ratio()
+        for tag, i1, i2, j1, j2 in opcodes if tag == 'replace'
+        for i, j in zip(range(i1, i2), range(j1, j2))
+    ]
+    return sum(ratios) / len(ratios)
+
+
+# Only the second of two similar blocks is kept and edited: every closing
+# brace ties, and the earliest one pairs the edit with the wrong block.
+BLOCKS = (
+    ['    if x:\n', '        foo(1)\n', '    }\n', '    if y:\n', '        bar(2)\n', '    }\n'],
+    ['    if y:\n', '        bar(3)\n', '    }\n'],
+)
+STATEMENTS = (
+    ['}\n', 'alpha = compute(1)\n', '}\n', 'beta = compute(2)\n', '}\n'],
+    ['}\n', 'beta = compute(3)\n', '}\n'],
+)
+
+
+def test_blocks_anchor_next_to_edit():
+    """Test that the tiebreak keeps the edited block together."""
+    a, b = BLOCKS
+    assert get_opcodes(a, b) == [('replace', 0, 2, 0, 2), ('equal', 2, 3, 2, 3), ('delete', 3, 6, 3, 3)]
+    assert get_opcodes(a, b, neighbor_tiebreak=True) == [
+        ('delete', 0, 3, 0, 0), ('equal', 3, 4, 0, 1), ('replace', 4, 5, 1, 2), ('equal', 5, 6, 2, 3),
+    ]
+
+
+@pytest.mark.parametrize("a,b,removed,added", [
+    (*BLOCKS, '-        bar(2)\n', '+        bar(3)\n'),
+    (*STATEMENTS, '-beta = compute(2)\n', '+beta = compute(3)\n'),
+])
+def test_tiebreak_pairs_more_similar_lines(a, b, removed, added):
+    """Test that changed lines are paired with more similar ones."""
+    default = get_opcodes(a, b)
+    tiebroken = get_opcodes(a, b, neighbor_tiebreak=True)
+    assert _paired_similarity(a, b, tiebroken) > _paired_similarity(a, b, default)
+    diff = unified_diff(a, b, neighbor_tiebreak=True)
+    assert diff.index(removed) + 1 == diff.index(added)
+
+
+@pytest.mark.parametrize("seed", range(25))
+def test_tiebreak_opcodes_valid(seed):
+    """Test that opcodes with the tiebreak still describe a -> b."""
+    rng = random.Random(seed)
+    pool = ['}\n', '\n', 'return x\n', 'x = 1\n', 'x = 2\n', 'y = 1\n']
+    a = [rng.choice(pool) for _ in range(rng.randint(0, 40))]
+    b = [rng.choice(pool) for _ in range(rng.randint(0, 40))]
+    rebuilt = []
+    (i, j) = (0, 0)
+    for tag, i1, i2, j1, j2 in get_opcodes(a, b, neighbor_tiebreak=True):
+        assert (i1, j1) == (i, j)
+        if tag == 'equal':
+            assert a[i1:i2] == b[j1:j2]
+        rebuilt.extend(b[j1:j2])
+        (i, j) = (i2, j2)
+    assert (i, j) == (len(a), len(b)) and rebuilt == b
+    assert verify_diff(a, b, neighbor_tiebreak=True)
+
+
+def test_default_unchanged():
+    """Test that without the flag the opcodes still match CPython."""
+    a, b = STATEMENTS
+    assert get_opcodes(a, b) == difflib.SequenceMatcher(None, a, b).get_opcodes()
+    assert get_grouped_opcodes(a, b, neighbor_tiebreak=True)[0][0][0] == 'delete'
@@ -0,0 +1,68 @@
+"""Test the normalize option of the unified diff functions."""
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_range, unified_diff_str, unified_diff_with_stats
+
+
+def test_lowercase():
+    """Test that case-only changes disappear and the diff shows original lines."""
+    a = ['Hello', 'World', 'one']
+    b = ['hello', 'WORLD', 'two']
+    diff = unified_diff(a, b, lineterm='', normalize=['lowercase'])
+    assert diff[2:] == ['@@ -1,3 +1,3 @@', ' Hello', ' World', '-one', '+two']
+
+
+def test_all_keys_equal_gives_empty_diff():
+    """Test that inputs equal after normalization have no diff."""
+    a = ['  x  y', 'z\r\n']
+    b = ['x y', 'z\n']
+    assert unified_diff(a, b, normalize=['strip_cr', 'trim', 'collapse_ws']) == []
+    assert unified_diff(a, b, normalize=['trim', 'collapse_ws']) != []
+
+
+@pytest.mark.parametrize("names,a,b,equal", [
+    (['trim'], ['  x\n'], ['x \n'], True),
+    (['trim'], ['x  y'], ['x y'], False),
+    (['collapse_ws'], ['x \t y'], ['x y'], True),
+    (['collapse_ws'], [' x'], ['x'], False),
+    (['strip_cr'], ['x\r\n'], ['x\n'], True),
+    (['strip_cr'], ['x\r'], ['x'], True),
+    (['trim'], ['x\n'], ['x'], False),
+    (['lowercase', 'trim'], [' X '], ['x'], True),
+])
+def test_transforms(names, a, b, equal):
+    """Test each transform, and that only strip_cr touches line endings."""
+    assert (unified_diff(a, b, normalize=names) == []) == equal
+
+
+def test_other_functions():
+    """Test that every diff function accepts normalize."""
+    a = ['A', 'b', 'c']
+    b = ['a', 'b', 'C', 'd']
+    expected = unified_diff(a, b, normalize=['lowercase'])
+    assert expected[2:] == ['@@ -1,3 +1,4 @@\n', ' A', ' b', ' c', '+d']
+    assert unified_diff_str('A\nb\nc', 'a\nb\nC\nd', normalize=['lowercase']) == expected
+    assert unified_diff_with_stats(a, b, normalize=['lowercase']) == (expected, (1, 0, 1))
+    assert unified_diff_range(a, b, (0, 3), (0, 4), normalize=['lowercase']) == expected
+
+
+def test_isjunk_sees_keys():
+    """Test that isjunk is called on the normalized lines."""
+    seen = []
+    unified_diff(['X'], ['Y'], isjunk=lambda line: seen.append(line) or False, normalize=['lowercase'])
+    assert seen == ['y']
+
+
+def test_validate_checks_keys():
+    """Test that validate checks equal runs by their normalized keys."""
+    a = ['Foo\n', 'bar\n', 'baz\n']
+    b = ['foo\n', 'bar\n', 'qux\n']
+    expected = ['--- \n', '+++ \n', '@@ -1,3 +1,3 @@\n', ' Foo\n', ' bar\n', '-baz\n', '+qux\n']
+    assert unified_diff(a, b, normalize=['lowercase'], validate=True) == expected
+    assert unified_diff_range(a, b, (0, 3), (0, 3), normalize=['lowercase'], validate=True) == expected
+
+
+def test_unknown_transform():
+    """Test that an unknown transform name is rejected."""
+    with pytest.raises(ValueError, match="unknown transform"):
+        unified_diff(['a'], ['b'], normalize=['upper'])
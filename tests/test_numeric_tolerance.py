@@ -0,0 +1,98 @@
+"""Test treating numbers within a tolerance as equal."""
+
+import pytest
+from difflib_rs import get_opcodes, get_opcodes_numeric, unified_diff, unified_diff_str
+
+A = ["time,value", "0.0,1.000001", "1.0,2.5", "2.0,3.75"]
+
+
+def test_close_numbers_are_equal():
+    """Test that lines differing only in close numbers give no diff."""
+    b = ["time,value", "0.0,1.000002", "1.0,2.5000001", "2.0,3.75"]
+    assert get_opcodes_numeric(A, b, abs_tol=1e-5) == [("equal", 0, 4, 0, 4)]
+    assert unified_diff(A, b, numeric_tolerance=(1e-5, 0.0)) == []
+    assert unified_diff(A, b) != []
+
+
+def test_numbers_outside_tolerance_are_replaced():
+    """Test that a matched line whose numbers aren't close is a change."""
+    b = ["time,value", "0.0,1.000002", "1.0,2.6", "2.0,3.75"]
+    assert get_opcodes_numeric(A, b, abs_tol=1e-5) == [
+        ("equal", 0, 2, 0, 2),
+        ("replace", 2, 3, 2, 3),
+        ("equal", 3, 4, 3, 4),
+    ]
+    diff = unified_diff(A, b, lineterm="", numeric_tolerance=(1e-5, 0.0))
+    assert [line for line in diff if line[:1] in "-+" and line[:3] not in ("---", "+++")] == [
+        "-1.0,2.5",
+        "+1.0,2.6",
+    ]
+
+
+def test_relative_tolerance():
+    """Test that rel_tol scales with the larger magnitude."""
+    assert get_opcodes_numeric(["x 1000"], ["x 1001"], rel_tol=1e-2) == [("equal", 0, 1, 0, 1)]
+    assert get_opcodes_numeric(["x 1"], ["x 2"], rel_tol=1e-2) == [("replace", 0, 1, 0, 1)]
+
+
+def test_skeleton_is_compared_exactly():
+    """Test that non-numeric text and the number of tokens must match."""
+    assert get_opcodes_numeric(["a 1"], ["b 1"], abs_tol=10) == [("replace", 0, 1, 0, 1)]
+    assert get_opcodes_numeric(["a 1"], ["a 1 2"], abs_tol=10) == [("replace", 0, 1, 0, 1)]
+
+
+@pytest.mark.parametrize(
+    "a, b, equal",
+    [
+        ("x=-1.5", "x=-1.50001", True),
+        ("3-1", "3-1.00001", True),
+        ("t .5", "t 0.50001", True),
+        ("e 1e-3", "e 0.0010001", True),
+        ("v2", "v3", False),
+        ("x_1", "x_2", False),
+        ("5px", "6px", False),
+        ("1.2.3", "1.2.4", False),
+    ],
+)
+def test_tokenization(a, b, equal):
+    """Test which digits count as numeric tokens."""
+    tag = get_opcodes_numeric([a], [b], abs_tol=1e-3)[0][0]
+    assert (tag == "equal") == equal
+
+
+def test_zero_tolerance_matches_plain_diff_on_numbers():
+    """Test that zero tolerance still equates the same number spelled differently."""
+    assert get_opcodes_numeric(["1.0"], ["1.00"]) == [("equal", 0, 1, 0, 1)]
+    assert get_opcodes_numeric(["1.0"], ["1.1"]) == get_opcodes(["1.0"], ["1.1"])
+
+
+def test_unified_diff_str_keeps_original_lines():
+    """Test that the diff shows the original text of changed lines."""
+    diff = unified_diff_str("a 1\nb 2\n", "a 1.0001\nb 3\n", lineterm="", numeric_tolerance=(1e-3, 0.0))
+    assert "-b 2" in diff and "+b 3" in diff
+    assert " a 1" in diff
+
+
+@pytest.mark.parametrize("tolerance", [(-1.0, 0.0), (0.0, float("nan")), (float("inf"), 0.0)])
+def test_invalid_tolerance(tolerance):
+    """Test that negative or non-finite tolerances are rejected."""
+    with pytest.raises(ValueError, match="numeric tolerances"):
+        get_opcodes_numeric(["1"], ["2"], *tolerance)
+    with pytest.raises(ValueError, match="numeric tolerances"):
+        unified_diff(["1"], ["2"], numeric_tolerance=tolerance)
+
+
+def test_rejects_window_size():
+    """Test that numeric tolerance can't be combined with windowed diffing."""
+    with pytest.raises(ValueError, match="window_size"):
+        unified_diff(["1"], ["2"], window_size=10, numeric_tolerance=(0.1, 0.0))
+
+
+def test_validate_uses_tolerance():
+    """Test that validate accepts equal runs that are only equal within the tolerance."""
+    a = ["x 1.0\n", "y 2.0\n", "z 3.0\n"]
+    b = ["x 1.05\n", "y 2.0\n", "z 4.0\n"]
+    assert unified_diff(a, b, validate=True, numeric_tolerance=(0.1, 0.0)) == [
+        '--- \n', '+++ \n', '@@ -1,3 +1,3 @@\n', ' x 1.0\n', ' y 2.0\n', '-z 3.0\n', '+z 4.0\n',
+    ]
+    assert unified_diff(a, b, validate=True, numeric_tolerance=(0.1, 0.0), normalize=['collapse_ws']) != []
@@ -0,0 +1,73 @@
+"""Test get_opcodes_arrays, opcodes as parallel numeric columns."""
+
+import random
+import struct
+from array import array
+
+import pytest
+from difflib_rs import get_opcodes, get_opcodes_arrays
+
+TAGS = ['equal', 'delete', 'insert', 'replace']
+
+
+def columns_to_opcodes(tags, i1, i2, j1, j2):
+    """Opcode tuples from the five columns, decoded with struct."""
+    count = len(tags)
+    indices = [struct.unpack(f"<{count}Q", column) for column in (i1, i2, j1, j2)]
+    return [(TAGS[tag], *(column[k] for column in indices)) for k, tag in enumerate(tags)]
+
+
+def test_columns_match_get_opcodes():
+    """Test the tag encoding and index columns on a small diff."""
+    a = ['a\n', 'b\n', 'c\n', 'd\n']
+    b = ['a\n', 'x\n', 'c\n', 'e\n', 'd\n']
+    tags, i1, i2, j1, j2 = get_opcodes_arrays(a, b)
+    assert list(tags) == [0, 3, 0, 2, 0]
+    assert struct.unpack("<5Q", i1) == (0, 1, 2, 3, 3)
+    assert struct.unpack("<5Q", j2) == (1, 2, 3, 4, 5)
+    assert columns_to_opcodes(tags, i1, i2, j1, j2) == get_opcodes(a, b)
+
+
+def test_empty_and_identical():
+    """Test empty inputs give empty columns and identical ones a single equal."""
+    assert get_opcodes_arrays([], []) == (b'', b'', b'', b'', b'')
+    tags, i1, i2, j1, j2 = get_opcodes_arrays(['a'], ['a'])
+    assert (tags, i1, i2, j1, j2) == (b'\x00', bytes(8), struct.pack("<Q", 1), bytes(8), struct.pack("<Q", 1))
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_random_matches_get_opcodes(seed):
+    """Test that the columns decode to get_opcodes, junk options included."""
+    rng = random.Random(seed)
+    a = [f"{rng.randint(0, 9)}" for _ in range(rng.randint(0, 300))]
+    b = [f"{rng.randint(0, 9)}" for _ in range(rng.randint(0, 300))]
+    isjunk = lambda line: line == '0'
+    for kwargs in ({}, {'autojunk': False}, {'isjunk': isjunk}):
+        columns = get_opcodes_arrays(a, b, **kwargs)
+        assert len({len(column) for column in columns[1:]}) == 1
+        assert len(columns[1]) == 8 * len(columns[0])
+        assert columns_to_opcodes(*columns) == get_opcodes(a, b, **kwargs)
+
+
+def test_array_module_wraps_columns():
+    """Test that array.array reads the index columns on little-endian machines."""
+    _, i1, _, _, _ = get_opcodes_arrays(['a', 'b'], ['b', 'c'])
+    column = array('Q')
+    column.frombytes(i1)
+    if column.itemsize == 8 and struct.pack("=Q", 1) == struct.pack("<Q", 1):
+        assert column.tolist() == [op[1] for op in get_opcodes(['a', 'b'], ['b', 'c'])]
+
+
+def test_numpy_frombuffer():
+    """Test the numpy recipe from the docs, when numpy is installed."""
+    try:
+        import numpy
+    except ImportError:
+        pytest.skip("numpy is not installed")
+    a = [str(i % 7) for i in range(100)]
+    b = [str(i % 5) for i in range(100)]
+    tags, i1, i2, j1, j2 = get_opcodes_arrays(a, b)
+    columns = [numpy.frombuffer(column, dtype="<u8") for column in (i1, i2, j1, j2)]
+    expected = get_opcodes(a, b)
+    assert numpy.frombuffer(tags, dtype=numpy.uint8).tolist() == [TAGS.index(op[0]) for op in expected]
+    assert [column.tolist() for column in columns] == [[op[k] for op in expected] for k in range(1, 5)]
@@ -0,0 +1,88 @@
+"""Test splitting equal opcodes into bounded runs for rendering."""
+
+import pytest
+from difflib_rs import get_opcodes, get_opcodes_for_render
+
+
+def test_default_matches_get_opcodes():
+    """Test that without max_equal_run the opcodes are unchanged."""
+    a = [str(i) for i in range(50)]
+    b = a[:20] + ["x"] + a[21:]
+    assert get_opcodes_for_render(a, b) == get_opcodes(a, b)
+
+
+def test_equal_runs_are_split():
+    """Test that long equal opcodes become chunks of at most max_equal_run."""
+    a = [str(i) for i in range(25)]
+    b = a[:10] + ["x"] + a[11:]
+    assert get_opcodes_for_render(a, b, max_equal_run=4) == [
+        ("equal", 0, 4, 0, 4),
+        ("equal", 4, 8, 4, 8),
+        ("equal", 8, 10, 8, 10),
+        ("replace", 10, 11, 10, 11),
+        ("equal", 11, 15, 11, 15),
+        ("equal", 15, 19, 15, 19),
+        ("equal", 19, 23, 19, 23),
+        ("equal", 23, 25, 23, 25),
+    ]
+
+
+@pytest.mark.parametrize("max_equal_run", [1, 2, 3, 7, 100])
+def test_ranges_stay_contiguous(max_equal_run):
+    """Test that the split opcodes cover both sequences without gaps."""
+    a = [str(i % 7) for i in range(60)]
+    b = [str(i % 5) for i in range(55)]
+    ops = get_opcodes_for_render(a, b, max_equal_run=max_equal_run)
+    assert (ops[0][1], ops[0][3]) == (0, 0)
+    assert (ops[-1][2], ops[-1][4]) == (len(a), len(b))
+    for prev, op in zip(ops, ops[1:]):
+        assert (prev[2], prev[4]) == (op[1], op[3])
+    for tag, i1, i2, j1, j2 in ops:
+        if tag == "equal":
+            assert 0 < i2 - i1 <= max_equal_run
+            assert a[i1:i2] == b[j1:j2]
+    merged = [op for op in get_opcodes(a, b) if op[0] != "equal"]
+    assert [op for op in ops if op[0] != "equal"] == merged
+
+
+def test_one_based():
+    """Test that one_based applies to the split opcodes."""
+    ops = get_opcodes_for_render(["a", "b", "c"], ["a", "b", "c"], max_equal_run=2, one_based=True)
+    assert ops == [("equal", 1, 2, 1, 2), ("equal", 3, 3, 3, 3)]
+
+
+def test_zero_is_rejected():
+    """Test that a zero max_equal_run is rejected."""
+    with pytest.raises(ValueError, match="max_equal_run must be positive"):
+        get_opcodes_for_render(["a"], ["a"], max_equal_run=0)
+
+
+def test_keys_of_unchanged_lines_persist():
+    """Test that an unchanged line keeps its key when other lines change."""
+    a = ['head', 'x', 'body', 'tail']
+    first = get_opcodes_for_render(a, ['head', 'y', 'body', 'tail'], keys=True)
+    second = get_opcodes_for_render(a, ['new', 'head', 'body', 'tail', 'more'], keys=True)
+
+    def equal_keys(ops):
+        return {key for op in ops if op[0] == 'equal' for key in op[5]}
+
+    assert {'=' in key for key in equal_keys(first)} == {True}
+    assert equal_keys(first) >= equal_keys(second)
+
+
+def test_keys_of_changed_lines():
+    """Test that changed lines get one position key per deleted and inserted line."""
+    ops = get_opcodes_for_render(['a', 'b'], ['a', 'c', 'd'], keys=True)
+    assert [op[:5] for op in ops] == [('equal', 0, 1, 0, 1), ('replace', 1, 2, 1, 3)]
+    assert len(ops[0][5]) == 1
+    assert [key.split(':')[0] for key in ops[1][5]] == ['-1', '+1', '+2']
+
+
+def test_keys_unique_with_repeated_lines():
+    """Test that repeated unchanged lines still get distinct keys."""
+    a = ['}', 'x', '}', '}']
+    ops = get_opcodes_for_render(a, a + ['y'], max_equal_run=2, keys=True)
+    keys = [key for op in ops for key in op[5]]
+    assert len(keys) == 5
+    assert len(set(keys)) == 5
+    assert [key for key in keys if key.startswith('=')][0].endswith('.0')
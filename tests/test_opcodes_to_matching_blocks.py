@@ -0,0 +1,44 @@
+"""Test opcodes_to_matching_blocks."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import (
+    deserialize_opcodes,
+    get_matching_blocks,
+    get_opcodes,
+    get_opcodes_for_render,
+    opcodes_to_matching_blocks,
+    serialize_opcodes,
+)
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_inverse_of_get_opcodes(seed):
+    """Test that blocks come back exactly as difflib computes them."""
+    rng = random.Random(seed)
+    a = [rng.choice('abcd') for _ in range(rng.randrange(20))]
+    b = [rng.choice('abcd') for _ in range(rng.randrange(20))]
+    expected = [tuple(block) for block in difflib.SequenceMatcher(None, a, b).get_matching_blocks()]
+    assert opcodes_to_matching_blocks(get_opcodes(a, b)) == expected
+    assert opcodes_to_matching_blocks(deserialize_opcodes(serialize_opcodes(get_opcodes(a, b)))) == expected
+
+
+def test_touching_equal_opcodes_merge():
+    """Test that split equal runs collapse back into one block."""
+    a = [str(i) for i in range(10)]
+    ops = get_opcodes_for_render(a, a, max_equal_run=3)
+    assert len(ops) == 4
+    assert opcodes_to_matching_blocks(ops) == get_matching_blocks(a, a) == [(0, 0, 10), (10, 10, 0)]
+
+
+def test_empty_opcodes():
+    """Test that no opcodes give only the sentinel."""
+    assert opcodes_to_matching_blocks([]) == [(0, 0, 0)]
+
+
+def test_unknown_tag():
+    """Test that an unknown tag is rejected."""
+    with pytest.raises(ValueError):
+        opcodes_to_matching_blocks([('same', 0, 1, 0, 1)])
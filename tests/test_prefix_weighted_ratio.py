@@ -0,0 +1,41 @@
+"""Test prefix_weighted_ratio."""
+
+import difflib
+
+import pytest
+from difflib_rs import prefix_weighted_ratio
+
+
+def test_prefix_match_ranks_first():
+    """Test that a candidate starting with the query beats one containing it."""
+    query = "app"
+    candidates = ["snapper", "apple", "happy"]
+    ranked = sorted(candidates, key=lambda c: -prefix_weighted_ratio(query, c))
+    assert ranked[0] == "apple"
+    assert prefix_weighted_ratio(query, "apple") == pytest.approx(0.75)
+
+
+def test_weighting_formula():
+    """Test that a block's weight is decay to the power of its offset."""
+    # "bc" matches at (0, 1): 2 * 2 * 0.5 / 5
+    assert prefix_weighted_ratio("bc", "abc", decay=0.5) == pytest.approx(0.4)
+    assert prefix_weighted_ratio("bc", "abc", decay=0.0) == 0.0
+
+
+@pytest.mark.parametrize("a,b", [("abc", "xbcy"), ("kitten", "sitting"), ("", "a"), ("same", "same")])
+def test_decay_one_is_plain_ratio(a, b):
+    """Test that without decay the score is difflib's ratio."""
+    assert prefix_weighted_ratio(a, b, decay=1.0) == pytest.approx(difflib.SequenceMatcher(None, a, b).ratio())
+
+
+def test_equal_and_empty():
+    """Test that equal strings score 1.0, including empty ones."""
+    assert prefix_weighted_ratio("", "") == 1.0
+    assert prefix_weighted_ratio("abc", "abc", decay=0.0) == 1.0
+
+
+@pytest.mark.parametrize("decay", [-0.1, 1.5])
+def test_invalid_decay(decay):
+    """Test that decay must be in [0, 1]."""
+    with pytest.raises(ValueError, match="decay must be in"):
+        prefix_weighted_ratio("a", "a", decay=decay)
@@ -0,0 +1,45 @@
+"""Test progress reporting during long diffs."""
+
+import random
+import sys
+
+from difflib_rs import get_opcodes, unified_diff
+
+
+def test_progress_ends_at_one():
+    """Test that a fast diff reports only its final 1.0."""
+    a = ['a', 'b', 'c']
+    b = ['a', 'x', 'c']
+    seen = []
+    assert unified_diff(a, b, progress=seen.append) == unified_diff(a, b)
+    assert seen == [1.0]
+
+
+def test_progress_is_monotonic():
+    """Test that estimates stay in [0, 1], never decrease and end at 1.0."""
+    random.seed(0)
+    # Few distinct lines without autojunk make for a slow enough diff to
+    # report progress along the way
+    a = [f"line{random.randint(0, 5)}" for _ in range(3000)]
+    b = [f"line{random.randint(0, 5)}" for _ in range(3000)]
+    seen = []
+    assert get_opcodes(a, b, autojunk=False, progress=seen.append) == get_opcodes(a, b, autojunk=False)
+    assert seen[-1] == 1.0
+    assert all(0.0 <= x <= 1.0 for x in seen)
+    assert seen == sorted(seen)
+
+
+def test_progress_exceptions_do_not_stop_the_diff():
+    """Test that an exception in the callback is reported as unraisable."""
+    raised = []
+    hook = sys.unraisablehook
+    sys.unraisablehook = lambda unraisable: raised.append(unraisable.exc_type)
+
+    def callback(fraction):
+        raise RuntimeError("boom")
+
+    try:
+        assert unified_diff(['a'], ['b'], progress=callback) == unified_diff(['a'], ['b'])
+    finally:
+        sys.unraisablehook = hook
+    assert raised == [RuntimeError]
@@ -0,0 +1,41 @@
+"""Test the similarity ratio of sub-ranges."""
+
+import difflib
+
+import pytest
+from difflib_rs import ratio_range
+
+A = [f"line {i}" for i in range(40)]
+B = A[:10] + ["x", "y"] + A[12:30] + ["z"] + A[31:]
+
+
+@pytest.mark.parametrize(
+    "a_range, b_range",
+    [((0, 40), (0, 40)), ((8, 14), (8, 14)), ((28, 33), (28, 33)), ((0, 5), (35, 40)), ((5, 5), (7, 9))],
+)
+def test_matches_difflib_on_slices(a_range, b_range):
+    """Test that the ratio equals difflib's ratio of the slices."""
+    expected = difflib.SequenceMatcher(None, A[slice(*a_range)], B[slice(*b_range)], autojunk=False).ratio()
+    assert ratio_range(A, B, a_range, b_range, autojunk=False) == pytest.approx(expected)
+
+
+def test_whole_range_is_ratio():
+    """Test that the full ranges give the plain ratio."""
+    expected = difflib.SequenceMatcher(None, A, B).ratio()
+    assert ratio_range(A, B, (0, len(A)), (0, len(B))) == pytest.approx(expected)
+
+
+def test_empty_ranges():
+    """Test that two empty ranges are identical and one empty range isn't similar."""
+    assert ratio_range(A, B, (3, 3), (7, 7)) == 1.0
+    assert ratio_range(A, B, (3, 3), (7, 9)) == 0.0
+
+
+@pytest.mark.parametrize(
+    "a_range, b_range, message",
+    [((5, 2), (0, 1), "start is after its end"), ((0, 41), (0, 1), "out of bounds"), ((0, 1), (0, 99), "out of bounds")],
+)
+def test_invalid_ranges(a_range, b_range, message):
+    """Test that bad ranges are rejected."""
+    with pytest.raises(ValueError, match=message):
+        ratio_range(A, B, a_range, b_range)
@@ -0,0 +1,49 @@
+"""Test the ratio_streaming function."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import ratio_streaming
+
+
+@pytest.mark.parametrize("seed", range(30))
+def test_matches_ratio_with_cutoff(seed):
+    """Test that the result is ratio() when it reaches cutoff, else None."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 20)}" for _ in range(random.randint(0, 80))]
+    changed = random.random()
+    b = [line if random.random() > changed else f"other{random.randint(0, 20)}" for line in a]
+    ratio = difflib.SequenceMatcher(None, a, b).ratio()
+    for cutoff in (0.0, 0.3, 0.6, 0.9, 1.0):
+        expected = ratio if ratio >= cutoff else None
+        assert ratio_streaming(a, b, cutoff) == expected
+
+
+def test_identical_and_empty():
+    """Test identical, empty and disjoint inputs."""
+    assert ratio_streaming(['a', 'b'], ['a', 'b'], 1.0) == 1.0
+    assert ratio_streaming([], [], 1.0) == 1.0
+    assert ratio_streaming(['a'], ['b'], 0.0) == 0.0
+    assert ratio_streaming(['a'], ['b'], 0.1) is None
+
+
+def test_length_mismatch_rejected():
+    """Test inputs whose lengths alone rule out the cutoff."""
+    assert ratio_streaming(['x'] * 10, ['x'], 0.5) is None
+    assert ratio_streaming(['x'] * 10, ['x'], 0.18) == pytest.approx(2 / 11)
+
+
+def test_with_isjunk():
+    """Test that junk settings match SequenceMatcher's."""
+    a = ['a', ' ', 'b', ' ', 'c']
+    b = ['a', 'b', ' ', 'x', 'c']
+    isjunk = str.isspace
+    ratio = difflib.SequenceMatcher(isjunk, a, b).ratio()
+    assert ratio_streaming(a, b, 0.0, isjunk=isjunk) == ratio
+
+
+def test_invalid_cutoff():
+    """Test that a cutoff outside [0, 1] is rejected."""
+    with pytest.raises(ValueError, match="cutoff"):
+        ratio_streaming(['a'], ['a'], 1.5)
@@ -0,0 +1,54 @@
+"""Test the reverse_hunks option."""
+
+import pytest
+from difflib_rs import (
+    apply_unified_diff,
+    unified_diff,
+    unified_diff_str,
+    unified_diff_with_stats,
+)
+
+A = [f"line {i}" for i in range(30)]
+B = A[:2] + ['first'] + A[3:15] + ['second'] + A[16:27] + ['third'] + A[28:]
+
+
+def hunks(diff):
+    """Split diff lines after the file headers into hunks."""
+    result = []
+    for line in diff[2:]:
+        if line.startswith('@@'):
+            result.append([])
+        result[-1].append(line)
+    return result
+
+
+def test_hunks_reversed_with_headers():
+    """Test that hunks come last first, each unchanged."""
+    forward = unified_diff(A, B, 'a', 'b', lineterm='')
+    backward = unified_diff(A, B, 'a', 'b', lineterm='', reverse_hunks=True)
+    assert backward[:2] == forward[:2]
+    assert hunks(backward) == hunks(forward)[::-1]
+    assert len(hunks(forward)) == 3
+
+
+def test_reverse_hunks_str_and_stats():
+    """Test the option through unified_diff_str and unified_diff_with_stats."""
+    a, b = "\n".join(A) + "\n", "\n".join(B) + "\n"
+    forward = unified_diff_str(a, b)
+    assert hunks(unified_diff_str(a, b, reverse_hunks=True)) == hunks(forward)[::-1]
+    diff, stats = unified_diff_with_stats(A, B, reverse_hunks=True)
+    assert stats == unified_diff_with_stats(A, B)[1]
+    assert hunks(diff) == hunks(unified_diff(A, B))[::-1]
+
+
+def test_single_hunk_unchanged():
+    """Test that a diff with one hunk is the same either way."""
+    b = A[:5] + ['x'] + A[6:]
+    assert unified_diff(A, b, reverse_hunks=True) == unified_diff(A, b)
+
+
+def test_reversed_diff_is_not_a_patch():
+    """Test that the reversed output doesn't apply."""
+    diff = unified_diff(A, B, lineterm='', reverse_hunks=True)
+    with pytest.raises(ValueError):
+        apply_unified_diff(A, diff)
@@ -0,0 +1,85 @@
+"""Test sectioned_diff, per-section diffs of titled documents."""
+
+import pytest
+from difflib_rs import sectioned_diff, unified_diff
+
+OLD = [
+    '# Changelog\n',
+    '\n',
+    '## 1.1.0\n',
+    '- Add widgets\n',
+    '- Fix crash\n',
+    '\n',
+    '## 1.0.0\n',
+    '- First release\n',
+]
+
+
+def test_only_changed_sections():
+    """Test that a change in one section yields a diff for that section only."""
+    new = list(OLD)
+    new[4] = '- Fix crash on exit\n'
+    diffs = sectioned_diff(OLD, new, '## ')
+    assert list(diffs) == [('## 1.1.0', 0)]
+    # Context stays within the section and lines count from its heading
+    assert diffs[('## 1.1.0', 0)] == [
+        '--- ## 1.1.0\n', '+++ ## 1.1.0\n', '@@ -1,4 +1,4 @@\n',
+        ' ## 1.1.0\n', ' - Add widgets\n', '-- Fix crash\n', '+- Fix crash on exit\n', ' \n',
+    ]
+    assert sectioned_diff(OLD, OLD, '## ') == {}
+
+
+def test_new_section_reported_wholesale():
+    """Test that an added section is all additions."""
+    new = OLD[:2] + ['## 1.2.0\n', '- Add gadgets\n', '\n'] + OLD[2:]
+    diffs = sectioned_diff(OLD, new, '## ')
+    assert diffs == {('## 1.2.0', 0): [
+        '--- ## 1.2.0\n', '+++ ## 1.2.0\n', '@@ -0,0 +1,3 @@\n',
+        '+## 1.2.0\n', '+- Add gadgets\n', '+\n',
+    ]}
+
+
+def test_removed_section_reported_wholesale():
+    """Test that a removed section is all removals."""
+    diffs = sectioned_diff(OLD, OLD[:6], '## ')
+    assert diffs == {('## 1.0.0', 0): [
+        '--- ## 1.0.0\n', '+++ ## 1.0.0\n', '@@ -1,2 +0,0 @@\n', '-## 1.0.0\n', '-- First release\n',
+    ]}
+
+
+def test_preamble_and_reordered_sections():
+    """Test the untitled preamble and that moved sections pair by title."""
+    new = ['# Changes\n', '\n'] + OLD[6:] + OLD[2:6]
+    diffs = sectioned_diff(OLD, new, '## ')
+    assert list(diffs) == [('', 0)]
+    assert diffs[('', 0)][3:] == ['-# Changelog\n', '+# Changes\n', ' \n']
+
+
+def test_matches_unified_diff_of_sections():
+    """Test that each diff is the unified diff of the two sections."""
+    new = list(OLD)
+    new[3:5] = ['- Add widgets and gizmos\n']
+    new.append('- Docs\n')
+    diffs = sectioned_diff(OLD, new, '## ', n=0, lineterm='')
+    assert list(diffs) == [('## 1.1.0', 0), ('## 1.0.0', 0)]
+    for title, (i1, i2), (j1, j2) in [('## 1.1.0', (2, 6), (2, 5)), ('## 1.0.0', (6, 8), (5, 8))]:
+        assert diffs[(title, 0)] == unified_diff(OLD[i1:i2], new[j1:j2], title, title, n=0, lineterm='')
+
+
+def test_repeated_titles_pair_in_order():
+    """Test that repeated headings pair by occurrence, each pair with its own key."""
+    a = ['### Fixed\n', 'x\n', '### Fixed\n', 'y\n']
+    b = ['### Fixed\n', 'x\n', '### Fixed\n', 'z\n']
+    diffs = sectioned_diff(a, b, '### ', n=0)
+    assert diffs == {('### Fixed', 1): ['--- ### Fixed\n', '+++ ### Fixed\n', '@@ -2 +2 @@\n', '-y\n', '+z\n']}
+    diffs = sectioned_diff(a, ['### Fixed\n', 'w\n', '### Fixed\n', 'z\n'], '### ', n=0)
+    assert diffs == {
+        ('### Fixed', 0): ['--- ### Fixed\n', '+++ ### Fixed\n', '@@ -2 +2 @@\n', '-x\n', '+w\n'],
+        ('### Fixed', 1): ['--- ### Fixed\n', '+++ ### Fixed\n', '@@ -2 +2 @@\n', '-y\n', '+z\n'],
+    }
+
+
+def test_empty_pattern():
+    """Test that an empty section pattern is rejected."""
+    with pytest.raises(ValueError, match="section_pattern"):
+        sectioned_diff(OLD, OLD, '')
@@ -0,0 +1,51 @@
+"""Test the reusable SequenceMatcher class."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import SequenceMatcher
+
+
+def _pairs(count):
+    random.seed(count)
+    for _ in range(count):
+        a = [f"line{random.randint(0, 15)}" for _ in range(random.randint(0, 80))]
+        b = [f"line{random.randint(0, 15)}" for _ in range(random.randint(0, 80))]
+        yield a, b
+
+
+def test_batch_matches_difflib():
+    """Test that reused buffers give each pair the same result as difflib."""
+    with SequenceMatcher() as m:
+        for a, b in _pairs(50):
+            expected = difflib.SequenceMatcher(None, a, b)
+            assert m.compare(a, b) == [tuple(op) for op in expected.get_opcodes()]
+            assert m.ratio(a, b) == expected.ratio()
+
+
+def test_isjunk_and_autojunk():
+    """Test that the configuration applies to every pair."""
+    a = ['x', ' ', 'y'] + ['z'] * 250
+    b = ['x', 'y', ' '] + ['z'] * 250
+    isjunk = str.isspace
+    with SequenceMatcher(isjunk=isjunk, autojunk=False) as m:
+        expected = difflib.SequenceMatcher(isjunk, a, b, autojunk=False).get_opcodes()
+        assert m.compare(a, b) == [tuple(op) for op in expected]
+
+
+def test_usable_after_exit():
+    """Test that leaving the block only frees buffers."""
+    m = SequenceMatcher()
+    with m as entered:
+        assert entered is m
+        assert m.ratio(['a'], ['a']) == 1.0
+    assert m.compare(['a'], ['b']) == [('replace', 0, 1, 0, 1)]
+    assert m.ratio([], []) == 1.0
+
+
+def test_exceptions_propagate():
+    """Test that __exit__ doesn't swallow exceptions."""
+    with pytest.raises(KeyError):
+        with SequenceMatcher():
+            raise KeyError('boom')
@@ -0,0 +1,53 @@
+"""Test serialize_opcodes and deserialize_opcodes."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import deserialize_opcodes, serialize_opcodes
+
+
+@pytest.mark.parametrize("seed", range(20))
+def test_round_trip_real_diffs(seed):
+    """Test that opcodes of random diffs survive a round trip."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 20)}" for _ in range(random.randint(0, 300))]
+    b = [f"line{random.randint(0, 20)}" for _ in range(random.randint(0, 300))]
+    opcodes = difflib.SequenceMatcher(None, a, b).get_opcodes()
+    data = serialize_opcodes(opcodes)
+    assert isinstance(data, bytes)
+    assert deserialize_opcodes(data) == opcodes
+
+
+@pytest.mark.parametrize("seed", range(20))
+def test_round_trip_arbitrary_opcodes(seed):
+    """Test that non-contiguous and large indices also round trip."""
+    random.seed(seed)
+    tags = ['equal', 'delete', 'insert', 'replace']
+    opcodes = []
+    for _ in range(random.randint(0, 50)):
+        i1, i2 = sorted(random.randint(0, 2**40) for _ in range(2))
+        j1, j2 = sorted(random.randint(0, 2**20) for _ in range(2))
+        opcodes.append((random.choice(tags), i1, i2, j1, j2))
+    assert deserialize_opcodes(serialize_opcodes(opcodes)) == opcodes
+
+
+def test_serialized_size_is_compact():
+    """Test that contiguous small opcodes take five bytes each plus a header."""
+    opcodes = [('equal', 0, 3, 0, 3), ('replace', 3, 4, 3, 5), ('equal', 4, 10, 5, 11)]
+    assert len(serialize_opcodes(opcodes)) == 1 + 5 * len(opcodes)
+    assert serialize_opcodes([]) == b'\x01'
+    assert deserialize_opcodes(b'\x01') == []
+
+
+@pytest.mark.parametrize("data", [b'', b'\x02', b'\x01\x07\x00\x00\x00\x00', b'\x01\x00\x00\x02', b'\x01\x00\x03\x00\x00\x00'])
+def test_deserialize_rejects_malformed(data):
+    """Test empty, versionless, bad-tag, truncated and negative-index input."""
+    with pytest.raises(ValueError):
+        deserialize_opcodes(data)
+
+
+def test_serialize_rejects_unknown_tag():
+    """Test that an unknown tag raises ValueError."""
+    with pytest.raises(ValueError):
+        serialize_opcodes([('bogus', 0, 1, 0, 1)])
@@ -0,0 +1,66 @@
+"""Test similarity_profile, local ratios along b."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import similarity_profile
+
+
+def test_identical_files_are_flat():
+    """Test that every window of identical files scores 1.0."""
+    a = [f"line {i}\n" for i in range(50)]
+    assert similarity_profile(a, a, 10) == [1.0] * 41
+
+
+def test_dip_where_the_files_differ():
+    """Test that the windows over a rewritten region score lowest."""
+    a = [f"line {i}\n" for i in range(100)]
+    b = a[:40] + [f"new {i}\n" for i in range(10)] + a[50:]
+    profile = similarity_profile(a, b, 10)
+    assert len(profile) == 91
+    assert profile[0] == 1.0 and profile[-1] == 1.0
+    assert profile[40] == 0.0
+    assert min(profile) == profile[40]
+    # Sliding into the change lowers the ratio one line at a time
+    assert profile[30:41] == sorted(profile[30:41], reverse=True)
+
+
+def test_insertion_and_deletion_ratios():
+    """Test the window ratios around pure insertions and deletions."""
+    a = ['a\n', 'b\n', 'c\n', 'd\n']
+    # Inserted x: the window [b, x] matches b against a's b
+    assert similarity_profile(a, ['a\n', 'b\n', 'x\n', 'c\n', 'd\n'], 2) == [1.0, 2 / 3, 2 / 3, 1.0]
+    # Deleted c counts in both windows holding b, the line before it
+    assert similarity_profile(a, ['a\n', 'b\n', 'd\n'], 2) == [0.8, 0.8]
+    # Leading deletions count in the first window
+    assert similarity_profile(a, ['c\n', 'd\n'], 1) == [0.5, 1.0]
+
+
+def test_short_b_gives_whole_ratio():
+    """Test a single value equal to the global ratio when b fits in one window."""
+    a = ['a\n', 'b\n', 'c\n']
+    b = ['a\n', 'x\n', 'c\n']
+    expected = difflib.SequenceMatcher(None, a, b).ratio()
+    assert similarity_profile(a, b, 3) == [expected]
+    assert similarity_profile(a, b, 10) == [expected]
+    assert similarity_profile(a, [], 5) == [0.0]
+    assert similarity_profile([], [], 5) == [1.0]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_random_profiles_in_range(seed):
+    """Test the length and bounds of profiles of random edits."""
+    rng = random.Random(seed)
+    a = [f"{rng.randint(0, 20)}\n" for _ in range(rng.randint(0, 120))]
+    b = [line if rng.random() < 0.8 else "x\n" for line in a if rng.random() > 0.1]
+    window = rng.randint(1, 20)
+    profile = similarity_profile(a, b, window)
+    assert len(profile) == max(len(b) - window + 1, 1)
+    assert all(0.0 <= ratio <= 1.0 for ratio in profile)
+
+
+def test_zero_window():
+    """Test that a window of 0 lines is rejected."""
+    with pytest.raises(ValueError):
+        similarity_profile(['a'], ['a'], 0)
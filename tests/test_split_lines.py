@@ -0,0 +1,52 @@
+"""Test the split_lines function."""
+
+import random
+
+import pytest
+from difflib_rs import split_lines, unified_diff, unified_diff_str
+
+BOUNDARIES = ['\n', '\r\n', '\r', '\v', '\f', '\x1c', '\x1d', '\x1e', '\x85', '\u2028', '\u2029']
+
+
+@pytest.mark.parametrize("keepends", [True, False])
+def test_split_lines_boundaries(keepends):
+    """Test each line boundary Python recognizes."""
+    for boundary in BOUNDARIES:
+        text = f"one{boundary}two{boundary}"
+        assert split_lines(text, keepends) == text.splitlines(keepends), repr(boundary)
+
+
+def test_split_lines_keepends_default():
+    """Test that line endings are kept by default."""
+    assert split_lines("a\nb\r\nc") == ["a\n", "b\r\n", "c"]
+    assert split_lines("a\nb\r\nc", keepends=False) == ["a", "b", "c"]
+
+
+def test_split_lines_edge_cases():
+    """Test empty text, blank lines and a lone final break."""
+    for text in ["", "\n", "\n\n", "\r\r\n", "no break", "\r\n\r", "x\u2028\u2028y"]:
+        assert split_lines(text) == text.splitlines(True)
+        assert split_lines(text, False) == text.splitlines()
+
+
+def test_split_lines_non_boundaries():
+    """Test characters that look like breaks but aren't for splitlines."""
+    text = "a\x1fb\u2027c\xc2d\xa0e"
+    assert split_lines(text) == [text]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_split_lines_matches_python(seed):
+    """Test random mixes of text and boundaries."""
+    random.seed(seed)
+    alphabet = BOUNDARIES + ['a', 'b', '\xe9', '\u20ac', ' ', '\x85a']
+    text = ''.join(random.choice(alphabet) for _ in range(300))
+    assert split_lines(text) == text.splitlines(True)
+    assert split_lines(text, keepends=False) == text.splitlines()
+
+
+def test_unified_diff_str_unicode_boundaries():
+    """Test that unified_diff_str splits on the same boundaries."""
+    a = "one\u2028two\fthree\x85four"
+    b = "one\u2028TWO\fthree\x85four"
+    assert unified_diff_str(a, b) == unified_diff(a.splitlines(), b.splitlines())
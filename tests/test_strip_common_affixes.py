@@ -0,0 +1,106 @@
+"""Test the strip_common_affixes fast path."""
+
+import random
+
+import pytest
+from difflib_rs import get_grouped_opcodes, get_opcodes, unified_diff, unified_diff_with_stats, verify_diff
+
+
+def numbered(count, start=0):
+    return [f"line {i}\n" for i in range(start, start + count)]
+
+
+def test_localized_edit_same_as_default():
+    """Test that a one-line edit in unique lines diffs the same either way."""
+    a = numbered(1000)
+    b = list(a)
+    b[500] = "changed\n"
+    assert get_opcodes(a, b, strip_common_affixes=True) == get_opcodes(a, b) == [
+        ('equal', 0, 500, 0, 500), ('replace', 500, 501, 500, 501), ('equal', 501, 1000, 501, 1000),
+    ]
+    assert unified_diff(a, b, strip_common_affixes=True) == unified_diff(a, b)
+
+
+def test_prefix_kept_over_longer_match():
+    """Test the documented difference: the common prefix is matched even when difflib anchors elsewhere."""
+    a = ['x', 'y', 'x', 'y', 'z']
+    b = ['x', 'y', 'z']
+    assert get_opcodes(a, b) == [('delete', 0, 2, 0, 0), ('equal', 2, 5, 0, 3)]
+    assert get_opcodes(a, b, strip_common_affixes=True) == [
+        ('equal', 0, 2, 0, 2), ('delete', 2, 4, 2, 2), ('equal', 4, 5, 2, 3),
+    ]
+
+
+def test_middle_offsets():
+    """Test that blocks found between the prefix and suffix are shifted to whole-sequence positions."""
+    prefix, suffix = numbered(5), numbered(4, start=100)
+    middle_a = ['a\n', 'b\n', 'c\n', 'd\n']
+    middle_b = ['b\n', 'x\n', 'c\n', 'd\n', 'e\n']
+    a, b = prefix + middle_a + suffix, prefix + middle_b + suffix
+    shifted = [
+        (tag, i1 + 5, i2 + 5, j1 + 5, j2 + 5) for tag, i1, i2, j1, j2 in get_opcodes(middle_a, middle_b)
+    ]
+    assert get_opcodes(a, b, strip_common_affixes=True) == (
+        [('equal', 0, 5, 0, 5)] + shifted[:-1] + [('insert', 9, 9, 9, 10), ('equal', 9, 13, 10, 14)]
+    )
+
+
+@pytest.mark.parametrize("a, b", [
+    ([], []),
+    ([], ['a\n']),
+    (['a\n'], []),
+    (['a\n', 'b\n'], ['a\n', 'b\n']),
+    (['a\n', 'b\n'], ['a\n']),
+    (['a\n', 'b\n'], ['b\n']),
+    (['a\n', 'b\n', 'a\n'], ['a\n']),
+    (['a\n'], ['a\n', 'a\n', 'a\n']),
+])
+def test_edge_cases(a, b):
+    """Test empty sides, full overlap and prefix and suffix sharing lines."""
+    opcodes = get_opcodes(a, b, strip_common_affixes=True)
+    rebuilt = []
+    (i, j) = (0, 0)
+    for tag, i1, i2, j1, j2 in opcodes:
+        assert (i1, j1) == (i, j)
+        if tag == 'equal':
+            assert a[i1:i2] == b[j1:j2]
+        rebuilt.extend(b[j1:j2])
+        (i, j) = (i2, j2)
+    assert (i, j) == (len(a), len(b)) and rebuilt == b
+    assert verify_diff(a, b, strip_common_affixes=True)
+
+
+@pytest.mark.parametrize("seed", range(20))
+def test_random_edits_round_trip(seed):
+    """Test that stripped diffs are valid and match as many lines as the middle allows."""
+    rng = random.Random(seed)
+    a = [f"{rng.randint(0, 15)}\n" for _ in range(rng.randint(0, 80))]
+    b = [line for line in a if rng.random() > 0.1]
+    for _ in range(rng.randint(0, 5)):
+        b.insert(rng.randint(0, len(b)), f"new {rng.random()}\n")
+    assert verify_diff(a, b, strip_common_affixes=True)
+    assert verify_diff(a, b, strip_common_affixes=True, unique_anchors=True)
+    grouped = get_grouped_opcodes(a, b, strip_common_affixes=True)
+    assert grouped == get_grouped_opcodes(a, b, strip_common_affixes=True)
+
+
+def test_junk_decided_over_all_of_b():
+    """Test that a line popular only in the prefix stays junk in the middle."""
+    popular = ['}\n'] * 10
+    a = numbered(300) + popular + ['a\n', '}\n', 'b\n'] + numbered(10, start=500)
+    b = numbered(300) + popular + ['c\n', '}\n', 'd\n'] + numbered(10, start=500)
+    opcodes = get_opcodes(a, b, strip_common_affixes=True)
+    # '}' is popular in b as a whole, so it can't anchor a match on its own
+    assert opcodes == [('equal', 0, 310, 0, 310), ('replace', 310, 313, 310, 313), ('equal', 313, 323, 313, 323)]
+    assert get_opcodes(a, b, strip_common_affixes=True, autojunk=False)[1:4] == [
+        ('replace', 310, 311, 310, 311), ('equal', 311, 312, 311, 312), ('replace', 312, 313, 312, 313),
+    ]
+
+
+def test_other_entry_points():
+    """Test that the unified diff variants take the option."""
+    a = numbered(50)
+    b = a[:20] + ['new\n'] + a[20:]
+    expected = unified_diff(a, b)
+    assert unified_diff(a, b, strip_common_affixes=True) == expected
+    assert unified_diff_with_stats(a, b, strip_common_affixes=True)[0] == expected
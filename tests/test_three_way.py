@@ -0,0 +1,98 @@
+"""Test the embed_base option and apply_unified_diff_3way."""
+
+import pytest
+from difflib_rs import (
+    apply_unified_diff,
+    apply_unified_diff_3way,
+    invert_unified_diff,
+    unified_diff,
+    unified_diff_str,
+)
+
+
+def test_embed_base_preamble():
+    """Test that the base comes before the file headers."""
+    a = ['one', 'two', 'three']
+    b = ['one', '2', 'three']
+    patch = unified_diff(a, b, 'a', 'b', lineterm='', embed_base=True)
+    assert patch[:4] == ['base-lines: 3', '=one', '=two', '=three']
+    assert patch[4:] == unified_diff(a, b, 'a', 'b', lineterm='')
+
+
+def test_embed_base_keepends():
+    """Test that base lines keep their endings and the header gets lineterm."""
+    patch = unified_diff_str("x\ny\n", "x\nz\n", keepends=True, embed_base=True)
+    assert patch[:3] == ['base-lines: 2\n', '=x\n', '=y\n']
+
+
+def test_embed_base_identical():
+    """Test that identical inputs still give an empty diff."""
+    assert unified_diff(['a'], ['a'], embed_base=True) == []
+
+
+def test_embedded_patch_applies_normally():
+    """Test that apply_unified_diff skips the preamble."""
+    a = [f"line {i}" for i in range(20)]
+    b = a[:5] + ['changed'] + a[6:]
+    patch = unified_diff(a, b, lineterm='', embed_base=True)
+    assert apply_unified_diff(a, patch) == (b, [(0, 0)])
+    assert apply_unified_diff_3way(a, patch) == (b, 0)
+
+
+def test_3way_merges_unrelated_change():
+    """Test that a change next to the hunk's context merges cleanly."""
+    base = [f"line {i}" for i in range(20)]
+    theirs = base[:10] + ['theirs'] + base[11:]
+    ours = base[:8] + ['ours'] + base[9:]
+    patch = unified_diff(base, theirs, lineterm='', embed_base=True)
+    with pytest.raises(ValueError, match="hunk #1 does not apply"):
+        apply_unified_diff(ours, patch)
+    merged, conflicts = apply_unified_diff_3way(ours, patch)
+    assert conflicts == 0
+    assert merged == base[:8] + ['ours', 'line 9', 'theirs'] + base[11:]
+
+
+def test_3way_conflict_markers():
+    """Test that the same line changed differently becomes a conflict."""
+    base = ['a\n', 'b\n', 'c\n']
+    theirs = ['a\n', 'B\n', 'c\n']
+    ours = ['a\n', 'beta\n', 'c\n']
+    patch = unified_diff(base, theirs, embed_base=True)
+    merged, conflicts = apply_unified_diff_3way(ours, patch)
+    assert conflicts == 1
+    assert merged == [
+        'a\n', '<<<<<<< ours\n', 'beta\n', '=======\n', 'B\n', '>>>>>>> theirs\n', 'c\n',
+    ]
+
+
+def test_3way_markers_without_line_endings():
+    """Test that marker lines follow lines without endings."""
+    patch = unified_diff(['x'], ['y'], lineterm='', embed_base=True)
+    merged, conflicts = apply_unified_diff_3way(['z'], patch)
+    assert conflicts == 1
+    assert merged == ['<<<<<<< ours', 'z', '=======', 'y', '>>>>>>> theirs']
+
+
+def test_3way_same_change_on_both_sides():
+    """Test that a change already made in a isn't a conflict."""
+    base = [f"line {i}" for i in range(10)]
+    theirs = base[:4] + ['new'] + base[5:]
+    ours = ['extra'] + theirs
+    patch = unified_diff(base, theirs, lineterm='', embed_base=True, n=0)
+    # With no context the removed line is what fails to apply
+    assert apply_unified_diff_3way(ours, patch) == (ours, 0)
+
+
+def test_3way_without_base():
+    """Test that a failing patch without a base still raises."""
+    patch = unified_diff(['x'], ['y'], lineterm='')
+    with pytest.raises(ValueError, match="hunk #1 does not apply"):
+        apply_unified_diff_3way(['z'], patch)
+
+
+def test_invert_drops_base():
+    """Test that inverting a patch drops the base, which is its new side."""
+    a = ['one', 'two']
+    b = ['one', '2']
+    patch = unified_diff(a, b, 'a', 'b', lineterm='', embed_base=True)
+    assert invert_unified_diff(patch) == unified_diff(b, a, 'b', 'a', lineterm='')
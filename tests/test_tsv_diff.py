@@ -0,0 +1,39 @@
+"""Test the tsv_diff function."""
+
+import csv
+import io
+
+from difflib_rs import tsv_diff
+
+
+def test_tsv_diff_all_ops():
+    """Test one row per line with the op names and line numbers."""
+    a = ['keep', 'drop', 'old', 'same']
+    b = ['keep', 'new', 'same', 'added']
+    assert tsv_diff(a, b) == [
+        'equal\t1\t1\tkeep',
+        'replace-del\t2\t\tdrop',
+        'replace-del\t3\t\told',
+        'replace-ins\t\t2\tnew',
+        'equal\t4\t3\tsame',
+        'insert\t\t4\tadded',
+    ]
+
+
+def test_tsv_diff_delete():
+    """Test that deleted rows leave the new line number blank."""
+    assert tsv_diff(['a', 'b', 'c'], ['a', 'c']) == ['equal\t1\t1\ta', 'delete\t2\t\tb', 'equal\t3\t2\tc']
+
+
+def test_tsv_diff_escapes_content():
+    """Test that embedded tabs, line breaks and backslashes stay in one field."""
+    rows = tsv_diff(['col1\tcol2\n'], ['C:\\path\r\n'])
+    assert rows == ['replace-del\t1\t\tcol1\\tcol2\\n', 'replace-ins\t\t1\tC:\\\\path\\r\\n']
+    for row in csv.reader(io.StringIO('\n'.join(rows)), delimiter='\t'):
+        assert len(row) == 4
+
+
+def test_tsv_diff_identical_and_empty():
+    """Test identical inputs and empty inputs."""
+    assert tsv_diff(['x', 'y'], ['x', 'y']) == ['equal\t1\t1\tx', 'equal\t2\t2\ty']
+    assert tsv_diff([], []) == []
@@ -0,0 +1,41 @@
+"""Test the unchanged_ranges_old and unchanged_ranges_new functions."""
+
+import random
+
+import pytest
+from difflib_rs import change_mask_new, change_mask_old, unchanged_ranges_new, unchanged_ranges_old
+
+
+def test_unchanged_ranges_basic():
+    """Test 1-based inclusive ranges around deleted, replaced and added lines."""
+    a = ['a', 'gone', 'b', 'old', 'c']
+    b = ['a', 'b', 'new', 'c', 'added']
+    assert unchanged_ranges_old(a, b) == [(1, 1), (3, 3), (5, 5)]
+    # 'a' and 'b' are separate runs in a but touch in b
+    assert unchanged_ranges_new(a, b) == [(1, 2), (4, 4)]
+
+
+def test_unchanged_ranges_edges():
+    """Test identical, empty and completely different inputs."""
+    a = ['x', 'y', 'z']
+    assert unchanged_ranges_old(a, a) == [(1, 3)]
+    assert unchanged_ranges_new(a, a) == [(1, 3)]
+    assert unchanged_ranges_new([], a) == []
+    assert unchanged_ranges_old(a, ['q']) == []
+
+
+def _expand(ranges):
+    return [line for first, last in ranges for line in range(first, last + 1)]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_unchanged_ranges_complement_masks(seed):
+    """Test that the ranges cover exactly the lines the masks leave unset."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 15)}" for _ in range(random.randint(0, 60))]
+    b = [f"line{random.randint(0, 15)}" for _ in range(random.randint(0, 60))]
+    old, new = unchanged_ranges_old(a, b), unchanged_ranges_new(a, b)
+    assert _expand(old) == [i + 1 for i, changed in enumerate(change_mask_old(a, b)) if not changed]
+    assert _expand(new) == [j + 1 for j, changed in enumerate(change_mask_new(a, b)) if not changed]
+    assert all(prev[1] + 1 < cur[0] for prev, cur in zip(old, old[1:]))
+    assert all(prev[1] + 1 < cur[0] for prev, cur in zip(new, new[1:]))
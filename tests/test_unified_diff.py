@@ -591,5 +591,307 @@ def test_known_examples():
     assert '+tree' in rust_content
 
 
+
+def test_brace_scope_context_keeps_block_together():
+    """Test that brace_scope_context extends context to close an open scope."""
+    a = [
+        'int main() {',
+        '    int x = 1;',
+        '    if (x) {',
+        '        x = 2;',
+        '        x = 3;',
+        '        x = 4;',
+        '    }',
+        '    return x;',
+        '}',
+    ]
+    b = list(a)
+    b[2] = '    if (x > 0) {'
+
+    plain = rust_unified_diff(a, b, 'a', 'b', n=1)
+    scoped = rust_unified_diff(a, b, 'a', 'b', n=1, brace_scope_context=10)
+
+    assert plain == list(difflib.unified_diff(a, b, 'a', 'b', n=1))
+    assert '     }' not in plain
+    assert '     }' in scoped
+    assert scoped[2] == '@@ -2,6 +2,6 @@\n'
+
+
+def test_brace_scope_context_respects_limit():
+    """Test that scopes needing more than the allowed extra context are left alone."""
+    a = ['void f() {'] + [f'    stmt_{i};' for i in range(20)] + ['}']
+    b = list(a)
+    b[0] = 'void g() {'
+
+    plain = rust_unified_diff(a, b, 'a', 'b', n=3)
+    assert rust_unified_diff(a, b, 'a', 'b', n=3, brace_scope_context=5) == plain
+    assert rust_unified_diff(a, b, 'a', 'b', n=3, brace_scope_context=30)[-1] == ' }'
+
+
+def test_brace_scope_context_merges_touching_hunks():
+    """Test that hunks whose extended context meets are merged into one."""
+    a = ['f {', 'x', 'p', 'q', 'r', 's', '}', 'y']
+    b = list(a)
+    b[1] = 'X'
+    b[7] = 'Y'
+
+    plain = rust_unified_diff(a, b, 'a', 'b', n=1)
+    scoped = rust_unified_diff(a, b, 'a', 'b', n=1, brace_scope_context=10)
+
+    assert sum(line.startswith('@@') for line in plain) == 2
+    assert sum(line.startswith('@@') for line in scoped) == 1
+    assert scoped[2] == '@@ -1,8 +1,8 @@\n'
+
+
+def test_brace_scope_context_no_braces_matches_python():
+    """Test that brace_scope_context is a no-op for text without braces."""
+    a = [f'line_{i}' for i in range(40)]
+    b = list(a)
+    b[5] = 'changed'
+    b[30] = 'changed'
+    expected = list(difflib.unified_diff(a, b, 'a', 'b'))
+    assert rust_unified_diff(a, b, 'a', 'b', brace_scope_context=10) == expected
+
+
+
+def test_window_size_larger_than_input_matches_python():
+    """Test that a window covering both inputs gives the exact diff."""
+    a = [f"line_{i}" for i in range(100)]
+    b = a[:10] + ['inserted'] + a[10:50] + a[55:]
+    expected = list(difflib.unified_diff(a, b, 'a', 'b'))
+    assert rust_unified_diff(a, b, 'a', 'b', window_size=1000) == expected
+
+
+@pytest.mark.parametrize("window_size", [8, 50, 200])
+def test_window_size_unique_lines_matches_python(window_size):
+    """Test that windowed diffs of unique lines with sparse changes are exact."""
+    a = [f"line_{i:04d}" for i in range(1000)]
+    b = list(a)
+    for i in range(7, 1000, 97):
+        b[i] = f"changed_{i}"
+    del b[500:503]
+    b.insert(800, 'inserted')
+
+    expected = list(difflib.unified_diff(a, b, 'a', 'b'))
+    assert rust_unified_diff(a, b, 'a', 'b', window_size=window_size) == expected
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_window_size_reconstructs_both_inputs(seed):
+    """Test that windowed diffs stay valid edits even when not minimal."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 30)}" for _ in range(300)]
+    b = [f"line{random.randint(0, 30)}" for _ in range(random.randint(200, 400))]
+
+    n = max(len(a), len(b))
+    result = rust_unified_diff(a, b, 'a', 'b', n=n, lineterm='', window_size=40)
+    body = result[3:]
+    assert [line[1:] for line in body if line[0] in ' -'] == a
+    assert [line[1:] for line in body if line[0] in ' +'] == b
+
+
+def test_window_size_zero_rejected():
+    """Test that window_size=0 raises ValueError."""
+    with pytest.raises(ValueError):
+        rust_unified_diff(['a'], ['b'], window_size=0)
+
+
+
+def python_unified_diff_with_junk(a, b, isjunk, n=3, autojunk=True):
+    """Python's unified_diff body, but with a configurable SequenceMatcher."""
+    result = []
+    for group in difflib.SequenceMatcher(isjunk, a, b, autojunk=autojunk).get_grouped_opcodes(n):
+        if not result:
+            result += ['--- a\n', '+++ b\n']
+        first, last = group[0], group[-1]
+        file1_range = difflib._format_range_unified(first[1], last[2])
+        file2_range = difflib._format_range_unified(first[3], last[4])
+        result.append(f'@@ -{file1_range} +{file2_range} @@\n')
+        for tag, i1, i2, j1, j2 in group:
+            if tag == 'equal':
+                result += [' ' + line for line in a[i1:i2]]
+                continue
+            result += ['-' + line for line in a[i1:i2]]
+            result += ['+' + line for line in b[j1:j2]]
+    return result
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_isjunk_matches_python(seed):
+    """Test that isjunk gives the same diff as Python's SequenceMatcher."""
+    random.seed(seed)
+    choices = ['', '', '#', 'x = 1', 'y = 2', 'z = 3', 'return', 'pass']
+    a = [random.choice(choices) for _ in range(60)]
+    b = [random.choice(choices) for _ in range(60)]
+    isjunk = lambda line: line in ('', '#')
+
+    expected = python_unified_diff_with_junk(a, b, isjunk)
+    assert rust_unified_diff(a, b, 'a', 'b', isjunk=isjunk) == expected
+
+
+def test_isjunk_errors_propagate():
+    """Test that exceptions raised by isjunk reach the caller."""
+    def isjunk(line):
+        raise RuntimeError("boom")
+
+    with pytest.raises(RuntimeError):
+        rust_unified_diff(['a'], ['b'], isjunk=isjunk)
+
+
+def test_symmetric_junk_blank_lines_do_not_anchor():
+    """Test that lines popular in a stop anchoring matches with symmetric_junk."""
+    a = []
+    for i in range(150):
+        a += [f"old_{i}", '']
+    b = [f"new_{i}" for i in range(100)] + [''] + [f"new_{i}" for i in range(100, 200)]
+
+    plain = rust_unified_diff(a, b, 'a', 'b', n=0)
+    symmetric = rust_unified_diff(a, b, 'a', 'b', n=0, symmetric_junk=True)
+
+    assert plain == list(difflib.unified_diff(a, b, 'a', 'b', n=0))
+    assert sum(line.startswith('@@') for line in plain) == 2
+    assert sum(line.startswith('@@') for line in symmetric) == 1
+
+
+def test_symmetric_junk_extends_matches():
+    """Test that junk on both sides still extends an anchored match."""
+    a = (['', 'keep_1', '', 'keep_2', ''] + [f"a_{i}" for i in range(100)]) * 2
+    b = (['', 'keep_1', '', 'keep_2', ''] + [f"b_{i}" for i in range(100)]) * 2
+
+    result = rust_unified_diff(a, b, 'a', 'b', n=0, lineterm='', symmetric_junk=True)
+    body = [line for line in result[2:] if not line.startswith('@@')]
+    assert all(line[1:] not in ('', 'keep_1', 'keep_2') for line in body)
+
+
+
+@pytest.mark.parametrize("n", [1, 2, 3])
+def test_compact_merges_hunks_with_small_gaps(n):
+    """Test that compact joins hunks separated by up to 3n equal lines."""
+    lines = [f"line_{i:02d}" for i in range(60)]
+    for gap, merged in [(2 * n + 1, True), (3 * n, True), (3 * n + 1, False)]:
+        modified = list(lines)
+        modified[10] = 'CHANGED_1'
+        modified[11 + gap] = 'CHANGED_2'
+
+        plain = rust_unified_diff(lines, modified, 'a', 'b', n=n)
+        compact = rust_unified_diff(lines, modified, 'a', 'b', n=n, compact=True)
+        assert sum(line.startswith('@@') for line in plain) == 2
+        assert sum(line.startswith('@@') for line in compact) == (1 if merged else 2)
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_compact_ranges_stay_correct(seed):
+    """Test that every compact hunk header matches the lines it covers."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 40)}" for _ in range(200)]
+    b = list(a)
+    for i in random.sample(range(200), 15):
+        b[i] = 'changed'
+
+    result = rust_unified_diff(a, b, 'a', 'b', n=2, lineterm='', compact=True)
+    hunks = []
+    for line in result[2:]:
+        if line.startswith('@@'):
+            hunks.append((line, []))
+        else:
+            hunks[-1][1].append(line)
+
+    for header, body in hunks:
+        old, new = header.split()[1:3]
+        old_start, old_len = (list(map(int, old[1:].split(','))) + [1])[:2]
+        new_start, new_len = (list(map(int, new[1:].split(','))) + [1])[:2]
+        old_lines = [line[1:] for line in body if line[0] in ' -']
+        new_lines = [line[1:] for line in body if line[0] in ' +']
+        assert len(old_lines) == old_len and len(new_lines) == new_len
+        assert a[old_start - 1:old_start - 1 + old_len] == old_lines
+        assert b[new_start - 1:new_start - 1 + new_len] == new_lines
+
+
+def test_compact_no_context_unchanged():
+    """Test that compact has no effect when n=0."""
+    a = [f"line_{i}" for i in range(20)]
+    b = list(a)
+    b[3] = 'x'
+    b[5] = 'y'
+    expected = list(difflib.unified_diff(a, b, 'a', 'b', n=0))
+    assert rust_unified_diff(a, b, 'a', 'b', n=0, compact=True) == expected
+
+
+
+@pytest.mark.parametrize("len_a,len_b,header", [
+    (1, 1, '@@ -1 +1 @@'),
+    (1, 3, '@@ -1 +1,3 @@'),
+    (3, 1, '@@ -1,3 +1 @@'),
+    (4, 5, '@@ -1,4 +1,5 @@'),
+    (0, 2, '@@ -0,0 +1,2 @@'),
+    (2, 0, '@@ -1,2 +0,0 @@'),
+])
+def test_fully_disjoint_inputs(len_a, len_b, header):
+    """Test that inputs with no common lines give one all-replace hunk."""
+    a = [f"old_{i}" for i in range(len_a)]
+    b = [f"new_{i}" for i in range(len_b)]
+
+    result = rust_unified_diff(a, b, 'a', 'b', lineterm='')
+    assert result == list(difflib.unified_diff(a, b, 'a', 'b', lineterm=''))
+    assert result == ['--- a', '+++ b', header] + ['-' + x for x in a] + ['+' + x for x in b]
+
+
+def test_autojunk_disabled_matches_python():
+    """Test that autojunk=False lets popular lines anchor matches."""
+    a = ["}" if i % 3 else f"stmt {i}" for i in range(300)]
+    b = a[:]
+    b[1:3] = ["changed"]
+    b[100:130] = ["}"] * 30
+
+    for autojunk in (True, False):
+        expected = python_unified_diff_with_junk(a, b, None, autojunk=autojunk)
+        assert rust_unified_diff(a, b, 'a', 'b', autojunk=autojunk) == expected
+    assert rust_unified_diff(a, b, autojunk=False) != rust_unified_diff(a, b)
+
+
+@pytest.mark.parametrize("seed", range(10))
+@pytest.mark.parametrize("options", [
+    {},
+    {'n': 0},
+    {'compact': True},
+    {'brace_scope_context': 3},
+    {'window_size': 16},
+    {'isjunk': lambda line: line == '', 'symmetric_junk': True},
+])
+def test_validate_accepts_real_diffs(seed, options):
+    """Test that validation passes and leaves the output unchanged."""
+    random.seed(seed)
+    pool = ['', '{', '}', 'x = 1', 'y = 2', 'return x'] + [f"line {i}" for i in range(20)]
+    a = [random.choice(pool) for _ in range(random.randint(0, 80))]
+    b = [random.choice(pool) for _ in range(random.randint(0, 80))]
+    expected = rust_unified_diff(a, b, 'a', 'b', **options)
+    assert rust_unified_diff(a, b, 'a', 'b', validate=True, **options) == expected
+
+
+def test_min_ratio_short_circuits_unrelated_files():
+    """Test that dissimilar inputs give a single notice line."""
+    a = [f"old {i}" for i in range(9)] + ['shared']
+    b = [f"new {i}" for i in range(9)] + ['shared']
+    ratio = difflib.SequenceMatcher(None, a, b).ratio()
+    assert rust_unified_diff(a, b, min_ratio=0.5) == [f'@@ files differ substantially (ratio={ratio:.2f}) @@\n']
+    assert rust_unified_diff(a, b, lineterm='', min_ratio=0.5) == ['@@ files differ substantially (ratio=0.10) @@']
+
+
+def test_min_ratio_above_floor_keeps_diff():
+    """Test that similar or identical inputs are diffed as usual."""
+    a = [f"line {i}" for i in range(10)]
+    b = a[:4] + ['changed'] + a[5:]
+    assert rust_unified_diff(a, b, min_ratio=0.5) == rust_unified_diff(a, b)
+    assert rust_unified_diff(a, b, min_ratio=0.9) == rust_unified_diff(a, b)
+    assert rust_unified_diff(a, a, min_ratio=1.0) == []
+
+
+@pytest.mark.parametrize("min_ratio", [-0.1, 1.5, float('nan')])
+def test_min_ratio_rejects_out_of_range(min_ratio):
+    """Test that min_ratio outside [0, 1] raises ValueError."""
+    with pytest.raises(ValueError):
+        rust_unified_diff(['a'], ['b'], min_ratio=min_ratio)
+
+
 if __name__ == "__main__":
     pytest.main([__file__, "-v"])
\ No newline at end of file
@@ -0,0 +1,91 @@
+"""Test unified diffs of bytes lines."""
+
+import difflib
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_bytes
+
+
+def test_matches_difflib_diff_bytes():
+    """Test that the output matches difflib.diff_bytes."""
+    a = [b"one\n", b"two\n", b"three\n", b"four\n"]
+    b = [b"one\n", b"2\n", b"three\n", b"four\n", b"five\n"]
+    expected = list(difflib.diff_bytes(difflib.unified_diff, a, b, b"a.txt", b"b.txt", b"then", b"now"))
+    assert unified_diff_bytes(a, b, b"a.txt", b"b.txt", b"then", b"now") == expected
+
+
+def test_invalid_utf8_round_trips():
+    """Test that bytes that aren't UTF-8 come out unchanged."""
+    a = [b"caf\xe9\n", b"\xff\xfe raw\n", b"same\n"]
+    b = [b"caf\xe8\n", b"\xff\xfe raw\n", b"same\n"]
+    diff = unified_diff_bytes(a, b, fromfile=b"latin-1 \xe9")
+    assert diff == [
+        b"--- latin-1 \xe9\n",
+        b"+++ \n",
+        b"@@ -1,3 +1,3 @@\n",
+        b"-caf\xe9\n",
+        b"+caf\xe8\n",
+        b" \xff\xfe raw\n",
+        b" same\n",
+    ]
+    assert all(isinstance(line, bytes) for line in diff)
+
+
+def test_lines_are_compared_byte_for_byte():
+    """Test that lines differing in one byte beyond ASCII differ."""
+    assert unified_diff_bytes([b"\x80"], [b"\x80"]) == []
+    assert unified_diff_bytes([b"\x80"], [b"\x81"], lineterm=b"")[2:] == [b"@@ -1 +1 @@", b"-\x80", b"+\x81"]
+
+
+def test_bytes_lineterm():
+    """Test that a bytes lineterm ends the headers."""
+    diff = unified_diff_bytes([b"a"], [b"b"], lineterm=b"\r\n")
+    assert diff[:3] == [b"--- \r\n", b"+++ \r\n", b"@@ -1 +1 @@\r\n"]
+
+
+def test_same_as_str_for_ascii():
+    """Test that ASCII input gives the encoded str diff."""
+    a = [f"line {i}" for i in range(20)]
+    b = a[:5] + ["x"] + a[7:]
+    expected = [line.encode() for line in unified_diff(a, b, n=2, lineterm="")]
+    assert unified_diff_bytes([line.encode() for line in a], [line.encode() for line in b], n=2, lineterm=b"") == expected
+
+
+def test_str_lines_are_rejected():
+    """Test that str lines aren't accepted in place of bytes."""
+    with pytest.raises(TypeError):
+        unified_diff_bytes(["a"], [b"b"])
+
+
+def test_binary_files_differ():
+    """Test that a NUL byte on either side gives a single GNU-style line."""
+    text = [b"header\n", b"body\n"]
+    binary = [b"\x89PNG\r\n", b"\x00\x00\x01\n"]
+    assert unified_diff_bytes(text, binary, b"a.png", b"b.png") == [b"Binary files a.png and b.png differ\n"]
+    assert unified_diff_bytes(binary, text, lineterm=b"") == [b"Binary files  and  differ"]
+
+
+def test_identical_binary_has_no_diff():
+    """Test that equal binary inputs still give an empty diff."""
+    binary = [b"\x00\x01\x02"]
+    assert unified_diff_bytes(binary, list(binary)) == []
+
+
+def test_binary_threshold():
+    """Test that control bytes over the threshold make an input binary."""
+    a = [b"ab\x01\x02\n"]
+    b = [b"ab\x01\x03\n"]
+    # 2 control bytes of 5
+    assert unified_diff_bytes(a, b, binary_threshold=0.5)[2:] == [b"@@ -1 +1 @@\n", b"-ab\x01\x02\n", b"+ab\x01\x03\n"]
+    assert unified_diff_bytes(a, b, binary_threshold=0.3) == [b"Binary files  and  differ\n"]
+    # Whitespace and backspace don't count
+    assert len(unified_diff_bytes([b"\t\t\x08\x0c\r\n"], [b"\t\n"], binary_threshold=0.0)) > 1
+    with pytest.raises(ValueError, match="binary_threshold"):
+        unified_diff_bytes(a, b, binary_threshold=1.5)
+
+
+def test_text_only():
+    """Test that text_only diffs lines of binary inputs."""
+    a = [b"\x00one\n"]
+    b = [b"\x00two\n"]
+    assert unified_diff_bytes(a, b, text_only=True)[3:] == [b"-\x00one\n", b"+\x00two\n"]
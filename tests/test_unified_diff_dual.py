@@ -0,0 +1,54 @@
+"""Test unified_diff_dual, plain and colored diffs from one computation."""
+
+import random
+import re
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_dual
+
+RESET = "\x1b[0m"
+ESCAPE = re.compile(r"\x1b\[[0-9;]*m")
+
+
+def test_colors_each_kind_of_line():
+    """Test the colors of headers, hunk headers, context, removed and added lines."""
+    a = ['one\n', 'two\n', 'three\n']
+    b = ['one\n', 'tree\n', 'three\n', 'four\n']
+    plain, colored = unified_diff_dual(a, b, 'a.txt', 'b.txt')
+    assert plain == unified_diff(a, b, 'a.txt', 'b.txt')
+    assert colored == [
+        f"\x1b[1m--- a.txt\n{RESET}",
+        f"\x1b[1m+++ b.txt\n{RESET}",
+        f"\x1b[34m@@ -1,3 +1,4 @@\n{RESET}",
+        f"{RESET} one\n{RESET}",
+        f"\x1b[31m-two\n{RESET}",
+        f"\x1b[32m+tree\n{RESET}",
+        f"{RESET} three\n{RESET}",
+        f"\x1b[32m+four\n{RESET}",
+    ]
+
+
+def test_identical_inputs():
+    """Test that equal inputs give two empty diffs."""
+    assert unified_diff_dual(['a\n'], ['a\n']) == ([], [])
+
+
+def test_content_that_looks_like_headers():
+    """Test that lines starting with --- or @@ are colored by their side, not their text."""
+    a = ['-- old\n', '@@ x\n']
+    b = ['++ new\n', '@@ x\n']
+    plain, colored = unified_diff_dual(a, b, n=1, lineterm='')
+    assert colored[3:] == [f"\x1b[31m--- old\n{RESET}", f"\x1b[32m+++ new\n{RESET}", f"{RESET} @@ x\n{RESET}"]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_colored_is_plain_with_escapes(seed):
+    """Test that removing the escapes from the colored lines gives the plain ones."""
+    rng = random.Random(seed)
+    a = [f"l{rng.randint(0, 20)}\n" for _ in range(60)]
+    b = [line if rng.random() < 0.8 else "x\n" for line in a]
+    n = rng.randint(0, 4)
+    plain, colored = unified_diff_dual(a, b, 'a', 'b', n=n)
+    assert plain == unified_diff(a, b, 'a', 'b', n=n)
+    assert [ESCAPE.sub('', line) for line in colored] == plain
+    assert all(line.endswith(RESET) for line in colored)
@@ -0,0 +1,68 @@
+"""Test the unified_diff_range function."""
+
+import difflib
+import random
+import re
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_range
+
+
+def shift_headers(lines, a_start, b_start):
+    """Renumber the hunk headers of a diff of slices to whole-file lines."""
+    def shift(match):
+        return f"@@ -{int(match[1]) + a_start}{match[2]} +{int(match[3]) + b_start}{match[4]} @@"
+    return [re.sub(r'^@@ -(\d+)((?:,\d+)?) \+(\d+)((?:,\d+)?) @@', shift, line) for line in lines]
+
+
+def test_range_covers_whole_files():
+    """Test that full ranges give the same output as unified_diff."""
+    a = [f"line {i}" for i in range(30)]
+    b = a[:10] + ['new'] + a[12:]
+    assert unified_diff_range(a, b, (0, len(a)), (0, len(b)), 'a', 'b') == unified_diff(a, b, 'a', 'b')
+
+
+def test_range_line_numbers_offset():
+    """Test that hunk headers are numbered as in the whole files."""
+    a = [f"line {i}" for i in range(300)]
+    b = a[:150] + ['changed'] + a[151:]
+    result = unified_diff_range(a, b, (100, 200), (100, 200), 'a', 'b', lineterm='')
+    assert result == ['--- a', '+++ b', '@@ -148,7 +148,7 @@',
+                      ' line 147', ' line 148', ' line 149', '-line 150', '+changed',
+                      ' line 151', ' line 152', ' line 153']
+    assert result == unified_diff(a, b, 'a', 'b', lineterm='')
+
+
+def test_range_ignores_changes_outside():
+    """Test that lines outside the ranges are never compared."""
+    a = ['x'] * 5 + [f"line {i}" for i in range(10)] + ['y'] * 5
+    b = ['X'] * 5 + [f"line {i}" for i in range(10)] + ['Y'] * 5
+    assert unified_diff_range(a, b, (5, 15), (5, 15)) == []
+    assert unified_diff_range(a, b, (0, 5), (0, 5), lineterm='')[2] == '@@ -1,5 +1,5 @@'
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_range_matches_python_on_slices(seed):
+    """Test against Python's unified_diff of the slices, renumbered."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 30)}" for _ in range(120)]
+    b = [f"line{random.randint(0, 30)}" for _ in range(120)]
+    alo, ahi = sorted(random.sample(range(121), 2))
+    blo, bhi = sorted(random.sample(range(121), 2))
+    expected = shift_headers(list(difflib.unified_diff(a[alo:ahi], b[blo:bhi], 'a', 'b', lineterm='')), alo, blo)
+    assert unified_diff_range(a, b, (alo, ahi), (blo, bhi), 'a', 'b', lineterm='') == expected
+
+
+def test_range_empty_side():
+    """Test an empty range on one side, e.g. a pure insertion."""
+    a = ['a', 'b', 'c']
+    b = ['a', 'new', 'b', 'c']
+    assert unified_diff_range(a, b, (1, 1), (1, 2), lineterm='')[2:] == ['@@ -1,0 +2 @@', '+new']
+
+
+@pytest.mark.parametrize("a_range,b_range", [((5, 2), (0, 3)), ((0, 3), (0, 11)), ((0, 11), (0, 3))])
+def test_range_validation(a_range, b_range):
+    """Test that inverted and out-of-bounds ranges raise ValueError."""
+    lines = [str(i) for i in range(10)]
+    with pytest.raises(ValueError):
+        unified_diff_range(lines, lines, a_range, b_range)
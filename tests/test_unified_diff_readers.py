@@ -0,0 +1,49 @@
+"""Test the unified_diff_readers function."""
+
+import io
+import os
+import tempfile
+
+import pytest
+from difflib_rs import unified_diff, unified_diff_readers
+
+A = [f"line {i}\n" for i in range(50)]
+B = A[:10] + ['changed\n'] + A[11:40] + A[42:] + ['extra\n']
+
+
+def test_readers_match_unified_diff():
+    """Test that diffing file objects gives the list diff."""
+    diff = unified_diff_readers(io.StringIO(''.join(A)), io.StringIO(''.join(B)), 'a', 'b', n=2)
+    assert diff == unified_diff(A, B, 'a', 'b', n=2)
+
+
+def test_readers_from_files():
+    """Test real file handles, without a final newline."""
+    with tempfile.TemporaryDirectory() as tmp:
+        paths = [os.path.join(tmp, name) for name in ('a.txt', 'b.txt')]
+        for path, lines in zip(paths, (A, B[:-1] + ['no newline'])):
+            with open(path, 'w') as f:
+                f.writelines(lines)
+        with open(paths[0]) as fa, open(paths[1]) as fb:
+            diff = unified_diff_readers(fa, fb)
+    assert diff == unified_diff(A, B[:-1] + ['no newline'])
+    assert diff[-1] == '+no newline'
+
+
+def test_readers_write_to_out():
+    """Test that the diff is written to out instead of returned."""
+    out = io.StringIO()
+    result = unified_diff_readers(io.StringIO(''.join(A)), io.StringIO(''.join(B)), out=out)
+    assert result is None
+    assert out.getvalue() == ''.join(unified_diff(A, B))
+    out = io.StringIO()
+    assert unified_diff_readers(io.StringIO('same\n'), io.StringIO('same\n'), out=out) is None
+    assert out.getvalue() == ''
+
+
+def test_readers_reject_bytes():
+    """Test that files opened in binary mode are rejected."""
+    with pytest.raises(TypeError):
+        unified_diff_readers(io.BytesIO(b'a\n'), io.BytesIO(b'b\n'))
+    with pytest.raises(TypeError):
+        unified_diff_readers('a\n', io.StringIO('b\n'))
@@ -505,4 +505,43 @@ def test_unified_diff_str_keepends_vs_baseline(keepends):
         )
     
     # Performance should be competitive
-    assert optimized_time <= baseline_time * 2, f"Optimized version should be competitive for keepends={keepends}"
\ No newline at end of file
+    assert optimized_time <= baseline_time * 2, f"Optimized version should be competitive for keepends={keepends}"
+
+
+def test_track_final_newline_added():
+    """Test that adding a final newline is reported as a deleted sentinel line."""
+    a, b = "one\ntwo", "one\ntwo\n"
+    assert unified_diff_str(a, b, 'a', 'b', lineterm='') == []
+    result = unified_diff_str(a, b, 'a', 'b', lineterm='', track_final_newline=True)
+    assert result == ['--- a', '+++ b', '@@ -1,3 +1,2 @@', ' one', ' two', '-']
+
+
+def test_track_final_newline_removed():
+    """Test that removing a final newline is reported as an inserted sentinel line."""
+    result = unified_diff_str("one\ntwo\n", "one\ntwo", 'a', 'b', lineterm='', track_final_newline=True)
+    assert result == ['--- a', '+++ b', '@@ -1,2 +1,3 @@', ' one', ' two', '+']
+
+
+def test_track_final_newline_both_present():
+    """Test that final newlines on both sides add nothing to the diff."""
+    result = unified_diff_str("x\ny\n", "x\nz\n", 'a', 'b', lineterm='', track_final_newline=True)
+    assert result == ['--- a', '+++ b', '@@ -1,2 +1,2 @@', ' x', '-y', '+z']
+
+
+def test_track_final_newline_both_missing():
+    """Test that a final newline missing on both sides is an unchanged sentinel line."""
+    result = unified_diff_str("x\ny", "x\nz", 'a', 'b', lineterm='', track_final_newline=True)
+    assert result == ['--- a', '+++ b', '@@ -1,3 +1,3 @@', ' x', '-y', '+z', ' ']
+
+
+def test_track_final_newline_empty():
+    """Test that two empty texts still have no diff."""
+    assert unified_diff_str("", "", track_final_newline=True) == []
+
+
+def test_track_final_newline_list_api():
+    """Test the list API with lines that keep their endings."""
+    a = ['one\n', 'two\n']
+    b = ['one\n', 'two']
+    result = unified_diff(a, b, 'a', 'b', track_final_newline=True)
+    assert result == ['--- a\n', '+++ b\n', '@@ -1,2 +1,3 @@\n', ' one\n', '-two\n', '+two', '+']
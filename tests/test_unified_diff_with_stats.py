@@ -0,0 +1,60 @@
+"""Test the unified_diff_with_stats function."""
+
+import pytest
+import random
+from difflib_rs import unified_diff, unified_diff_with_stats
+
+
+def count_stats(diff):
+    """Count (added, removed, hunks) from rendered diff lines."""
+    body = diff[2:]
+    added = sum(1 for line in body if line.startswith('+'))
+    removed = sum(1 for line in body if line.startswith('-'))
+    hunks = sum(1 for line in body if line.startswith('@@'))
+    return added, removed, hunks
+
+
+def test_with_stats_basic():
+    """Test the text and counts for a small diff."""
+    a = ['one', 'two', 'three', 'four']
+    b = ['zero', 'one', 'tree', 'four']
+    lines, stats = unified_diff_with_stats(a, b, 'Original', 'Current')
+    assert lines == unified_diff(a, b, 'Original', 'Current')
+    assert stats == (2, 2, 1)
+
+
+def test_with_stats_identical():
+    """Test that identical inputs give no lines and zero counts."""
+    a = ['same'] * 5
+    assert unified_diff_with_stats(a, a) == ([], (0, 0, 0))
+
+
+@pytest.mark.parametrize("n", [0, 1, 3])
+def test_with_stats_matches_separate_calls(n):
+    """Test against unified_diff and counts taken from its output."""
+    random.seed(n)
+    for _ in range(20):
+        a = [f"line{random.randint(0, 20)}" for _ in range(80)]
+        b = [f"line{random.randint(0, 20)}" for _ in range(80)]
+        lines, stats = unified_diff_with_stats(a, b, 'a', 'b', n=n)
+        assert lines == unified_diff(a, b, 'a', 'b', n=n)
+        assert stats == count_stats(lines)
+
+
+def test_with_stats_accepts_unified_diff_options():
+    """Test that matcher options are honored the same way as in unified_diff."""
+    a = [f"line_{i}" for i in range(200)]
+    b = a[:50] + ['new'] + a[60:]
+    kwargs = dict(n=2, window_size=64, brace_scope_context=4)
+    lines, stats = unified_diff_with_stats(a, b, **kwargs)
+    assert lines == unified_diff(a, b, **kwargs)
+    assert stats == (1, 10, 1)
+
+
+def test_with_stats_min_ratio_keeps_counts():
+    """Test that the min_ratio notice replaces the lines but not the counts."""
+    a = ['a1', 'a2', 'a3']
+    b = ['b1', 'b2']
+    lines, stats = unified_diff_with_stats(a, b, min_ratio=0.5)
+    assert lines == ['@@ files differ substantially (ratio=0.00) @@\n']
+    assert stats == (2, 3, 1)
@@ -0,0 +1,102 @@
+"""Test anchoring diffs on lines unique to both sides."""
+
+import random
+
+import pytest
+from difflib_rs import get_grouped_opcodes, get_opcodes, unified_diff, verify_diff
+
+# Moving `first` below `second` gives the plain matcher a longer run of
+# common lines ("}" and blanks) to latch onto than the function bodies.
+A = [
+    "def first():",
+    "    return 1",
+    "}",
+    "",
+    "def second():",
+    "    return 2",
+    "}",
+    "",
+]
+B = [
+    "def second():",
+    "    return 2",
+    "}",
+    "",
+    "def first():",
+    "    return 1",
+    "}",
+    "",
+]
+
+
+def apply(a, b, opcodes):
+    result = []
+    for tag, i1, i2, j1, j2 in opcodes:
+        result.extend(a[i1:i2] if tag == "equal" else b[j1:j2])
+    return result
+
+
+def test_anchors_are_kept_in_order():
+    """Test that unique lines in the same order on both sides are matched."""
+    a = ["x", "{", "unique a", "}", "{", "unique b", "}"]
+    b = ["{", "}", "unique a", "{", "unique b", "}", "y"]
+    ops = get_opcodes(a, b, unique_anchors=True)
+    equal_pairs = {(i1 + k, j1 + k) for tag, i1, i2, j1, j2 in ops if tag == "equal" for k in range(i2 - i1)}
+    assert (2, 2) in equal_pairs
+    assert (5, 4) in equal_pairs
+
+
+def test_moved_block_keeps_unique_lines_matched():
+    """Test that a moved function matches on its unique lines."""
+    ops = get_opcodes(A, B, unique_anchors=True)
+    matched = [a for tag, i1, i2, _, _ in ops if tag == "equal" for a in A[i1:i2]]
+    assert "def second():" in matched or "def first():" in matched
+    assert apply(A, B, ops) == B
+
+
+def test_off_by_default():
+    """Test that the default matches plain difflib behavior."""
+    assert get_opcodes(A, B) == get_opcodes(A, B, unique_anchors=False)
+
+
+@pytest.mark.parametrize("seed", range(20))
+def test_opcodes_rebuild_b(seed):
+    """Test that anchored opcodes are always a valid edit script."""
+    random.seed(seed)
+    vocabulary = ["}", "", "{"] + [f"line {i}" for i in range(30)]
+    a = [random.choice(vocabulary) for _ in range(random.randrange(80))]
+    b = [random.choice(vocabulary) for _ in range(random.randrange(80))]
+    ops = get_opcodes(a, b, unique_anchors=True)
+    assert apply(a, b, ops) == b
+    for tag, i1, i2, j1, j2 in ops:
+        if tag == "equal":
+            assert a[i1:i2] == b[j1:j2]
+    assert get_grouped_opcodes(a, b, unique_anchors=True) == get_grouped_opcodes(a, b, unique_anchors=True)
+    assert verify_diff(a, b, unique_anchors=True)
+
+
+def test_unique_lines_anchor_against_longer_common_run():
+    """Test that a unique line wins over a longer run of repeated lines."""
+    a = ["unique", "}", "}", "}", "tail"]
+    b = ["}", "}", "}", "head", "unique"]
+    plain = get_opcodes(a, b)
+    anchored = get_opcodes(a, b, unique_anchors=True)
+    assert ("equal", 1, 4, 0, 3) in plain
+    assert ("equal", 0, 1, 4, 5) in anchored
+
+
+def test_junk_lines_are_not_anchors():
+    """Test that lines isjunk accepts never become anchors."""
+    a = ["junk 1", "junk 2", "keep"]
+    b = ["keep", "junk 1", "junk 2"]
+    ops = get_opcodes(a, b, isjunk=lambda line: line.startswith("junk"), unique_anchors=True)
+    assert apply(a, b, ops) == b
+    assert ("equal", 2, 3, 0, 1) in ops
+    assert ("equal", 0, 2, 1, 3) in get_opcodes(a, b, unique_anchors=True)
+
+
+def test_unified_diff_option():
+    """Test that unified_diff accepts the option and stays a valid diff."""
+    diff = unified_diff(A, B, lineterm="", unique_anchors=True)
+    assert diff[0:2] == ["--- ", "+++ "]
+    assert diff != []
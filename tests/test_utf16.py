@@ -0,0 +1,54 @@
+"""Test the UTF-16 code unit helpers."""
+
+import difflib
+import random
+
+import pytest
+from difflib_rs import char_opcodes_utf16, utf16_len
+
+
+def utf16_offset(text, index):
+    """UTF-16 code unit offset of a char index of text."""
+    return len(text[:index].encode('utf-16-le')) // 2
+
+
+def test_utf16_len():
+    """Test lengths of BMP and astral-plane text."""
+    assert utf16_len("") == 0
+    assert utf16_len("abc") == 3
+    assert utf16_len("é€") == 2
+    assert utf16_len("\U0001F600") == 2
+    assert utf16_len("a\U0001F600b\U00010348") == 6
+
+
+def test_char_opcodes_utf16_astral():
+    """Test that astral-plane characters shift offsets by two units."""
+    a = "\U0001F600 hello"
+    b = "\U0001F600 world"
+    ops = char_opcodes_utf16(a, b)
+    assert ops[0] == ('equal', 0, 3, 0, 3)
+    assert ops[-1][2] == utf16_len(a)
+    assert ops[-1][4] == utf16_len(b)
+
+
+def test_char_opcodes_utf16_replaced_emoji():
+    """Test replacing one astral character with another."""
+    assert char_opcodes_utf16("x\U0001F600y", "x\U0001F601y") == [
+        ('equal', 0, 1, 0, 1),
+        ('replace', 1, 3, 1, 3),
+        ('equal', 3, 4, 3, 4),
+    ]
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_char_opcodes_utf16_matches_python(seed):
+    """Test against Python's char opcodes mapped to UTF-16 offsets."""
+    random.seed(seed)
+    alphabet = ['a', 'b', 'é', '\U0001F600', '\U00010348']
+    a = ''.join(random.choice(alphabet) for _ in range(random.randint(0, 30)))
+    b = ''.join(random.choice(alphabet) for _ in range(random.randint(0, 30)))
+    expected = [
+        (tag, utf16_offset(a, i1), utf16_offset(a, i2), utf16_offset(b, j1), utf16_offset(b, j2))
+        for tag, i1, i2, j1, j2 in difflib.SequenceMatcher(None, a, b).get_opcodes()
+    ]
+    assert char_opcodes_utf16(a, b) == expected
@@ -0,0 +1,97 @@
+"""Test verify_diff, and that diffs round-trip under every matching and grouping option."""
+
+import random
+
+import pytest
+from difflib_rs import verify_diff
+
+
+def random_pair(rng):
+    """Lines with repeats, blanks, braces and a possibly missing final newline, and an edit of them."""
+    pool = ['{\n', '}\n', '\n', '    x = 1\n', '    return x\n', 'def f():\n', '# note\n', '\t\n']
+    a = [rng.choice(pool) if rng.random() < 0.6 else f"line {rng.randint(0, 40)}\n" for _ in range(rng.randint(0, 60))]
+    b = []
+    for line in a:
+        roll = rng.random()
+        if roll < 0.1:
+            continue
+        b.append(line if roll < 0.85 else f"changed {rng.randint(0, 9)}\n")
+        if rng.random() < 0.05:
+            b.append(rng.choice(pool))
+    for lines in (a, b):
+        if lines and rng.random() < 0.3:
+            lines[-1] = lines[-1].rstrip('\n')
+    return a, b
+
+
+OPTIONS = [
+    {},
+    {'n': 0},
+    {'n': 10},
+    {'isjunk': lambda line: not line.strip()},
+    {'autojunk': False},
+    {'compact': True},
+    {'window_size': 7},
+    {'brace_scope_context': 3},
+    {'unique_anchors': True},
+    {'gap_penalty': 0.5},
+    {'neighbor_tiebreak': True},
+    {'min_hunk_lines': 6},
+    {'hunk_checksums': True},
+    {'symmetric_junk': True},
+    {'max_occurrences_per_element': 2},
+    {'adaptive_context': (1, 5)},
+    {'auto_context': 20},
+    {'comment_prefixes': ['#']},
+    {'comment_prefixes': ['#'], 'block_comment': ('/*', '*/')},
+    {'n': 1, 'compact': True, 'unique_anchors': True, 'min_hunk_lines': 4, 'hunk_checksums': True},
+]
+
+
+@pytest.mark.parametrize("seed", range(30))
+def test_random_diffs_round_trip(seed):
+    """Test that random edits round-trip under each option."""
+    rng = random.Random(seed)
+    a, b = random_pair(rng)
+    for options in OPTIONS:
+        assert verify_diff(a, b, **options), options
+
+
+def test_edge_cases():
+    """Test empty, identical and fully replaced inputs."""
+    assert verify_diff([], [])
+    assert verify_diff([], ['a\n'])
+    assert verify_diff(['a\n'], [])
+    assert verify_diff(['a\n', 'b'], ['a\n', 'b'])
+    assert verify_diff(['a\n', 'b\n'], ['c\n', 'd'])
+    assert verify_diff(['x'], ['x\n'])
+
+
+def test_line_endings_compared_exactly():
+    """Test that CRLF and LF lines round-trip as they are."""
+    assert verify_diff(['a\r\n', 'b\r\n'], ['a\n', 'b\r\n', 'c'])
+
+
+def test_invalid_option():
+    """Test that an invalid option raises instead of returning False."""
+    with pytest.raises(ValueError):
+        verify_diff(['a\n'], ['b\n'], window_size=0)
+    with pytest.raises(ValueError):
+        verify_diff(['a\n'], ['b\n'], gap_penalty=-1.0)
+
+
+def test_display_options_return_false():
+    """Test that options changing what the diff shows give False rather than raising."""
+    a = ['Hello\n', 'world\n']
+    b = ['hello\n', 'there\n']
+    assert verify_diff(a, b)
+    assert not verify_diff(a, b, normalize=['lowercase'])
+    assert not verify_diff(['  x\n', '  y\n'], ['x\n', 'y\n'], dedent=True)
+    assert not verify_diff(['x 1.0\n'], ['x 1.01\n'], numeric_tolerance=(0.1, 0.0))
+    assert not verify_diff(a, b, show_line_numbers=True)
+
+
+def test_unknown_option():
+    """Test that an option unified_diff doesn't take is a TypeError."""
+    with pytest.raises(TypeError):
+        verify_diff(['a\n'], ['b\n'], fromfile='a')
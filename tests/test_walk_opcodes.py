@@ -0,0 +1,77 @@
+"""Test the walk_opcodes function."""
+
+import pytest
+import difflib
+import random
+from difflib_rs import walk_opcodes
+
+
+def collect(a, b, **kwargs):
+    """Walk all opcodes into a list."""
+    seen = []
+    assert walk_opcodes(a, b, lambda *op: seen.append(op), **kwargs) is True
+    return seen
+
+
+@pytest.mark.parametrize("seed", range(10))
+def test_walk_opcodes_matches_python(seed):
+    """Test that the visited opcodes equal SequenceMatcher.get_opcodes()."""
+    random.seed(seed)
+    a = [f"line{random.randint(0, 10)}" for _ in range(50)]
+    b = [f"line{random.randint(0, 10)}" for _ in range(50)]
+    assert collect(a, b) == difflib.SequenceMatcher(None, a, b).get_opcodes()
+
+
+def test_walk_opcodes_with_isjunk():
+    """Test that isjunk is honored like SequenceMatcher's."""
+    a = ['', 'x', '', 'y', '', 'z']
+    b = ['x', '', '', 'y', 'z', '']
+    isjunk = lambda line: line == ''
+    assert collect(a, b, isjunk=isjunk) == difflib.SequenceMatcher(isjunk, a, b).get_opcodes()
+
+
+def test_walk_opcodes_stops_on_false():
+    """Test that a falsy return value stops the walk early."""
+    a = ['a', 'b', 'c', 'd', 'e']
+    b = ['a', 'x', 'c', 'y', 'e']
+    seen = []
+
+    def callback(tag, i1, i2, j1, j2):
+        seen.append(tag)
+        return tag != 'replace'
+
+    assert walk_opcodes(a, b, callback) is False
+    assert seen == ['equal', 'replace']
+
+
+def test_walk_opcodes_none_continues():
+    """Test that returning None (no return statement) keeps walking."""
+    calls = []
+    assert walk_opcodes(['a', 'b'], ['a', 'c'], lambda *op: calls.append(op)) is True
+    assert len(calls) == 2
+
+
+def test_walk_opcodes_empty():
+    """Test that empty inputs never call the callback."""
+    assert walk_opcodes([], [], lambda *op: pytest.fail("called")) is True
+
+
+def test_walk_opcodes_exception_propagates():
+    """Test that exceptions raised by the callback reach the caller."""
+    def callback(*op):
+        raise KeyError("stop")
+
+    with pytest.raises(KeyError):
+        walk_opcodes(['a'], ['b'], callback)
+
+
+@pytest.mark.parametrize("autojunk", [True, False])
+def test_walk_opcodes_autojunk_option(autojunk):
+    """Test that autojunk=False keeps popular lines as anchors, like CPython."""
+    b = []
+    for i in range(60):
+        b += [f"def f{i}():", f"    return {i}", "}", "}", "}"]
+    a = ["}"] * 5 + b[5:]
+    a[150:160] = ["}"] * 10
+    expected = difflib.SequenceMatcher(None, a, b, autojunk=autojunk).get_opcodes()
+    assert collect(a, b, autojunk=autojunk) == expected
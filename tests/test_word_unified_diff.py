@@ -0,0 +1,48 @@
+"""Test word-level unified diffs."""
+
+from difflib_rs import word_unified_diff
+
+A = "The quick brown fox jumps over the lazy dog near the river bank today."
+
+
+def test_replace_in_context():
+    """Test that a changed word is shown with n words of context."""
+    b = A.replace("lazy", "sleepy")
+    assert word_unified_diff(A, b, n=2) == [
+        "@@ -6,5 +6,5 @@",
+        "over the [-lazy-]{+sleepy+} dog near",
+    ]
+
+
+def test_header_positions_are_split_indices():
+    """Test that header ranges are 1-based positions in str.split()."""
+    b = A.replace("fox", "cat").replace("river", "lake")
+    diff = word_unified_diff(A, b, n=1)
+    assert diff[0::2] == ["@@ -3,3 +3,3 @@", "@@ -11,3 +11,3 @@"]
+    assert A.split()[2:5] == ["brown", "fox", "jumps"]
+    assert diff[1::2] == ["brown [-fox-]{+cat+} jumps", "the [-river-]{+lake+} bank"]
+
+
+def test_insert_and_delete():
+    """Test pure insertions and deletions keep their surrounding spaces."""
+    assert word_unified_diff("a b c", "a b x y c", n=1) == ["@@ -2,2 +2,4 @@", "b {+x y+} c"]
+    assert word_unified_diff("a b x y c", "a b c", n=1) == ["@@ -2,4 +2,2 @@", "b [-x y-] c"]
+
+
+def test_whitespace_is_not_a_change():
+    """Test that reflowing text gives no diff."""
+    assert word_unified_diff("one two\nthree", "one  two three\n") == []
+
+
+def test_original_whitespace_is_kept():
+    """Test that context keeps the newlines of the source."""
+    a = "first line\nsecond line\nthird line"
+    b = "first line\nsecond change\nthird line"
+    assert word_unified_diff(a, b, n=2) == ["@@ -2,5 +2,5 @@", "line\nsecond [-line-]{+change+}\nthird line"]
+
+
+def test_empty_texts():
+    """Test diffs against empty or whitespace-only texts."""
+    assert word_unified_diff("", "") == []
+    assert word_unified_diff("", "new words") == ["@@ -0,0 +1,2 @@", "{+new words+}"]
+    assert word_unified_diff("old", "  ") == ["@@ -1 +0,0 @@", "[-old-]"]